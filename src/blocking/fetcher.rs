@@ -3,7 +3,7 @@ use std::ops::{Deref, DerefMut};
 use reqwest::Client;
 use url::Url;
 
-use crate::{IdBuf, Result};
+use crate::{DiagnosticsBundle, IdBuf, ProxyPool, Result};
 use crate::blocking::descrambler::VideoDescrambler;
 use crate::fetcher::VideoFetcher as AsyncVideoFetcher;
 
@@ -36,6 +36,30 @@ impl VideoFetcher {
         Self(AsyncVideoFetcher::from_id_with_client(video_id, client))
     }
 
+    /// A synchronous wrapper around [`VideoFetcher::with_gl`](crate::VideoFetcher::with_gl).
+    #[inline]
+    pub fn with_gl(self, gl: &str) -> Self {
+        Self(self.0.with_gl(gl))
+    }
+
+    /// A synchronous wrapper around [`VideoFetcher::with_proxy`](crate::VideoFetcher::with_proxy).
+    #[inline]
+    pub fn with_proxy(self, proxy: reqwest::Proxy) -> Result<Self> {
+        Ok(Self(self.0.with_proxy(proxy)?))
+    }
+
+    /// A synchronous wrapper around [`VideoFetcher::with_proxy_pool`](crate::VideoFetcher::with_proxy_pool).
+    #[inline]
+    pub fn with_proxy_pool(self, pool: ProxyPool) -> Self {
+        Self(self.0.with_proxy_pool(pool))
+    }
+
+    /// A synchronous wrapper around [`VideoFetcher::with_diagnostics`](crate::VideoFetcher::with_diagnostics).
+    #[inline]
+    pub fn with_diagnostics(self, handler: impl Fn(DiagnosticsBundle) + Send + Sync + 'static) -> Self {
+        Self(self.0.with_diagnostics(handler))
+    }
+
     /// A synchronous wrapper around [`VideoFetcher::fetch`](crate::VideoFetcher::fetch).
     #[inline]
     pub fn fetch(self) -> Result<VideoDescrambler> {
@@ -1,88 +1,91 @@
+use chrono::{DateTime, Utc};
+use futures::{Stream, TryStreamExt};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
 use regex::Regex;
+use reqwest::{Client, Response, StatusCode};
 use serde::Serialize;
-use crate::{channel_info::{channel_video::ChannelVideo, ChannelInfo}, helper::{initial_data, parese_channel_metadata, parese_channel_videos}, playlist::crate_client, Playlist};
-
+use serde_json::Value;
+use crate::{channel_info::{channel_about::ChannelAbout, channel_playlist::ChannelPlaylist, channel_short::ChannelShort, channel_stream::ChannelStream, channel_video::{ChannelVideo, VideoSort}, community_post::CommunityPost, ChannelInfo}, helper::{self, initial_data, parese_channel_metadata, parese_channel_videos}, playlist::crate_client, playlist_info::req_json::{NextReq, ResolveUrlReq}, ChannelId, ChannelIdBuf, Id, Playlist};
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
 
+#[derive(Clone, Serialize, derivative::Derivative)]
+#[derivative(Debug, PartialEq)]
 pub struct Channel {
     channel_info: ChannelInfo,
-    videos: Vec<ChannelVideo>
+    videos: Vec<ChannelVideo>,
+    #[serde(skip)]
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    client: Client,
 }
 
 
 impl Channel {
+    /// Requests a channel from any url, which [`ChannelResolver::resolve`] accepts.
+    ///
+    /// ### Errors
+    /// - When [`ChannelResolver::resolve`] fails.
+    /// - When [`Channel::from_channel_id`] fails.
     pub async fn from_url(url: url::Url) -> crate::Result<Self> {
-        let list_regex = [
-            r"/c/([%\d\w_\-]+)(\/.*)?",
-            r"/channel/([%\w\d_\-]+)(\/.*)?",
-            r"/u/([%\d\w_\-]+)(\/.*)?",
-            r"/user/([%\w\d_\-]+)(\/.*)?",
-            r"/@([%\w\d_\-]+)(\/.*)?",
-        ];
-        for regex in list_regex {
-            let re = Regex::new(regex).unwrap();
-            let vec = re.captures(url.path());
-            if vec.is_none() {
-                continue;
-            }
-            let vec = vec.unwrap();
-            let id_raw = vec.get(1);
-            if id_raw.is_none() {
-                continue;
-            }
-            let id = id_raw.unwrap().as_str();
-            return Self::from_id(id).await
-        }
-        Err(crate::Error::BadIdFormat)
+        Self::from_id(url.as_str()).await
     }
 
+    /// Requests a channel from a channel id, handle, custom url, legacy username, or video id.
+    /// Everything, which isn't a canonical [`ChannelId`] already, is resolved using the global
+    /// [`ChannelResolver`].
+    ///
+    /// ### Errors
+    /// - When [`ChannelResolver::resolve`] fails.
+    /// - When [`Channel::from_channel_id`] fails.
     pub async fn from_id(id: &str) -> crate::Result<Self> {
-        let client = crate_client().unwrap();
-        let list_channel_url = [
-            "/c/",
-            "/channel/",
-            "/u/",
-            "/user/",
-            "/@", 
-        ];
-        let mut page_raw = "".to_string();
-        for url in list_channel_url {
-            let url = format!("https://www.youtube.com{url}{id}/videos");
-            let req = client.get(url.clone()).send().await?;
-            if !req.status().is_success() {
-                continue;
-            }
-            page_raw = req.text().await?;
-            if page_raw.matches("Something went wrong").count() == 4 {
-                page_raw = "".to_string();
-                continue;
-            }
-            break;
-        }
-        if page_raw.is_empty() {
+        let channel_id = ChannelResolver::global().resolve(id).await?;
+        Self::from_channel_id(channel_id.as_borrowed()).await
+    }
+
+    /// Requests a channel and all of its videos.
+    ///
+    /// ### Errors
+    /// - When the channel does not exist.
+    /// - When the channel page has an unexpected format.
+    pub async fn from_channel_id(channel_id: ChannelId<'_>) -> crate::Result<Self> {
+        let url = format!("{}/videos", channel_id.channel_url());
+        let (req, client) = crate::proxy::send(|client| client.get(url.as_str())).await?;
+        if req.status() == StatusCode::NOT_FOUND {
             return Err(crate::Error::BadIdFormat);
         }
-        let init_obj = initial_data(&page_raw).unwrap();
+        let page_raw = crate::Error::check_response(req)?.text().await?;
+        let init_obj = initial_data(&page_raw)
+            .ok_or_else(|| crate::Error::layout_change("the channel page", "ytInitialData"))?;
         let channel_info = parese_channel_metadata(&init_obj)?;
         let channel_name = channel_info.title.clone();
-        let videos = Self::get_videos(init_obj, channel_name).await?;
-        Ok(Self { videos, channel_info })
+        let videos = Self::get_videos(&client, init_obj, channel_name).await?;
+        Ok(Self { videos, channel_info, client })
     }
 
-    pub(crate) async fn get_videos(init_obj: String, channel_name: String) -> crate::Result<Vec<ChannelVideo>> {
+    /// Creates a channel from the `ytInitialData` of its Videos tab, without performing any
+    /// requests. Only the first page of videos is part of `ytInitialData`.
+    ///
+    /// ### Errors
+    /// - When `initial_data` has an unexpected format.
+    pub fn from_initial_data(initial_data: &str) -> crate::Result<Self> {
+        let channel_info = parese_channel_metadata(initial_data)?;
+        let (videos, _) = parese_channel_videos(initial_data, channel_info.title.clone())?;
+        Ok(Self { channel_info, videos, client: crate_client()? })
+    }
+
+    /// Parses the videos of `init_obj`, and requests all following pages with `client`.
+    pub(crate) async fn get_videos(client: &Client, init_obj: String, channel_name: String) -> crate::Result<Vec<ChannelVideo>> {
         let mut vec_videos = Vec::new();
         let mut init_obj = init_obj;
         loop {
-            let (videos, continuation) = parese_channel_videos(&init_obj, channel_name.clone());
+            let (videos, continuation) = parese_channel_videos(&init_obj, channel_name.clone())?;
             vec_videos.extend(videos);
-            if continuation.is_none() {
-                break;
+            match continuation {
+                Some(continuation) => init_obj = Playlist::get_from_continuation(client, &continuation).await?,
+                None => break,
             }
-            let continuation = continuation.unwrap();
-            let init_obj_r = Playlist::get_from_continuation(&continuation).await;
-            if init_obj_r.is_err() { break; }
-            init_obj = init_obj_r.unwrap();
         }
         Ok(vec_videos)
     }
@@ -95,4 +98,284 @@ impl Channel {
         self.videos.clone()
     }
 
+    /// The url of a tab of the channel, i.e. `shorts`.
+    fn tab_url(&self, tab: &str) -> String {
+        format!("{}/{tab}", self.channel_info.channel_url.trim_end_matches('/'))
+    }
+
+    /// Lazily requests the videos of the channel in the given order, page by page.
+    pub fn videos_stream(&self, sort: VideoSort) -> impl Stream<Item=crate::Result<ChannelVideo>> {
+        let author = self.channel_info.title.clone();
+        // the latest videos are the default, so no chip has to be selected
+        let chip = Some(sort.chip()).filter(|_| sort != VideoSort::Latest);
+
+        helper::channel_tab_stream(self.client.clone(), self.tab_url("videos"), chip, move |item| {
+            Ok(helper::parse_channel_video(item)?.map(|mut video| video.add_author(author.clone())))
+        })
+    }
+
+    /// Lazily requests the latest videos of the channel, until the first one, which was published
+    /// before `since`. No further pages are requested after that.
+    ///
+    /// Since YouTube mostly shows relative dates, like `3 weeks ago`, the comparison is based on
+    /// [`ChannelVideo::is_published_since`]. Videos without an estimate (i.e. upcoming streams)
+    /// never end the stream.
+    pub fn videos_since(&self, since: DateTime<Utc>) -> impl Stream<Item=crate::Result<ChannelVideo>> {
+        self
+            .videos_stream(VideoSort::Latest)
+            .try_take_while(move |video| futures::future::ready(Ok(video.is_published_since(since))))
+    }
+
+    /// Lazily requests the Shorts of the channel, page by page.
+    pub fn shorts(&self) -> impl Stream<Item=crate::Result<ChannelShort>> {
+        helper::channel_tab_stream(self.client.clone(), self.tab_url("shorts"), None, |item| Ok(helper::parse_channel_short(item)))
+    }
+
+    /// Lazily requests the live streams of the channel, page by page. This includes streams, which
+    /// are currently live, scheduled streams, and past streams.
+    pub fn streams(&self) -> impl Stream<Item=crate::Result<ChannelStream>> {
+        helper::channel_tab_stream(self.client.clone(), self.tab_url("streams"), None, |item| Ok(helper::parse_channel_stream(item)))
+    }
+
+    /// Lazily requests the playlists of the channel, page by page.
+    pub fn playlists(&self) -> impl Stream<Item=crate::Result<ChannelPlaylist>> {
+        helper::channel_tab_stream(self.client.clone(), self.tab_url("playlists"), None, |item| Ok(helper::parse_channel_playlist(item)))
+    }
+
+    /// Lazily requests the community posts of the channel, page by page.
+    pub fn community_posts(&self) -> impl Stream<Item=crate::Result<CommunityPost>> {
+        helper::channel_tab_stream(self.client.clone(), self.tab_url("community"), None, |item| Ok(helper::parse_community_post(item)))
+    }
+
+    /// Requests the About tab of the channel.
+    ///
+    /// ### Errors
+    /// - When a request fails.
+    /// - When the About tab has an unexpected format.
+    pub async fn about(&self) -> crate::Result<ChannelAbout> {
+        let body = self.client
+            .get(self.tab_url("about"))
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let data: Value = serde_json::from_str(
+            &initial_data(&body)
+                .ok_or_else(|| crate::Error::layout_change("the about tab", "ytInitialData"))?
+        )?;
+
+        if let Some(about) = helper::parse_channel_about(&data) {
+            return Ok(about);
+        }
+        // newer pages load the about panel lazily
+        let continuation = helper::channel_about_continuation(&data)
+            .ok_or_else(|| crate::Error::layout_change(
+                "the about tab",
+                "onResponseReceivedEndpoints[].showEngagementPanelEndpoint.continuationItemRenderer",
+            ))?;
+        let response = helper::get_continuation(&self.client, "browse", &continuation).await?;
+        helper::parse_channel_about(&response)
+            .ok_or_else(|| crate::Error::layout_change("the about response", "aboutChannelViewModel"))
+    }
+
+}
+
+/// Resolves handles (`@name`), custom urls (`/c/name`), legacy usernames (`/user/name`) and videos
+/// to the canonical [`ChannelId`] of a channel, using InnerTube's `navigation/resolve_url`
+/// endpoint.
+///
+/// Resolved ids are cached, so each input is only requested once. The cache holds at most
+/// [`DEFAULT_RESOLVER_CAPACITY`] inputs (or the capacity passed to
+/// [`ChannelResolver::with_capacity`]), and evicts the oldest ones first. Clones share the same
+/// cache.
+#[derive(Clone, Debug)]
+pub struct ChannelResolver {
+    cache: Arc<Mutex<ResolverCache>>,
+}
+
+/// The number of inputs, the cache of a [`ChannelResolver`] holds, if not configured with
+/// [`ChannelResolver::with_capacity`].
+pub const DEFAULT_RESOLVER_CAPACITY: usize = 10_000;
+
+#[derive(Debug)]
+struct ResolverCache {
+    ids: HashMap<String, ChannelIdBuf>,
+    // the inputs in the order they were inserted, so the oldest ones can be evicted first
+    order: VecDeque<String>,
+    capacity: usize,
+}
+
+static GLOBAL_RESOLVER: Lazy<ChannelResolver> = Lazy::new(ChannelResolver::default);
+
+impl Default for ChannelResolver {
+    #[inline]
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_RESOLVER_CAPACITY)
+    }
+}
+
+impl ChannelResolver {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a resolver, which caches at most `capacity` inputs.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let cache = ResolverCache { ids: HashMap::new(), order: VecDeque::new(), capacity };
+        Self { cache: Arc::new(Mutex::new(cache)) }
+    }
+
+    /// The resolver, which is used by [`Channel::from_id`] and [`Channel::from_url`].
+    #[inline]
+    pub fn global() -> &'static Self {
+        &GLOBAL_RESOLVER
+    }
+
+    /// The cached id for `input`, if it was resolved before.
+    pub fn cached(&self, input: &str) -> Option<ChannelIdBuf> {
+        self.cache
+            .lock()
+            .ok()?
+            .ids
+            .get(input.trim())
+            .cloned()
+    }
+
+    /// Adds an already known id to the cache. If the cache is full, the oldest input is evicted.
+    pub fn insert(&self, input: &str, channel_id: ChannelIdBuf) {
+        let mut cache = match self.cache.lock() {
+            Ok(cache) => cache,
+            Err(_) => return,
+        };
+        let input = input.trim();
+        if let Some(cached) = cache.ids.get_mut(input) {
+            *cached = channel_id;
+            return;
+        }
+        if cache.capacity == 0 {
+            return;
+        }
+        while cache.ids.len() >= cache.capacity {
+            match cache.order.pop_front() {
+                Some(oldest) => cache.ids.remove(&oldest),
+                None => break,
+            };
+        }
+        cache.order.push_back(input.to_owned());
+        cache.ids.insert(input.to_owned(), channel_id);
+    }
+
+    /// Resolves `input` to the canonical id of a channel.
+    ///
+    /// `input` can be a channel id, a handle (with or without the leading `@`), a custom name,
+    /// a legacy username, a video id, or the url of any of them. Canonical ids and `/channel/`
+    /// urls are resolved without any requests.
+    ///
+    /// ### Errors
+    /// - When a request fails.
+    /// - When `input` does not belong to any channel ([`Error::BadIdFormat`](crate::Error::BadIdFormat)).
+    pub async fn resolve(&self, input: &str) -> crate::Result<ChannelIdBuf> {
+        static CHANNEL_URL: Lazy<Regex> = Lazy::new(||
+            Regex::new(r"(?:^|/)channel/(UC[a-zA-Z0-9_-]{22})(?:[/?#]|$)").unwrap()
+        );
+
+        let input = input.trim();
+        if let Ok(channel_id) = ChannelId::from_str(input) {
+            return Ok(channel_id.into_owned());
+        }
+        if let Some(captures) = CHANNEL_URL.captures(input) {
+            return ChannelIdBuf::from_string(captures[1].to_owned()).map_err(|_| crate::Error::BadIdFormat);
+        }
+        if let Some(channel_id) = self.cached(input) {
+            return Ok(channel_id);
+        }
+
+        // the first request is sent through the proxy pool, the following ones with its client
+        let mut client = None;
+        let is_url = input.contains("youtube.com/") || input.contains("youtu.be/");
+        let video_id = Id::from_raw(input).ok();
+
+        let mut channel_id = None;
+        if !is_url || video_id.is_none() {
+            for url in Self::candidate_urls(input) {
+                channel_id = Self::resolve_url(&mut client, &url).await?;
+                if channel_id.is_some() {
+                    break;
+                }
+            }
+        }
+        if let (None, Some(video_id)) = (&channel_id, video_id) {
+            channel_id = Self::resolve_video(&mut client, video_id).await?;
+        }
+
+        let channel_id = channel_id.ok_or(crate::Error::BadIdFormat)?;
+        self.insert(input, channel_id.clone());
+        Ok(channel_id)
+    }
+
+    /// The urls, which could belong to `input`, in the order they are tried.
+    fn candidate_urls(input: &str) -> Vec<String> {
+        if input.contains("youtube.com/") {
+            return match input.starts_with("http") {
+                true => vec![input.to_owned()],
+                false => vec![format!("https://{input}")],
+            };
+        }
+        if input.starts_with('@') {
+            return vec![format!("https://www.youtube.com/{input}")];
+        }
+        ["@", "c/", "user/"]
+            .iter()
+            .map(|prefix| format!("https://www.youtube.com/{prefix}{input}"))
+            .collect()
+    }
+
+    async fn resolve_url(client: &mut Option<Client>, url: &str) -> crate::Result<Option<ChannelIdBuf>> {
+        let response = Self::post(
+            client,
+            "https://www.youtube.com/youtubei/v1/navigation/resolve_url?prettyPrint=false",
+            &ResolveUrlReq::new(url),
+        ).await?;
+        // unknown urls are answered with `400 Bad Request` or `404 Not Found`
+        if matches!(response.status(), StatusCode::BAD_REQUEST | StatusCode::NOT_FOUND) {
+            return Ok(None);
+        }
+        let response: Value = crate::Error::check_response(response)?
+            .json()
+            .await?;
+
+        Ok(response["endpoint"]["browseEndpoint"]["browseId"]
+            .as_str()
+            .and_then(|browse_id| ChannelIdBuf::from_string(browse_id.to_owned()).ok()))
+    }
+
+    async fn resolve_video(client: &mut Option<Client>, video_id: Id<'_>) -> crate::Result<Option<ChannelIdBuf>> {
+        let response = Self::post(
+            client,
+            "https://www.youtube.com/youtubei/v1/next?prettyPrint=false",
+            &NextReq::new(video_id.as_str()),
+        ).await?;
+        let response: Value = crate::Error::check_response(response)?
+            .json()
+            .await?;
+
+        Ok(helper::find_key(&response, "videoOwnerRenderer")
+            .and_then(|owner| owner["navigationEndpoint"]["browseEndpoint"]["browseId"].as_str())
+            .and_then(|browse_id| ChannelIdBuf::from_string(browse_id.to_owned()).ok()))
+    }
+
+    /// Sends the first request of a resolution through the proxy pool, and the following ones with
+    /// the client of the proxy, which answered the first one.
+    async fn post(client: &mut Option<Client>, url: &str, body: &impl Serialize) -> crate::Result<Response> {
+        match client {
+            Some(client) => Ok(client.post(url).json(body).send().await?),
+            None => {
+                let (response, first_client) = crate::proxy::send(|client| client.post(url).json(body)).await?;
+                *client = Some(first_client);
+                Ok(response)
+            }
+        }
+    }
 }
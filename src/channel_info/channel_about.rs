@@ -0,0 +1,42 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// The information of the About tab of a channel.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChannelAbout {
+    pub description: String,
+    pub subscriber_count: Option<u64>,
+    pub view_count: Option<u64>,
+    pub video_count: Option<u64>,
+    pub joined_date: Option<NaiveDate>,
+    pub country: Option<String>,
+    pub links: Vec<ChannelLink>,
+}
+
+impl ChannelAbout {
+    /// Parses the `ytInitialData` of the About tab, or the raw `youtubei/v1/browse` response of its
+    /// [`continuation`](ChannelAbout::continuation). Returns `None`, if the About panel is loaded
+    /// lazily.
+    ///
+    /// ### Errors
+    /// - When the json is invalid.
+    pub fn from_json(json: &str) -> crate::Result<Option<Self>> {
+        Ok(crate::helper::parse_channel_about(&serde_json::from_str(json)?))
+    }
+
+    /// The continuation token of the About panel, which newer channel pages load lazily.
+    ///
+    /// ### Errors
+    /// - When the json is invalid.
+    pub fn continuation(json: &str) -> crate::Result<Option<String>> {
+        Ok(crate::helper::channel_about_continuation(&serde_json::from_str(json)?))
+    }
+}
+
+/// A link, which is shown on the About tab of a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChannelLink {
+    pub title: String,
+    /// The url, without YouTube's redirect.
+    pub url: String,
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+use crate::video_info::player_response::video_details::Thumbnail;
+use crate::PlaylistIdBuf;
+
+/// A playlist of the Playlists tab of a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChannelPlaylist {
+    pub playlist_id: PlaylistIdBuf,
+    pub title: String,
+    pub video_count: Option<u64>,
+    pub thumbnails: Vec<Thumbnail>,
+}
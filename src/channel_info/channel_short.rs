@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{video_info::player_response::video_details::Thumbnail, IdBuf};
+
+/// A video of the Shorts tab of a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChannelShort {
+    pub video_id: IdBuf,
+    pub title: String,
+    /// The abbreviated views, like `1.2K views`.
+    pub view_count_text: Option<String>,
+    pub thumbnails: Vec<Thumbnail>,
+}
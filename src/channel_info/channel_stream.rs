@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{video_info::player_response::video_details::Thumbnail, IdBuf};
+
+/// A video of the Live tab of a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChannelStream {
+    pub video_id: IdBuf,
+    pub title: String,
+    pub status: StreamStatus,
+    /// Only available for past streams.
+    pub length_seconds: Option<u64>,
+    pub thumbnails: Vec<Thumbnail>,
+    /// The abbreviated views, like `1.2K views`, or the number of viewers for live streams.
+    pub view_count_text: Option<String>,
+    /// For past streams, i.e. `Streamed 2 days ago`.
+    pub published_time_text: Option<String>,
+}
+
+/// Whether a [`ChannelStream`] is live, scheduled, or already over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StreamStatus {
+    Live,
+    Upcoming {
+        /// The scheduled start, if YouTube provides one.
+        start_time: Option<DateTime<Utc>>,
+    },
+    Past,
+}
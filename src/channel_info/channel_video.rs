@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{video_info::player_response::video_details::Thumbnail, IdBuf};
@@ -8,17 +9,74 @@ pub struct ChannelVideo {
     pub video_id: IdBuf,
     #[serde(deserialize_with = "deserialize_run")]
     pub title: String,
+    /// `None` for live streams and premieres, which have not ended yet.
     #[serde(rename(deserialize = "lengthText"))]
-    #[serde(deserialize_with = "deserialize_length")]
-    pub length_seconds: u64,
+    #[serde(default, deserialize_with = "deserialize_length")]
+    pub length_seconds: Option<u64>,
     #[serde(rename = "thumbnail")]
     #[serde(deserialize_with = "Thumbnail::deserialize_vec")]
     pub thumbnails: Vec<Thumbnail>,
     #[serde(default)]
     pub author: String,
+    /// `None` for live streams and premieres, which have not started yet.
+    #[serde(rename(deserialize = "publishedTimeText"))]
+    #[serde(default, deserialize_with = "deserialize_published")]
+    pub published: Option<PublishedTime>,
+}
+
+/// The time a video was published, as YouTube shows it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PublishedTime {
+    /// The raw, mostly relative, text, like `3 weeks ago`.
+    pub text: String,
+    /// A best-effort estimate based on `text` and the time it was requested. Relative months and
+    /// years are counted as 30 and 365 days.
+    pub estimate: Option<DateTime<Utc>>,
+}
+
+/// The order of the videos of a channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VideoSort {
+    #[default]
+    Latest,
+    Popular,
+    Oldest,
+}
+
+impl VideoSort {
+    /// The text and the position of the filter chip of this order.
+    pub(crate) fn chip(self) -> (&'static str, usize) {
+        match self {
+            Self::Latest => ("Latest", 0),
+            Self::Popular => ("Popular", 1),
+            Self::Oldest => ("Oldest", 2),
+        }
+    }
+
+    /// Extracts the continuation token of the filter chip of this order from the `ytInitialData`
+    /// of the Videos tab. The chip is found by its text, or by its position, if the page is not
+    /// in English.
+    ///
+    /// ### Errors
+    /// - When `initial_data` is no valid json.
+    pub fn continuation(self, initial_data: &str) -> crate::Result<Option<String>> {
+        let (text, position) = self.chip();
+        Ok(crate::helper::chip_continuation(&serde_json::from_str(initial_data)?, text, position))
+    }
 }
 
 impl ChannelVideo {
+    /// Whether the video was published at or after `since`, according to
+    /// [`PublishedTime::estimate`]. Videos without an estimate (i.e. upcoming streams) count as
+    /// published since then.
+    #[inline]
+    pub fn is_published_since(&self, since: DateTime<Utc>) -> bool {
+        self.published
+            .as_ref()
+            .and_then(|published| published.estimate)
+            .is_none_or(|estimate| estimate >= since)
+    }
+
     pub(crate) fn add_author(&mut self, author: String) -> Self {
         self.author = author;
         self.to_owned()
@@ -48,7 +106,16 @@ struct Run {
     text: String,
 }
 
-fn deserialize_length<'de, D>(deserializer: D) -> Result<u64, D::Error>
+fn deserialize_published<'de, D>(deserializer: D) -> Result<Option<PublishedTime>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let text = crate::serde_impl::text::deserialize(deserializer)?;
+    let estimate = crate::helper::parse_relative_time(&text, DateTime::<Utc>::from(std::time::SystemTime::now()));
+    Ok(Some(PublishedTime { text, estimate }))
+}
+
+fn deserialize_length<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -59,16 +126,8 @@ where
     }
 
     let index = Index::deserialize(deserializer)?;
-
-    let parts: Vec<&str> = index.simple_text.split(':').collect();
-    let mut out_sec = 0;
-    let mut multiplier = 1;
-
-    for part in parts.iter().rev() {
-        let value: u64 = part.parse().unwrap();
-        out_sec += value * multiplier;
-        multiplier *= 60;
-    }
-
-    Ok(out_sec)
+    crate::helper::parse_length(&index.simple_text).map(Some).ok_or_else(|| serde::de::Error::invalid_value(
+        serde::de::Unexpected::Str(&index.simple_text),
+        &"a length like `1:02:03`",
+    ))
 }
\ No newline at end of file
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{video_info::player_response::video_details::{TextRun, Thumbnail}, IdBuf};
+
+/// A post of the Community tab of a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CommunityPost {
+    pub id: String,
+    pub text: Vec<TextRun>,
+    /// Each image in all of its available sizes.
+    pub images: Vec<Vec<Thumbnail>>,
+    pub poll: Option<Poll>,
+    /// The video, which is attached to the post.
+    pub video_id: Option<IdBuf>,
+    pub like_count: Option<u64>,
+    pub published_time_text: Option<String>,
+}
+
+/// A poll, which is attached to a [`CommunityPost`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Poll {
+    pub choices: Vec<String>,
+    /// The abbreviated votes, like `1.2K votes`.
+    pub total_votes_text: Option<String>,
+}
+
+impl CommunityPost {
+    /// The text of the post without any formatting.
+    #[inline]
+    pub fn plain_text(&self) -> String {
+        self.text
+            .iter()
+            .map(|run| run.text.as_str())
+            .collect()
+    }
+}
@@ -1,8 +1,23 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
+use crate::helper::{
+    parse_channel_playlist, parse_channel_short, parse_channel_stream, parse_channel_tab_page,
+    parse_channel_video, parse_community_post,
+};
 use crate::video_info::player_response::video_details::Thumbnail;
+use channel_playlist::ChannelPlaylist;
+use channel_short::ChannelShort;
+use channel_stream::ChannelStream;
+use channel_video::ChannelVideo;
+use community_post::CommunityPost;
 
+pub mod channel_about;
+pub mod channel_playlist;
+pub mod channel_short;
+pub mod channel_stream;
 pub mod channel_video;
+pub mod community_post;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -15,4 +30,63 @@ pub struct ChannelInfo {
     #[serde(deserialize_with = "Thumbnail::deserialize_vec")]
     pub thumbnail: Vec<Thumbnail>,
     pub available_countries: Vec<String>
-}
\ No newline at end of file
+}
+/// An item of a channel tab, like a [`ChannelShort`](channel_short::ChannelShort) of the Shorts
+/// tab.
+pub trait ChannelTabItem: Sized {
+    /// Parses a single item of the tab, or returns `None`, if the item has a different type.
+    ///
+    /// ### Errors
+    /// - When the item has the type, but an unexpected format.
+    fn from_item(item: &Value) -> crate::Result<Option<Self>>;
+}
+
+/// A single page of a channel tab.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChannelTabPage<T> {
+    pub items: Vec<T>,
+    /// The token of the next page.
+    pub continuation: Option<String>,
+}
+
+impl<T: ChannelTabItem> ChannelTabPage<T> {
+    /// Parses the `ytInitialData` of a channel tab, or a raw `youtubei/v1/browse` continuation
+    /// response of it. Items of other types are skipped.
+    ///
+    /// ### Errors
+    /// - When the json is invalid, or has an unexpected format.
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        let (items, continuation) = parse_channel_tab_page(&serde_json::from_str(json)?, &T::from_item)?;
+        Ok(Self { items, continuation })
+    }
+}
+
+impl ChannelTabItem for ChannelVideo {
+    fn from_item(item: &Value) -> crate::Result<Option<Self>> {
+        parse_channel_video(item)
+    }
+}
+
+impl ChannelTabItem for ChannelShort {
+    fn from_item(item: &Value) -> crate::Result<Option<Self>> {
+        Ok(parse_channel_short(item))
+    }
+}
+
+impl ChannelTabItem for ChannelStream {
+    fn from_item(item: &Value) -> crate::Result<Option<Self>> {
+        Ok(parse_channel_stream(item))
+    }
+}
+
+impl ChannelTabItem for ChannelPlaylist {
+    fn from_item(item: &Value) -> crate::Result<Option<Self>> {
+        Ok(parse_channel_playlist(item))
+    }
+}
+
+impl ChannelTabItem for CommunityPost {
+    fn from_item(item: &Value) -> crate::Result<Option<Self>> {
+        Ok(parse_community_post(item))
+    }
+}
@@ -0,0 +1,138 @@
+use std::ops::Range;
+#[cfg(feature = "download")]
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::helper::{find_key, initial_data, text};
+use crate::{Error, IdBuf, Resource};
+
+/// A clip, which is a short, looping part of a video, that was shared by a viewer
+/// (`youtube.com/clip/<id>`).
+///
+/// Clips don't have their own streams, so downloading a clip means downloading the clipped part
+/// of the source video.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Clip {
+    pub id: String,
+    /// The video the clip was taken from.
+    pub video_id: IdBuf,
+    /// The title the viewer gave the clip.
+    pub title: Option<String>,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+impl Clip {
+    /// Requests the clip page of a clip id (`Ugkx...`).
+    ///
+    /// ### Errors
+    /// - When the clip does not exist.
+    /// - When the clip page has an unexpected format.
+    pub async fn from_id(id: &str) -> crate::Result<Self> {
+        let mut url = url::Url::parse("https://www.youtube.com/clip")?;
+        url
+            .path_segments_mut()
+            .map_err(|_| Error::BadIdFormat)?
+            .push(id);
+
+        let (response, _) = crate::proxy::send(|client| client.get(url.as_str())).await?;
+        let body = response
+            .error_for_status()?
+            .text()
+            .await?;
+        let initial_data = initial_data(&body)
+            .ok_or_else(|| Error::layout_change("the clip page", "ytInitialData"))?;
+        Self::from_initial_data(id, &initial_data)
+    }
+
+    /// Requests the clip of a clip url.
+    ///
+    /// ### Errors
+    /// - When `url` is no clip url ([`Error::BadIdFormat`]).
+    /// - When [`Clip::from_id`] fails.
+    pub async fn from_url(url: &url::Url) -> crate::Result<Self> {
+        match Resource::parse(url.as_str())? {
+            Resource::Clip { id } => Self::from_id(&id).await,
+            _ => Err(Error::BadIdFormat),
+        }
+    }
+
+    /// Creates a clip from the `ytInitialData` of its clip page, without performing any requests.
+    ///
+    /// ### Errors
+    /// - When `initial_data` does not contain the source video, or the clipped time range.
+    pub fn from_initial_data(id: &str, initial_data: &str) -> crate::Result<Self> {
+        let initial_data: Value = serde_json::from_str(initial_data)?;
+
+        let video_id = initial_data["currentVideoEndpoint"]["watchEndpoint"]["videoId"]
+            .as_str()
+            .and_then(|video_id| IdBuf::from_string(video_id.to_owned()).ok())
+            .ok_or_else(|| Error::layout_change("the clip page", "currentVideoEndpoint.watchEndpoint.videoId"))?;
+
+        // the time range is part of the command, which loops the clip in the player
+        let (start_ms, end_ms) = ["clipConfig", "loopCommand"]
+            .iter()
+            .filter_map(|key| find_key(&initial_data, key))
+            .find_map(|config| Some((
+                lenient_ms(&config["startTimeMs"])?,
+                lenient_ms(&config["endTimeMs"])?,
+            )))
+            .filter(|(start_ms, end_ms)| start_ms < end_ms)
+            .ok_or_else(|| Error::layout_change("the clip page", "clipConfig"))?;
+
+        let title = find_key(&initial_data, "clipAttributionRenderer")
+            .and_then(|renderer| text(&renderer["title"]))
+            .filter(|title| !title.is_empty());
+
+        Ok(Self { id: id.to_owned(), video_id, title, start_ms, end_ms })
+    }
+
+    /// The clipped part of the source video.
+    #[inline]
+    pub fn time_range(&self) -> Range<Duration> {
+        Duration::from_millis(self.start_ms)..Duration::from_millis(self.end_ms)
+    }
+
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.end_ms - self.start_ms)
+    }
+
+    /// Requests the source video of the clip.
+    ///
+    /// ### Errors
+    /// - When [`Video::from_id`](crate::Video::from_id) fails.
+    #[cfg(feature = "download")]
+    pub async fn video(&self) -> crate::Result<crate::Video> {
+        crate::Video::from_id(self.video_id.clone()).await
+    }
+
+    /// Downloads the clipped part of `stream`, which has to be a stream of the source video, to
+    /// the provided file path, and returns the time range, which was actually downloaded.
+    ///
+    /// See [`Stream::download_range_to`](crate::Stream::download_range_to) for the streams, which
+    /// can be downloaded partially, and the precision of the downloaded range.
+    ///
+    /// ### Errors
+    /// - When `stream` doesn't belong to the source video ([`Error::WrongVideo`]).
+    /// - When [`Stream::download_range_to`](crate::Stream::download_range_to) fails.
+    #[cfg(feature = "download")]
+    pub async fn download_to<P: AsRef<Path>>(&self, stream: &crate::Stream, path: P) -> crate::Result<Range<Duration>> {
+        if stream.video_details.video_id != self.video_id {
+            return Err(Error::WrongVideo {
+                expected: self.video_id.clone(),
+                found: stream.video_details.video_id.clone(),
+            });
+        }
+        stream.download_range_to(path, self.time_range()).await
+    }
+}
+
+fn lenient_ms(value: &Value) -> Option<u64> {
+    value
+        .as_u64()
+        .or_else(|| value.as_str()?.parse().ok())
+}
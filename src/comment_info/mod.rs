@@ -0,0 +1,97 @@
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+
+use crate::helper::{continuation_stream, parse_comment_page};
+use crate::ChannelIdBuf;
+use crate::video_info::player_response::video_details::{TextRun, Thumbnail};
+
+/// A single comment, or a reply to a comment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: String,
+    pub author: String,
+    pub author_channel_id: Option<ChannelIdBuf>,
+    pub author_thumbnails: Vec<Thumbnail>,
+    pub text: Vec<TextRun>,
+    /// The like count is only as exact as YouTube displays it (i.e. `1.2K` becomes `1200`).
+    pub like_count: u64,
+    /// The relative time, like `2 days ago`.
+    pub published_time_text: String,
+    pub reply_count: u64,
+    pub is_pinned: bool,
+    pub is_hearted: bool,
+    pub is_author_channel_owner: bool,
+    /// The continuation used by [`Comment::replies`]. `None` for replies, and for comments without
+    /// any replies.
+    pub replies_continuation: Option<String>,
+}
+
+/// The order in which comments are requested.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CommentSort {
+    #[default]
+    Top,
+    Newest,
+}
+
+/// A single page of a comments (or replies) continuation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommentPage {
+    pub comments: Vec<Comment>,
+    /// The token of the next page.
+    pub continuation: Option<String>,
+    /// Only available on the first page of the comment section.
+    pub top_continuation: Option<String>,
+    /// Only available on the first page of the comment section.
+    pub newest_continuation: Option<String>,
+    /// Only available on the first page of the comment section.
+    pub comment_count: Option<u64>,
+}
+
+impl CommentPage {
+    /// Parses a raw response of `youtubei/v1/next`.
+    ///
+    /// ### Errors
+    /// - When the response is no valid json, or has an unexpected format.
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        parse_comment_page(&serde_json::from_str(json)?)
+    }
+}
+
+impl Comment {
+    /// The plain text of the comment.
+    #[inline]
+    pub fn plain_text(&self) -> String {
+        self.text.iter().map(|run| run.text.as_str()).collect()
+    }
+
+    /// Lazily requests the replies to this comment, page by page.
+    /// The stream is empty, if the comment has no replies.
+    pub fn replies(&self) -> impl Stream<Item=crate::Result<Comment>> {
+        continuation_stream("next", None, self.replies_continuation.clone(), |response| {
+            let page = parse_comment_page(response)?;
+            Ok((page.comments, page.continuation))
+        })
+    }
+}
+
+/// Lazily requests the comment section starting at `continuation`, page by page.
+///
+/// The first page of the comment section contains the tokens for the different sort orders. If
+/// `sort` is not [`CommentSort::Top`], the first page is therefore only used to switch to the
+/// requested order. If it contains no token for that order, the stream fails, instead of silently
+/// returning the top comments.
+pub(crate) fn comments(continuation: Option<String>, sort: CommentSort) -> impl Stream<Item=crate::Result<Comment>> {
+    let mut sort = Some(sort);
+    continuation_stream("next", None, continuation, move |response| {
+        let page = parse_comment_page(response)?;
+        if let Some(CommentSort::Newest) = sort.take() {
+            let newest = page.newest_continuation.ok_or_else(|| crate::Error::layout_change(
+                "the comments response",
+                "commentsHeaderRenderer.sortMenu.sortFilterSubMenuRenderer.subMenuItems[1]",
+            ))?;
+            return Ok((Vec::new(), Some(newest)));
+        }
+        Ok((page.comments, page.continuation))
+    })
+}
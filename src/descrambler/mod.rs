@@ -1,11 +1,15 @@
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
 use reqwest::Client;
 use url::Url;
 use cipher::Cipher;
 use regex::Regex;
 
-use crate::{IdBuf, Stream, Video, VideoDetails, VideoInfo};
+use crate::{DiagnosticsBundle, IdBuf, Stream, Video, VideoDetails, VideoInfo};
+use crate::diagnostics::DiagnosticsHandler;
+use crate::proxy::PooledProxy;
 use crate::error::Error;
 use crate::video_info::player_response::streaming_data::RawFormat;
 use crate::video_info::player_response::streaming_data::StreamingData;
@@ -90,6 +94,12 @@ pub struct VideoDescrambler {
     pub(crate) client: Client,
     pub(crate) js: String,
     pub (crate) js_player_id: String,
+    /// When the video was fetched, which is the start of [`StreamingData::expires_in_seconds`].
+    pub(crate) fetched_at: DateTime<Utc>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    pub(crate) diagnostics: Option<DiagnosticsHandler>,
+    #[derivative(PartialEq = "ignore")]
+    pub(crate) proxy: Option<PooledProxy>,
 }
 
 impl VideoDescrambler {
@@ -102,6 +112,32 @@ impl VideoDescrambler {
     #[log_derive::logfn(ok = "Trace", err = "Error")]
     #[log_derive::logfn_inputs(Trace)]
     pub async fn descramble(mut self) -> crate::Result<Video> {
+        // the signatures get replaced while descrambling, so the original ones have to be saved
+        let streaming_data = self.diagnostics
+            .as_ref()
+            .and_then(|_| serde_json::to_string(&self.video_info.player_response.streaming_data).ok());
+
+        match self.descramble_streams().await {
+            Ok(streams) => Ok(Video {
+                video_info: self.video_info,
+                streams,
+            }),
+            Err(err) => {
+                if let Some(ref handler) = self.diagnostics {
+                    let mut bundle = DiagnosticsBundle::new(self.video_id().clone(), &err)
+                        .with_player_js(&self.js_player_id, &self.js);
+                    if let Some(ref streaming_data) = streaming_data {
+                        bundle.push_snippet("player response: streamingData", streaming_data);
+                    }
+                    handler(bundle);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    async fn descramble_streams(&mut self) -> crate::Result<Vec<Stream>> {
+        let expires_at = self.expires_at();
         let streaming_data = self.video_info.player_response.streaming_data
             .as_mut()
             .ok_or_else(|| Error::Custom(
@@ -114,43 +150,49 @@ impl VideoDescrambler {
                 apply_descrambler_adaptive_fmts(streaming_data, adaptive_fmts_raw)?;
             }
     
-            apply_signature(streaming_data, &self.js, &self.js_player_id)?;
+            let player_id = &self.js_player_id;
+            apply_signature(streaming_data, &self.js, player_id)
+                .map_err(|err| Error::Signature { player_id: player_id.clone(), source: Box::new(err) })?;
             
             Self::initialize_streams(
                 streaming_data,
                 &mut streams,
                 &self.client,
+                &self.proxy,
+                expires_at,
                 &self.video_info.player_response.video_details,
             );
         }
-        Self::hls_descramble(&self, &mut streams).await;
-        Ok(Video {
-            video_info: self.video_info,
-            streams,
-        })
+        self.hls_descramble(&mut streams).await?;
+        Ok(streams)
     }
 
-    async fn get_prise_hls(&self, streams: &mut Vec<Stream>, hls_manifest_url:String) {
-        let req_out = self.client.get(hls_manifest_url)
-        .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8")
-        .send().await;
-        if req_out.is_err() {
-            return;
-        }
-        let req_bytes = req_out.unwrap().bytes().await;
-        if req_bytes.is_err() {
-            return;
-        }
-        let n = m3u8_rs::parse_master_playlist(&req_bytes.unwrap()).unwrap().1;
-        for i  in n.variants {
-            let codex = i.codecs;
-            let codex_out = codex.unwrap();
-            let re = Regex::new(r"/itag/(\d+)/").unwrap();
-            let itag_raw = &re.captures(&i.uri).unwrap()[1];
+    async fn get_prise_hls(&self, streams: &mut Vec<Stream>, hls_manifest_url: String) -> crate::Result<()> {
+        static ITAG: Lazy<Regex> = Lazy::new(||
+            Regex::new(r"/itag/(\d+)/").unwrap()
+        );
+
+        let manifest = self.client.get(hls_manifest_url)
+            .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8")
+            .send()
+            .await?
+            .bytes()
+            .await?;
+        let master = m3u8_rs::parse_master_playlist_res(&manifest)
+            .map_err(|_| Error::layout_change("the HLS manifest", "#EXTM3U"))?;
+
+        for (index, variant) in master.variants.into_iter().enumerate() {
+            let missing = |attribute: &str| Error::layout_change("the HLS manifest", format!("variants[{index}].{attribute}"));
+            let codecs = variant.codecs.as_deref().ok_or_else(|| missing("CODECS"))?;
+            let frame_rate = variant.frame_rate.ok_or_else(|| missing("FRAME-RATE"))?;
+            let itag = ITAG
+                .captures(&variant.uri)
+                .and_then(|captures| captures[1].parse::<u64>().ok())
+                .ok_or_else(|| missing("URI"))?;
+
             let mut qlt = "hd720";
             let (mut width, mut height) = (None, None);
-            if i.resolution.is_some() {
-                let r = i.resolution.unwrap();
+            if let Some(r) = variant.resolution {
                 width = Some(r.width);
                 height = Some(r.height);
                 qlt = if r.height >= 2160 {
@@ -171,26 +213,49 @@ impl VideoDescrambler {
                     "tiny"
                 };
             }
-            let url_qr = url_escape::encode_component(&i.uri);
-            let out = serde_json::json!({"fps": i.frame_rate.unwrap() as u8, "signatureCipher": format!("url={}", url_qr), "itag": itag_raw.parse::<u64>().unwrap(), "quality": qlt, "mimeType": format!("video/mp4; codecs=\"{}\"", codex_out), "projectionType": "RECTANGULAR", "width": width, "height": height, "bitrate": i.bandwidth});
-            let raw_f = serde_json::from_value::<RawFormat>(out).unwrap();
-            let stream = Stream::from_raw_format(raw_f, self.client.clone(), Arc::clone(&self.video_info.player_response.video_details));
+            let url_qr = url_escape::encode_component(&variant.uri);
+            let out = serde_json::json!({"fps": frame_rate as u8, "signatureCipher": format!("url={}", url_qr), "itag": itag, "quality": qlt, "mimeType": format!("video/mp4; codecs=\"{}\"", codecs), "projectionType": "RECTANGULAR", "width": width, "height": height, "bitrate": variant.bandwidth});
+            let raw_f = serde_json::from_value::<RawFormat>(out).map_err(|err| Error::LayoutChange {
+                context: "the HLS manifest".into(),
+                path: format!("variants[{index}]").into(),
+                source: Some(err),
+            })?;
+            let stream = Stream::from_raw_format(
+                raw_f,
+                self.client.clone(),
+                self.proxy.clone(),
+                self.expires_at(),
+                Arc::clone(&self.video_info.player_response.video_details),
+            );
             streams.push(stream);
         }
+        Ok(())
     }
 
-    pub async fn hls_descramble(&self, streams: &mut Vec<Stream>) {
-        let streaming_data = self.video_info.player_response.streaming_data
+    /// Adds the streams of the HLS manifest, which is only available for live streams.
+    ///
+    /// ### Errors
+    /// - When the manifest can't be requested.
+    /// - When the manifest has an unexpected format.
+    pub async fn hls_descramble(&self, streams: &mut Vec<Stream>) -> crate::Result<()> {
+        let hls_manifest_url = self.video_info.player_response.streaming_data
             .as_ref()
-            .ok_or_else(|| Error::Custom(
-                "VideoInfo contained no StreamingData, which is essential for downloading.".into()
-            )).unwrap();
-        if streaming_data.hls_manifest_url.is_some() {
-            let url_hls = streaming_data.hls_manifest_url.as_ref().unwrap();
-            Self::get_prise_hls(&self, streams, url_hls.to_string()).await
+            .and_then(|streaming_data| streaming_data.hls_manifest_url.clone());
+        match hls_manifest_url {
+            Some(hls_manifest_url) => self.get_prise_hls(streams, hls_manifest_url).await,
+            None => Ok(()),
         }
     }
 
+    /// When the stream urls expire, according to the streaming data.
+    fn expires_at(&self) -> Option<DateTime<Utc>> {
+        let expires_in = self.video_info.player_response.streaming_data
+            .as_ref()?
+            .expires_in_seconds;
+        self.fetched_at
+            .checked_add_signed(chrono::Duration::from_std(std::time::Duration::from_secs(expires_in)).ok()?)
+    }
+
     /// The [`VideoInfo`] of the video.
     #[inline]
     pub fn video_info(&self) -> &VideoInfo {
@@ -221,12 +286,16 @@ impl VideoDescrambler {
         streaming_data: &mut StreamingData,
         streams: &mut Vec<Stream>,
         client: &Client,
+        proxy: &Option<PooledProxy>,
+        expires_at: Option<DateTime<Utc>>,
         video_details: &Arc<VideoDetails>,
     ) {
         for raw_format in streaming_data.formats.drain(..).chain(streaming_data.adaptive_formats.drain(..)) {
             let stream = Stream::from_raw_format(
                 raw_format,
                 client.clone(),
+                proxy.clone(),
+                expires_at,
                 Arc::clone(video_details),
             );
             streams.push(stream);
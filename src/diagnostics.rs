@@ -0,0 +1,110 @@
+//! Opt-in diagnostics for reporting errors, which are caused by changes on YouTube's side.
+//!
+//! After enabling them with [`VideoFetcher::with_diagnostics`](crate::VideoFetcher::with_diagnostics),
+//! a [`DiagnosticsBundle`] is passed to the handler whenever fetching or descrambling a video
+//! fails. It contains the parts of the watch page and the player, `rustube` extracts data from,
+//! and can be serialized and attached to a bug report.
+
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, IdBuf};
+
+/// The maximal length of a single [`Snippet`] in bytes.
+pub const MAX_SNIPPET_LEN: usize = 4096;
+
+/// The handler, which receives the [`DiagnosticsBundle`] of a failed request.
+pub type DiagnosticsHandler = Arc<dyn Fn(DiagnosticsBundle) + Send + Sync>;
+
+/// Everything needed to reproduce an error, which occurred while fetching or descrambling a video.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagnosticsBundle {
+    pub video_id: IdBuf,
+    /// The error, including all of its sources.
+    pub error: String,
+    /// The id of the player JavaScript, i.e. `4fbb4d5b`.
+    pub player_id: Option<String>,
+    pub crate_version: String,
+    pub snippets: Vec<Snippet>,
+}
+
+/// A part of a page or response.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Snippet {
+    /// Where the snippet was taken from, i.e. `watch page: ytInitialPlayerResponse`.
+    pub source: String,
+    pub content: String,
+    /// Whether `content` was cut off at [`MAX_SNIPPET_LEN`].
+    pub truncated: bool,
+}
+
+impl DiagnosticsBundle {
+    pub fn new(video_id: IdBuf, error: &Error) -> Self {
+        let mut message = error.to_string();
+        let mut source = std::error::Error::source(error);
+        while let Some(err) = source {
+            let err_message = err.to_string();
+            if !message.contains(&err_message) {
+                message.push_str(": ");
+                message.push_str(&err_message);
+            }
+            source = err.source();
+        }
+
+        Self {
+            video_id,
+            error: message,
+            player_id: None,
+            crate_version: env!("CARGO_PKG_VERSION").to_owned(),
+            snippets: Vec::new(),
+        }
+    }
+
+    /// Adds the parts of the watch page, which contain the player response, the initial data and
+    /// the url of the player.
+    pub fn with_watch_html(mut self, html: &str) -> Self {
+        static PLAYER_ID: Lazy<Regex> = Lazy::new(|| Regex::new(r"/s/player/([\w-]+)/").unwrap());
+
+        for key in ["playabilityStatus", "ytInitialPlayerResponse", "ytInitialData"] {
+            if let Some(start) = html.find(key) {
+                self.push_snippet(format!("watch page: {key}"), &html[start..]);
+            }
+        }
+        if let Some(captures) = PLAYER_ID.captures(html) {
+            self.player_id.get_or_insert_with(|| captures[1].to_owned());
+            self.push_snippet("watch page: player url", &html[captures.get(0).unwrap().start()..]);
+        }
+        self
+    }
+
+    /// Adds the part of the player JavaScript, which contains the function used to descramble the
+    /// signatures.
+    pub fn with_player_js(mut self, player_id: &str, js: &str) -> Self {
+        self.player_id = Some(player_id.to_owned());
+        // the signature function starts by splitting the signature into its characters
+        if let Some(position) = js.find(r#"=a.split("")"#) {
+            let mut start = position.saturating_sub(MAX_SNIPPET_LEN / 4);
+            while !js.is_char_boundary(start) {
+                start -= 1;
+            }
+            self.push_snippet("player: signature function", &js[start..]);
+        }
+        self
+    }
+
+    /// Adds a snippet, which is cut off at [`MAX_SNIPPET_LEN`].
+    pub fn push_snippet(&mut self, source: impl Into<String>, content: &str) {
+        let mut len = content.len().min(MAX_SNIPPET_LEN);
+        while !content.is_char_boundary(len) {
+            len -= 1;
+        }
+        self.snippets.push(Snippet {
+            source: source.into(),
+            content: content[..len].to_owned(),
+            truncated: len < content.len(),
+        });
+    }
+}
@@ -1,5 +1,8 @@
 use alloc::borrow::Cow;
 
+#[cfg(feature = "fetch")]
+use crate::video_info::player_response::playability_status::PlayabilityStatus;
+
 /// Errors that can occur during the id extraction or the video download process.   
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -7,19 +10,111 @@ pub enum Error {
     BadIdFormat,
     #[cfg(feature = "fetch")]
     #[error("the video you requested is unavailable:\n{0:#?}")]
-    VideoUnavailable(Box<crate::video_info::player_response::playability_status::PlayabilityStatus>),
+    VideoUnavailable(Box<PlayabilityStatus>),
+    /// The video is not available in the country the request was made from.
+    ///
+    /// `allowed` are the countries (ISO 3166-1 alpha-2 codes), in which the video is available,
+    /// and `blocked` the ones it's known to be blocked in, which is the country YouTube located the
+    /// request in. Both are empty, if the watch page did not contain them, and `blocked` is also
+    /// empty, if the country was overridden with [`VideoFetcher::with_gl`].
+    ///
+    /// [`VideoFetcher::with_gl`]: crate::VideoFetcher::with_gl
+    #[cfg(feature = "fetch")]
+    #[error("the video is not available in this country: {reason}")]
+    GeoBlocked {
+        reason: String,
+        allowed: Vec<String>,
+        blocked: Vec<String>,
+    },
+    /// Watching the video requires signing in to confirm the viewer's age.
+    #[cfg(feature = "fetch")]
+    #[error("the video is age-restricted: {reason}")]
+    AgeRestricted { reason: String },
+    /// The video is only available to members of the channel.
+    #[cfg(feature = "fetch")]
+    #[error("the video is only available to channel members: {reason}")]
+    MembersOnly { reason: String },
+    #[cfg(feature = "fetch")]
+    #[error("the video is private: {reason}")]
+    Private { reason: String },
+    /// The video was removed, i.e. by the uploader, because of a copyright claim, or because the
+    /// channel was terminated. `reason` is the explanation shown in the player.
+    #[cfg(feature = "fetch")]
+    #[error("the video was removed: {reason}")]
+    Removed { reason: String },
+    /// YouTube answered with `429 Too Many Requests`, or redirected to its captcha page.
+    /// `retry_after` is the value of the `Retry-After` header, if there was one.
+    #[cfg(feature = "fetch")]
+    #[error("YouTube rate-limits the requests")]
+    RateLimited { retry_after: Option<core::time::Duration> },
+    /// The stream signatures could not be descrambled with the JavaScript of the player
+    /// `player_id`, which usually means YouTube changed its player.
+    #[cfg(feature = "descramble")]
+    #[error("failed to descramble the signatures with player `{player_id}`: {source}")]
+    Signature {
+        player_id: String,
+        #[source]
+        source: Box<Error>,
+    },
+    /// All proxies of the [`ProxyPool`](crate::proxy::ProxyPool) are cooling down, after they were
+    /// rate-limited, blocked, or could not be reached.
+    #[cfg(feature = "fetch")]
+    #[error("all proxies of the pool are unhealthy")]
+    NoHealthyProxy,
+    /// A [`ProxyPool`](crate::proxy::ProxyPool) was created without any proxies.
+    #[cfg(feature = "fetch")]
+    #[error("a proxy pool needs at least one proxy")]
+    EmptyProxyPool,
+    /// The scheme of a proxy url is not one of `http`, `https`, `socks5` and `socks5h`.
+    #[cfg(feature = "fetch")]
+    #[error("unsupported proxy scheme `{0}`")]
+    UnsupportedProxyScheme(String),
     #[cfg(feature = "download")]
     #[error("the video contains no streams")]
     NoStreams,
+    /// A stream was passed together with a video, it does not belong to, i.e. to
+    /// [`Clip::download_to`](crate::Clip::download_to) with a stream of another video than the
+    /// source video of the clip.
+    #[cfg(feature = "download")]
+    #[error("the stream belongs to the video `{found}` instead of `{expected}`")]
+    WrongVideo {
+        expected: crate::IdBuf,
+        found: crate::IdBuf,
+    },
+    /// Only DASH streams in the MP4 container can be downloaded partially, i.e. with
+    /// [`Stream::download_range_to`](crate::Stream::download_range_to).
+    #[cfg(feature = "download")]
+    #[error("only DASH MP4 streams can be downloaded partially")]
+    PartialDownloadUnsupported,
+    /// The time range, which should be downloaded, is not part of the stream.
+    #[cfg(feature = "download")]
+    #[error("the time range {0:?} is not part of the stream")]
+    TimeRangeNotInStream(core::ops::Range<core::time::Duration>),
+    /// The url of a stream had to be refreshed, but the video no longer has a stream with its
+    /// itag.
+    #[cfg(feature = "download")]
+    #[error("the stream with itag {itag} is no longer available")]
+    StreamUnavailable { itag: u64 },
 
     #[error(transparent)]
     #[cfg(feature = "fetch")]
     IO(#[from] std::io::Error),
     #[error(transparent)]
     #[cfg(feature = "fetch")]
-    Request(#[from] reqwest::Error),
+    Request(reqwest::Error),
     #[error("YouTube returned an unexpected response: `{0}`")]
     UnexpectedResponse(Cow<'static, str>),
+    /// A part of a page or response could not be parsed, which usually means YouTube changed its
+    /// layout. `path` is the JSON path (or another description of the part) which was missing or
+    /// had an unexpected format.
+    #[error("failed to parse {context}: unexpected value at `{path}`")]
+    #[cfg(feature = "fetch")]
+    LayoutChange {
+        context: Cow<'static, str>,
+        path: Cow<'static, str>,
+        #[source]
+        source: Option<serde_json::Error>,
+    },
     #[error(transparent)]
     #[cfg(feature = "fetch")]
     QueryDeserialization(#[from] serde_qs::Error),
@@ -42,3 +137,98 @@ pub enum Error {
     #[cfg(feature = "callback")]
     ChannelClosed,
 }
+
+impl Error {
+    /// A [`Error::LayoutChange`] without an underlying error.
+    #[cfg(feature = "fetch")]
+    pub(crate) fn layout_change(context: impl Into<Cow<'static, str>>, path: impl Into<Cow<'static, str>>) -> Self {
+        Self::LayoutChange { context: context.into(), path: path.into(), source: None }
+    }
+
+    /// Turns `429 Too Many Requests` responses, and redirects to the captcha page, into
+    /// [`Error::RateLimited`], and other error statuses into [`Error::Request`].
+    #[cfg(feature = "fetch")]
+    pub(crate) fn check_response(response: reqwest::Response) -> crate::Result<reqwest::Response> {
+        Ok(Self::check_rate_limit(response)?.error_for_status()?)
+    }
+
+    /// Turns `429 Too Many Requests` responses, and redirects to the captcha page, into
+    /// [`Error::RateLimited`].
+    #[cfg(feature = "fetch")]
+    pub(crate) fn check_rate_limit(response: reqwest::Response) -> crate::Result<reqwest::Response> {
+        let redirected_to_captcha = response.url().host_str().is_some_and(|host| host.ends_with("google.com"))
+            && response.url().path().starts_with("/sorry");
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS || redirected_to_captcha {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok()?.trim().parse().ok())
+                .map(core::time::Duration::from_secs);
+            return Err(Self::RateLimited { retry_after });
+        }
+        Ok(response)
+    }
+
+    /// Whether the error was likely caused by the proxy (or IP), the request was sent from, and
+    /// the request should be retried with another one.
+    #[cfg(feature = "fetch")]
+    pub(crate) fn is_proxy_failure(&self) -> bool {
+        match self {
+            Self::RateLimited { .. } => true,
+            Self::Request(err) => {
+                err.status() == Some(reqwest::StatusCode::FORBIDDEN) || err.is_connect() || err.is_timeout()
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "fetch")]
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        match err.status() {
+            Some(reqwest::StatusCode::TOO_MANY_REQUESTS) => Self::RateLimited { retry_after: None },
+            _ => Self::Request(err),
+        }
+    }
+}
+
+/// Classifies why a video is not playable, based on the status and the texts shown in the player.
+///
+/// Statuses, which don't match any of the specific variants, become [`Error::VideoUnavailable`].
+#[cfg(feature = "fetch")]
+impl From<PlayabilityStatus> for Error {
+    fn from(status: PlayabilityStatus) -> Self {
+        let reason = status.reason().unwrap_or_default();
+        let subreason = status.subreason();
+        let text = core::iter::once(&reason)
+            .chain(&subreason)
+            .chain(status.messages())
+            .map(|text| text.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mentions = |patterns: &[&str]| patterns.iter().any(|pattern| text.contains(pattern));
+
+        match status {
+            PlayabilityStatus::Ok { .. } | PlayabilityStatus::LiveStreamOffline { .. } => {
+                Self::VideoUnavailable(Box::new(status))
+            }
+            _ if mentions(&["your country", "in your location"]) => {
+                Self::GeoBlocked { reason, allowed: Vec::new(), blocked: Vec::new() }
+            }
+            PlayabilityStatus::LoginRequired { desktop_legacy_age_gate_reason: Some(_), .. } => {
+                Self::AgeRestricted { reason }
+            }
+            _ if mentions(&["confirm your age", "age-restricted", "inappropriate for some users"]) => {
+                Self::AgeRestricted { reason }
+            }
+            _ if mentions(&["members-only", "members only", "join this channel"]) => Self::MembersOnly { reason },
+            _ if mentions(&["private video", "video is private"]) => Self::Private { reason },
+            PlayabilityStatus::Unplayable { .. } | PlayabilityStatus::Error { .. }
+            if mentions(&["removed", "terminated", "no longer available", "copyright", "violating"]) => {
+                Self::Removed { reason: subreason.unwrap_or(reason) }
+            }
+            status => Self::VideoUnavailable(Box::new(status)),
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! A client for the RSS/Atom feeds of channels and playlists.
+//!
+//! The feeds only contain the latest 15 videos, but are a lot cheaper to request than a
+//! [`Channel`](crate::Channel), which makes them a good fit for detecting new uploads.
+
+use chrono::{DateTime, FixedOffset};
+use reqwest::{header, Client, StatusCode};
+use roxmltree::{Document, Node};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{ChannelId, ChannelIdBuf, Error, IdBuf, PlaylistId, PlaylistIdBuf};
+use crate::video_info::player_response::video_details::Thumbnail;
+
+const ATOM: &str = "http://www.w3.org/2005/Atom";
+const YT: &str = "http://www.youtube.com/xml/schemas/2015";
+const MEDIA: &str = "http://search.yahoo.com/mrss/";
+
+/// A parsed channel or playlist feed.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct Feed {
+    pub title: String,
+    /// The channel of the feed, or the owner of the playlist.
+    pub channel_id: Option<ChannelIdBuf>,
+    /// Only available for playlist feeds.
+    pub playlist_id: Option<PlaylistIdBuf>,
+    pub author: Option<String>,
+    pub published: Option<DateTime<FixedOffset>>,
+    /// The latest videos, newest first.
+    pub entries: Vec<FeedEntry>,
+}
+
+/// A single video of a [`Feed`].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct FeedEntry {
+    pub video_id: IdBuf,
+    pub channel_id: Option<ChannelIdBuf>,
+    pub title: String,
+    pub author: Option<String>,
+    pub description: String,
+    pub published: DateTime<FixedOffset>,
+    /// Changes whenever the title, description or thumbnail of the video changes.
+    pub updated: DateTime<FixedOffset>,
+    pub views: Option<u64>,
+    pub thumbnail: Option<Thumbnail>,
+}
+
+impl Feed {
+    /// Parses the XML of a feed.
+    ///
+    /// ### Errors
+    /// - When `xml` is not a valid feed.
+    pub fn from_xml(xml: &str) -> crate::Result<Self> {
+        let document = Document::parse(xml)
+            .map_err(|err| Error::UnexpectedResponse(format!("the feed is no valid XML: {err}").into()))?;
+        let feed = document.root_element();
+        if !feed.has_tag_name((ATOM, "feed")) {
+            return Err(Error::UnexpectedResponse("the XML document is no Atom feed".into()));
+        }
+
+        Ok(Self {
+            title: child_text(feed, ATOM, "title").unwrap_or_default(),
+            channel_id: child_text(feed, YT, "channelId").and_then(|id| ChannelIdBuf::from_string(id).ok()),
+            playlist_id: child_text(feed, YT, "playlistId").and_then(|id| PlaylistIdBuf::from_string(id).ok()),
+            author: child(feed, ATOM, "author").and_then(|author| child_text(author, ATOM, "name")),
+            published: child_text(feed, ATOM, "published").and_then(|date| DateTime::parse_from_rfc3339(&date).ok()),
+            entries: feed
+                .children()
+                .filter(|node| node.has_tag_name((ATOM, "entry")))
+                .map(FeedEntry::from_node)
+                .collect::<crate::Result<_>>()?,
+        })
+    }
+}
+
+impl FeedEntry {
+    fn from_node(entry: Node<'_, '_>) -> crate::Result<Self> {
+        let date = |name: &str| child_text(entry, ATOM, name)
+            .and_then(|date| DateTime::parse_from_rfc3339(&date).ok())
+            .ok_or_else(|| Error::UnexpectedResponse(format!("a feed entry has no valid `{name}` date").into()));
+        let group = child(entry, MEDIA, "group");
+        let thumbnail = group
+            .and_then(|group| child(group, MEDIA, "thumbnail"))
+            .and_then(|thumbnail| Some(Thumbnail {
+                width: thumbnail.attribute("width")?.parse().ok()?,
+                height: thumbnail.attribute("height")?.parse().ok()?,
+                url: thumbnail.attribute("url")?.to_owned(),
+            }));
+
+        Ok(Self {
+            video_id: child_text(entry, YT, "videoId")
+                .and_then(|id| IdBuf::from_string(id).ok())
+                .ok_or_else(|| Error::UnexpectedResponse("a feed entry has no valid video id".into()))?,
+            channel_id: child_text(entry, YT, "channelId").and_then(|id| ChannelIdBuf::from_string(id).ok()),
+            title: child_text(entry, ATOM, "title").unwrap_or_default(),
+            author: child(entry, ATOM, "author").and_then(|author| child_text(author, ATOM, "name")),
+            description: group
+                .and_then(|group| child_text(group, MEDIA, "description"))
+                .unwrap_or_default(),
+            published: date("published")?,
+            updated: date("updated")?,
+            views: group
+                .and_then(|group| child(group, MEDIA, "community"))
+                .and_then(|community| child(community, MEDIA, "statistics"))
+                .and_then(|statistics| statistics.attribute("views")?.parse().ok()),
+            thumbnail,
+        })
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, namespace: &str, name: &str) -> Option<Node<'a, 'input>> {
+    node
+        .children()
+        .find(|child| child.has_tag_name((namespace, name)))
+}
+
+fn child_text(node: Node<'_, '_>, namespace: &str, name: &str) -> Option<String> {
+    child(node, namespace, name).map(|child| child.text().unwrap_or_default().to_owned())
+}
+
+/// A client for the feed of a channel or playlist, which uses conditional requests.
+///
+/// After each successful request, the `ETag` and `Last-Modified` headers of the response are
+/// stored and sent with the next request, so an unchanged feed is neither transferred nor parsed
+/// again. To keep them across restarts, use [`ChannelFeed::etag`], [`ChannelFeed::last_modified`]
+/// and [`ChannelFeed::with_validators`].
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug, PartialEq)]
+pub struct ChannelFeed {
+    url: Url,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    client: Client,
+}
+
+impl ChannelFeed {
+    /// The feed of the latest uploads of a channel.
+    ///
+    /// ### Errors
+    /// - When the client could not be created.
+    pub fn for_channel(channel_id: ChannelId<'_>) -> crate::Result<Self> {
+        Self::new("channel_id", channel_id.as_str())
+    }
+
+    /// The feed of the latest videos of a playlist.
+    ///
+    /// ### Errors
+    /// - When the client could not be created.
+    pub fn for_playlist(playlist_id: PlaylistId<'_>) -> crate::Result<Self> {
+        Self::new("playlist_id", playlist_id.as_str())
+    }
+
+    fn new(key: &str, id: &str) -> crate::Result<Self> {
+        Ok(Self {
+            url: Url::parse_with_params("https://www.youtube.com/feeds/videos.xml", &[(key, id)])?,
+            etag: None,
+            last_modified: None,
+            client: crate::crate_client()?,
+        })
+    }
+
+    /// Restores the validators of a previous response.
+    #[inline]
+    pub fn with_validators(mut self, etag: Option<String>, last_modified: Option<String>) -> Self {
+        self.etag = etag;
+        self.last_modified = last_modified;
+        self
+    }
+
+    #[inline]
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// The `ETag` of the last response.
+    #[inline]
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// The `Last-Modified` header of the last response.
+    #[inline]
+    pub fn last_modified(&self) -> Option<&str> {
+        self.last_modified.as_deref()
+    }
+
+    /// Requests the feed. Returns `None`, if it did not change since the last request.
+    ///
+    /// ### Errors
+    /// - When the request fails (i.e. `404 Not Found` for unknown channels).
+    /// - When the feed can't be parsed.
+    pub async fn fetch(&mut self) -> crate::Result<Option<Feed>> {
+        let mut request = self.client.get(self.url.as_str());
+        if let Some(ref etag) = self.etag {
+            request = request.header(header::IF_NONE_MATCH, etag);
+        }
+        if let Some(ref last_modified) = self.last_modified {
+            request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+        }
+
+        let response = request.send().await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        let response = response.error_for_status()?;
+
+        let validator = |name: header::HeaderName| response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+        let (etag, last_modified) = (validator(header::ETAG), validator(header::LAST_MODIFIED));
+
+        let feed = Feed::from_xml(&response.text().await?)?;
+        self.etag = etag;
+        self.last_modified = last_modified;
+        Ok(Some(feed))
+    }
+}
@@ -1,10 +1,13 @@
+use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use reqwest::Client;
 use serde::Deserialize;
 use url::Url;
 
-use crate::{Error, Id, IdBuf, PlayerResponse, VideoDescrambler, VideoInfo};
+use crate::{DiagnosticsBundle, Error, Id, IdBuf, PlayerResponse, VideoDescrambler, VideoInfo};
+use crate::diagnostics::DiagnosticsHandler;
+use crate::proxy::{PooledProxy, ProxyPool};
 use crate::video_info::player_response::playability_status::PlayabilityStatus;
 
 /// A fetcher used to download all necessary data from YouTube, which then could be used
@@ -69,6 +72,13 @@ pub struct VideoFetcher {
     watch_url: Url,
     #[derivative(PartialEq = "ignore")]
     client: Client,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    diagnostics: Option<DiagnosticsHandler>,
+    #[derivative(PartialEq = "ignore")]
+    proxy_pool: Option<ProxyPool>,
+    /// The proxy of the pool, the last request was sent through.
+    #[derivative(PartialEq = "ignore")]
+    proxy: Option<PooledProxy>,
 }
 
 impl VideoFetcher {
@@ -85,21 +95,16 @@ impl VideoFetcher {
     }
 
     /// Constructs a [`VideoFetcher`] from an `Id`.
+    ///
+    /// The fetcher uses the global [`ProxyPool`], if one is installed.
     /// ### Errors
     /// When [`reqwest`] fails to initialize an new [`Client`].
     #[inline]
     pub fn from_id(video_id: IdBuf) -> crate::Result<Self> {
-        // maybe make these feature gated, to prevent overhead for users that
-        //  don't have problems with youtube consent
-        let cookie_jar = recommended_cookies();
-        let headers = recommended_headers();
-
-        let client = Client::builder()
-            .default_headers(headers)
-            .cookie_provider(std::sync::Arc::new(cookie_jar))
-            .build()?;
-
-        Ok(Self::from_id_with_client(video_id, client))
+        let client = recommended_client_builder().build()?;
+        let mut fetcher = Self::from_id_with_client(video_id, client);
+        fetcher.proxy_pool = ProxyPool::global();
+        Ok(fetcher)
     }
 
     /// Constructs a [`VideoFetcher`] from an [`Id`] and an existing [`Client`].
@@ -112,9 +117,74 @@ impl VideoFetcher {
             watch_url: video_id.watch_url(),
             video_id,
             client,
+            diagnostics: None,
+            proxy_pool: None,
+            proxy: None,
         }
     }
 
+    /// Sets the country (an ISO 3166-1 alpha-2 code, like `"DE"`) YouTube should localize the
+    /// watch page for.
+    ///
+    /// This does not bypass geo-restrictions, which depend on the location of the request. To
+    /// fetch a video, that's blocked in the own country, use [`VideoFetcher::with_proxy`] with a
+    /// proxy in one of the allowed countries (see [`Error::GeoBlocked`]).
+    #[inline]
+    pub fn with_gl(mut self, gl: &str) -> Self {
+        let query = self.watch_url
+            .query_pairs()
+            .filter(|(key, _)| key != "gl")
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect::<Vec<_>>();
+        self.watch_url
+            .query_pairs_mut()
+            .clear()
+            .extend_pairs(query)
+            .append_pair("gl", gl);
+        self
+    }
+
+    /// Routes all requests of this fetcher, and of the streams of the video, through `proxy`.
+    ///
+    /// This replaces the client with a new one, which is built the same way as in
+    /// [`VideoFetcher::from_id`].
+    /// ### Errors
+    /// When [`reqwest`] fails to initialize an new [`Client`].
+    #[inline]
+    pub fn with_proxy(mut self, proxy: reqwest::Proxy) -> crate::Result<Self> {
+        self.client = recommended_client_builder()
+            .proxy(proxy)
+            .build()?;
+        self.proxy_pool = None;
+        Ok(self)
+    }
+
+    /// Sends the requests through the proxies of `pool`.
+    ///
+    /// When a proxy gets rate-limited, blocked or can't be reached, it's marked as unhealthy, and
+    /// the video is fetched again with the next proxy. Proxies in the country set with
+    /// [`VideoFetcher::with_gl`] are preferred, and when the video is geo-blocked, it's fetched
+    /// again with a proxy in one of the allowed countries, if the pool has one.
+    ///
+    /// The streams of the video are downloaded with the proxy, which fetched the video, since
+    /// their urls only work for the IP, that requested them.
+    #[inline]
+    pub fn with_proxy_pool(mut self, pool: ProxyPool) -> Self {
+        self.proxy_pool = Some(pool);
+        self
+    }
+
+    /// Enables diagnostics. Whenever fetching or descrambling the video fails, `handler` receives
+    /// a [`DiagnosticsBundle`] with the parts of the watch page and the player, which are needed to
+    /// reproduce the error.
+    ///
+    /// Collecting the diagnostics has no costs, until an error occurs.
+    #[inline]
+    pub fn with_diagnostics(mut self, handler: impl Fn(DiagnosticsBundle) + Send + Sync + 'static) -> Self {
+        self.diagnostics = Some(std::sync::Arc::new(handler));
+        self
+    }
+
     /// Fetches all available video data and deserializes it into [`VideoInfo`].
     ///
     /// ### Errors
@@ -128,7 +198,7 @@ impl VideoFetcher {
     #[cfg(feature = "fetch")]
     #[log_derive::logfn(ok = "Trace", err = "Error")]
     #[log_derive::logfn_inputs(Trace)]
-    pub async fn fetch(self) -> crate::Result<VideoDescrambler> {
+    pub async fn fetch(mut self) -> crate::Result<VideoDescrambler> {
         // fixme:
         //  It seems like watch_html also contains a PlayerResponse in all cases. VideoInfo
         //  only contains the  extra field `adaptive_fmts_raw`. It may be possible to just use the
@@ -142,17 +212,17 @@ impl VideoFetcher {
         //          PlayerResponse in most cases. (It would also be possible to just check, whether
         //          or not watch_html contains PlayerResponse, and otherwise request video_info).
 
-        let watch_html = self.get_html(&self.watch_url).await?;
-        let is_age_restricted = is_age_restricted(&watch_html);
-        Self::check_downloadability(&watch_html, is_age_restricted)?;
-
-        let (video_info, js, js_player_id) = self.get_video_info_and_js(&watch_html, is_age_restricted).await?;
+        let fetched_at = DateTime::<Utc>::from(std::time::SystemTime::now());
+        let (video_info, js, js_player_id) = self.fetch_rotating(true).await?;
 
         Ok(VideoDescrambler {
             video_info,
             client: self.client,
             js,
             js_player_id,
+            fetched_at,
+            diagnostics: self.diagnostics,
+            proxy: self.proxy,
         })
     }
 
@@ -172,12 +242,8 @@ impl VideoFetcher {
     /// that YouTube changed their API, and `rustube` did not adapt to this change yet. Please feel
     /// free to open a GitHub issue if this is the case.
     #[cfg(feature = "fetch")]
-    pub async fn fetch_info(self) -> crate::Result<VideoInfo> {
-        let watch_html = self.get_html(&self.watch_url).await?;
-        let is_age_restricted = is_age_restricted(&watch_html);
-        Self::check_fetchability(&watch_html, is_age_restricted)?;
-        let (video_info, _js, _js_player_id) = self.get_video_info_and_js(&watch_html, is_age_restricted).await?;
-
+    pub async fn fetch_info(mut self) -> crate::Result<VideoInfo> {
+        let (video_info, _js, _js_player_id) = self.fetch_rotating(false).await?;
         Ok(video_info)
     }
 
@@ -193,17 +259,90 @@ impl VideoFetcher {
         &self.watch_url
     }
 
-    fn check_downloadability(watch_html: &str, is_age_restricted: bool) -> crate::Result<PlayabilityStatus> {
+    /// The country set with [`VideoFetcher::with_gl`].
+    fn gl(&self) -> Option<String> {
+        self.watch_url
+            .query_pairs()
+            .find(|(key, _)| key == "gl")
+            .map(|(_, gl)| gl.into_owned())
+    }
+
+    /// Fetches the video with the proxies of the pool, until one of them succeeds, or the error
+    /// is not caused by the proxy. Without a pool, the video is fetched once with the client.
+    async fn fetch_rotating(&mut self, downloadable: bool) -> crate::Result<(VideoInfo, String, String)> {
+        let pool = match self.proxy_pool {
+            Some(ref pool) => pool.clone(),
+            None => return self.fetch_once(downloadable).await,
+        };
+
+        let mut countries = self.gl().into_iter().collect::<Vec<_>>();
+        let mut result = Err(Error::NoHealthyProxy);
+        for _ in 0..pool.len() {
+            let proxy = match pool.pick_in(&countries).or_else(|| pool.pick()) {
+                Some(proxy) => proxy,
+                None => break,
+            };
+            self.client = proxy.client().clone();
+            self.proxy = Some(proxy.clone());
+
+            result = self.fetch_once(downloadable).await;
+            match result {
+                Err(ref err) if err.is_proxy_failure() => proxy.mark_unhealthy(),
+                Err(Error::GeoBlocked { ref allowed, .. })
+                if !proxy.entry().is_in(allowed) && pool.entries().any(|entry| entry.is_in(allowed)) => {
+                    countries = allowed.clone();
+                }
+                _ => break,
+            }
+        }
+        result
+    }
+
+    async fn fetch_once(&self, downloadable: bool) -> crate::Result<(VideoInfo, String, String)> {
+        let watch_html = self.get_html(&self.watch_url).await?;
+        let is_age_restricted = is_age_restricted(&watch_html);
+        let result = match downloadable {
+            true => self.check_downloadability(&watch_html, is_age_restricted).map(drop),
+            false => self.check_fetchability(&watch_html, is_age_restricted),
+        };
+        let result = match result {
+            Ok(()) => self.get_video_info_and_js(&watch_html, is_age_restricted).await,
+            Err(err) => Err(err),
+        };
+        self.report(result, &watch_html)
+    }
+
+    /// Passes a [`DiagnosticsBundle`] to the diagnostics handler, if `result` is an error.
+    fn report<T>(&self, result: crate::Result<T>, watch_html: &str) -> crate::Result<T> {
+        if let (Err(err), Some(handler)) = (&result, &self.diagnostics) {
+            handler(DiagnosticsBundle::new(self.video_id.clone(), err).with_watch_html(watch_html));
+        }
+        result
+    }
+
+    /// Checks, whether a watch page, which was requested from [`VideoFetcher::watch_url`],
+    /// contains a downloadable video. This is the check [`VideoFetcher::fetch`] performs, before
+    /// it requests the rest of the video.
+    ///
+    /// ### Errors
+    /// - When the video is private, geo-blocked, only for members, or otherwise not accessible.
+    /// - When the watch page contains no playability status.
+    #[inline]
+    pub fn check_watch_page(&self, watch_html: &str) -> crate::Result<()> {
+        self.check_downloadability(watch_html, is_age_restricted(watch_html)).map(drop)
+    }
+
+    fn check_downloadability(&self, watch_html: &str, is_age_restricted: bool) -> crate::Result<PlayabilityStatus> {
         let playability_status = Self::extract_playability_status(watch_html)?;
 
         match playability_status {
             PlayabilityStatus::Ok { .. } => Ok(playability_status),
             PlayabilityStatus::LoginRequired { .. } if is_age_restricted => Ok(playability_status),
-            ps => Err(Error::VideoUnavailable(Box::new(ps)))
+            ps => Err(self.unavailable(watch_html, ps))
         }
     }
 
-    fn check_fetchability(watch_html: &str, is_age_restricted: bool) -> crate::Result<()> {
+    fn check_fetchability(&self, watch_html: &str, is_age_restricted: bool) -> crate::Result<()> {
         let playability_status = Self::extract_playability_status(watch_html)?;
 
         match playability_status {
@@ -211,7 +350,42 @@ impl VideoFetcher {
             PlayabilityStatus::Unplayable { .. } => Ok(()),
             PlayabilityStatus::LiveStreamOffline { .. } => Ok(()),
             PlayabilityStatus::LoginRequired { .. } if is_age_restricted => Ok(()),
-            ps => Err(Error::VideoUnavailable(Box::new(ps)))
+            ps => Err(self.unavailable(watch_html, ps))
+        }
+    }
+
+    /// Converts the [`PlayabilityStatus`] of an unavailable video into an [`Error`], and adds the
+    /// countries of the watch page to [`Error::GeoBlocked`].
+    ///
+    /// The watch page only contains the country, YouTube located the request in, if it was not
+    /// overridden with [`VideoFetcher::with_gl`], so `blocked` stays empty otherwise.
+    fn unavailable(&self, watch_html: &str, playability_status: PlayabilityStatus) -> Error {
+        static AVAILABLE_COUNTRIES: Lazy<Regex> = Lazy::new(||
+            Regex::new(r#""availableCountries"\s*:\s*(\[[^\]]*\])"#).unwrap()
+        );
+        // the innertube context is the one the request was located in, so it's preferred
+        static GL: Lazy<[Regex; 2]> = Lazy::new(|| [
+            Regex::new(r#""INNERTUBE_CONTEXT_GL"\s*:\s*"(\w{2})""#).unwrap(),
+            Regex::new(r#""GL"\s*:\s*"(\w{2})""#).unwrap(),
+        ]);
+
+        match Error::from(playability_status) {
+            Error::GeoBlocked { reason, .. } => Error::GeoBlocked {
+                reason,
+                allowed: AVAILABLE_COUNTRIES
+                    .captures(watch_html)
+                    .and_then(|captures| serde_json::from_str(&captures[1]).ok())
+                    .unwrap_or_default(),
+                blocked: match self.gl() {
+                    Some(_) => Vec::new(),
+                    None => GL
+                        .iter()
+                        .find_map(|gl| gl.captures(watch_html))
+                        .map(|captures| vec![captures[1].to_owned()])
+                        .unwrap_or_default(),
+                },
+            },
+            err => err,
         }
     }
 
@@ -232,9 +406,7 @@ impl VideoFetcher {
             .map(serde_json::from_str::<PlayabilityStatus>)
             .filter_map(Result::ok)
             .next()
-            .ok_or_else(|| Error::UnexpectedResponse(
-                "watch html did not contain a PlayabilityStatus".into()
-            ))
+            .ok_or_else(|| Error::layout_change("the watch page", "playabilityStatus"))
     }
 
     #[inline]
@@ -245,17 +417,17 @@ impl VideoFetcher {
     ) -> crate::Result<(VideoInfo, String, String)> {
         let (js, js_player_id, player_response) = self.get_js(is_age_restricted, watch_html).await?;
 
-        let player_response = player_response.ok_or_else(|| Error::UnexpectedResponse(
-            "Could not acquire the player response from the watch html!\n\
-            It looks like YouTube changed it's API again :-/\n\
-            If this not yet reported, it would be great if you could file an issue:
-            (https://github.com/DzenanJupic/rustube/issues/new?assignees=&labels=youtube-api-changed&template=youtube_api_changed.yml).".into()
-        ))?;
+        let player_response = player_response
+            .ok_or_else(|| Error::layout_change("the watch page", "ytInitialPlayerResponse"))?;
+
+        let initial_data = crate::helper::initial_data(watch_html)
+            .and_then(|initial_data| serde_json::from_str(&initial_data).ok());
 
         let video_info = VideoInfo {
             player_response,
             adaptive_fmts_raw: None,
             is_age_restricted,
+            initial_data,
         };
 
         Ok((video_info, js, js_player_id))
@@ -276,10 +448,11 @@ impl VideoFetcher {
             }
             false => js_url(watch_html)?
         };
-        let js_player_id_regex = Regex::new(r#"/player/([\w\d]+)/"#).unwrap();
-        let js_url_string = &js_url.to_string();
-        let js_player_id_captures = js_player_id_regex.captures(js_url_string).unwrap();
-        let js_player_id = js_player_id_captures.get(1).unwrap().as_str().to_string(); 
+        static JS_PLAYER_ID: Lazy<Regex> = Lazy::new(|| Regex::new(r#"/player/([\w\d]+)/"#).unwrap());
+        let js_player_id = JS_PLAYER_ID
+            .captures(js_url.as_str())
+            .ok_or_else(|| Error::layout_change("the player url", "/player/<id>/"))?[1]
+            .to_owned();
         self
             .get_html(&js_url)
             .await
@@ -323,15 +496,11 @@ impl VideoFetcher {
     #[log_derive::logfn_inputs(Debug)]
     #[log_derive::logfn(ok = "Trace", err = "Error", fmt = "get_html() => `{}`")]
     async fn get_html(&self, url: &Url) -> crate::Result<String> {
-        Ok(
-            self.client
-                .get(url.as_str())
-                .send()
-                .await?
-                .error_for_status()?
-                .text()
-                .await?
-        )
+        let response = self.client
+            .get(url.as_str())
+            .send()
+            .await?;
+        Ok(Error::check_response(response)?.text().await?)
     }
 
     /*#[inline]
@@ -460,9 +629,7 @@ fn get_ytplayer_config(html: &str) -> crate::Result<PlayerResponse> {
             let json = parse_for_object(html, pattern).ok()?;
             deserialize_ytplayer_config(json).ok()
         })
-        .ok_or_else(|| Error::UnexpectedResponse(
-            "Could not find ytplayer_config in the watch html.".into()
-        ))
+        .ok_or_else(|| Error::layout_change("the watch page", "ytInitialPlayerResponse"))
 }
 
 /// Extracts a json object from a string starting after a pattern.
@@ -530,9 +697,7 @@ fn get_ytplayer_js(html: &str) -> crate::Result<&str> {
 
     match JS_URL_PATTERNS.captures(html) {
         Some(function_match) => Ok(function_match.get(1).unwrap().as_str()),
-        None => Err(Error::UnexpectedResponse(
-            "could not extract the ytplayer-javascript url from the watch html".into()
-        ))
+        None => Err(Error::layout_change("the watch page", "assets.js"))
     }
 }
 
@@ -593,6 +758,18 @@ fn is_json_object_end(curr_char: u8, skip: &mut bool, stack: &mut Vec<u8>) -> bo
     stack.is_empty()
 }
 
+/// A [`reqwest::ClientBuilder`] with the [`recommended_headers`] and [`recommended_cookies`].
+pub fn recommended_client_builder() -> reqwest::ClientBuilder {
+    // maybe make these feature gated, to prevent overhead for users that
+    //  don't have problems with youtube consent
+    let cookie_jar = recommended_cookies();
+    let headers = recommended_headers();
+
+    Client::builder()
+        .default_headers(headers)
+        .cookie_provider(std::sync::Arc::new(cookie_jar))
+}
+
 pub fn recommended_cookies() -> reqwest::cookie::Jar {
     let cookie = "CONSENT=YES+; Path=/; Domain=youtube.com; Secure; Expires=Fri, 01 Jan 2038 00:00:00 GMT;";
     let url = "https://youtube.com".parse().unwrap();
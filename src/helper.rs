@@ -1,19 +1,31 @@
+use std::convert::TryFrom;
+
+use futures::{Stream, StreamExt, TryStreamExt};
+use chrono::{DateTime, NaiveDate, Utc};
+use once_cell::sync::Lazy;
 use regex::Regex;
+use reqwest::Client;
+use serde::Deserialize;
 use serde_json::Value;
 use url::Url;
 
-use crate::{channel_info::{channel_video::ChannelVideo, ChannelInfo}, crate_client, playlist_info::{playlist_video::PlaylistVideo, PlaylistInfo}};
+use crate::{channel_info::{channel_about::{ChannelAbout, ChannelLink}, channel_playlist::ChannelPlaylist, channel_short::ChannelShort, channel_stream::{ChannelStream, StreamStatus}, channel_video::ChannelVideo, community_post::{CommunityPost, Poll}, ChannelInfo}, crate_client, playlist_info::{playlist_video::{Availability, PlaylistVideo}, PlaylistInfo, PlaylistPrivacy}};
+use crate::comment_info::{Comment, CommentPage};
+use crate::playlist_info::req_json::ContinuationReq;
+use crate::{ChannelIdBuf, IdBuf, PlaylistIdBuf};
+use crate::search_info::{SearchChannel, SearchCorrection, SearchPage, SearchPlaylist, SearchResult, SearchVideo};
+use crate::video_info::{chapter::Chapter, heatmap::HeatMarker, player_response::video_details::{TextRun, Thumbnail}, related_video::RelatedVideo};
 
 pub(crate) fn initial_data(watch_html: &str) -> Option<String> {
-    let regex_pattern = vec![
-        r#"window\[['"]ytInitialData['"]]\s*=\s*"#,
-        r#"ytInitialData\s*=\s*"#,
-    ];
-    for pattern in regex_pattern {
-        let reg_p =  Regex::new(pattern).unwrap();
-        if let Some(initial_data) = reg_p.find(&watch_html) {
+    static PATTERNS: Lazy<[Regex; 2]> = Lazy::new(|| [
+        Regex::new(r#"window\[['"]ytInitialData['"]]\s*=\s*"#).unwrap(),
+        Regex::new(r#"ytInitialData\s*=\s*"#).unwrap(),
+    ]);
+
+    for pattern in PATTERNS.iter() {
+        if let Some(initial_data) = pattern.find(watch_html) {
             let start_point = initial_data.end();
-            let object: String = find_object_from_startpoint(&watch_html, start_point)?;
+            let object: String = find_object_from_startpoint(watch_html, start_point)?;
             return Some(object);
         }
     }
@@ -21,7 +33,7 @@ pub(crate) fn initial_data(watch_html: &str) -> Option<String> {
 }
 
 pub(crate) fn find_object_from_startpoint(html: &str, start_point: usize) -> Option<String> {
-    let html = &html[start_point..];
+    let html = html.get(start_point..)?;
     let chars: Vec<char> = html.chars().collect();
 
     if !matches!(chars.get(0), Some('{') | Some('[')) {
@@ -41,9 +53,10 @@ pub(crate) fn find_object_from_startpoint(html: &str, start_point: usize) -> Opt
     ].iter().cloned().collect::<std::collections::HashMap<_, _>>();
 
     while i < chars.len() {
-        if stack.is_empty() {
-            break;
-        }
+        let curr_context = match stack.last() {
+            Some(curr_context) => *curr_context,
+            None => break,
+        };
 
         if let Some(curr) = curr_char {
             if !curr.is_whitespace() {
@@ -52,16 +65,15 @@ pub(crate) fn find_object_from_startpoint(html: &str, start_point: usize) -> Opt
         }
 
         curr_char = Some(chars[i]);
-        let curr_context = stack.last().unwrap();
 
         if let Some(curr_char) = curr_char {
-            if curr_char == context_closers[curr_context] {
+            if context_closers.get(&curr_context) == Some(&curr_char) {
                 stack.pop();
                 i += 1;
                 continue;
             }
 
-            if *curr_context == '"' || *curr_context == '/' {
+            if curr_context == '"' || curr_context == '/' {
                 if curr_char == '\\' {
                     i += 2;
                     continue;
@@ -82,117 +94,1463 @@ pub(crate) fn find_object_from_startpoint(html: &str, start_point: usize) -> Opt
     Some(full_obj)
 }
 
-pub(crate) fn parese_playlist_videos(obj_data: &str) -> (Vec<PlaylistVideo>, Option<String>) {
-    let initial_data: Value = serde_json::from_str(&obj_data).unwrap();
-    let videos;
-    let section_contents = initial_data["contents"][
-        "twoColumnBrowseResultsRenderer"][
-        "tabs"][0]["tabRenderer"]["content"][
-        "sectionListRenderer"]["contents"].clone();
-    if !section_contents.is_null() {
-        let mut important_content = section_contents[
-            0]["itemSectionRenderer"][
-            "contents"][0]["playlistVideoListRenderer"].clone();
-        if important_content.is_null() {
-            important_content = section_contents[
-                    1]["itemSectionRenderer"][
-                    "contents"][0]["playlistVideoListRenderer"].clone()
-        }
-        videos = important_content["contents"].clone();
-    } else {
-        let important_content = initial_data["onResponseReceivedActions"][0]["appendContinuationItemsAction"]["continuationItems"].clone();
-        videos = important_content;
-    }
-    let mut videos_raw = videos.as_array().unwrap().to_owned();
-    let mut results = Vec::new();
-    let continuation = videos_raw[videos_raw.len() - 1]["continuationItemRenderer"]["continuationEndpoint"]["continuationCommand"]["token"].clone();
-    let mut continuation_id = None;
-    if let Some(continuation) = continuation.as_str() {
-        let continuation_index = videos_raw.len() - 1;
-        videos_raw = videos_raw[..continuation_index].to_vec();
-        continuation_id = Some(continuation.to_string());
-    }
-    for video in videos_raw {     
-        let pvideo = serde_json::from_value::<PlaylistVideo>(video["playlistVideoRenderer"].clone()).unwrap();   
-        results.push(pvideo);
+/// Extracts the videos and the next continuation token from either the `ytInitialData` of a
+/// playlist page, or a `youtubei/v1/browse` continuation response.
+pub(crate) fn parese_playlist_videos(data: &Value) -> crate::Result<(Vec<PlaylistVideo>, Option<String>)> {
+    let items = match data["onResponseReceivedActions"].as_array() {
+        Some(actions) => actions
+            .iter()
+            .filter_map(|action| action["appendContinuationItemsAction"]["continuationItems"].as_array())
+            .flatten()
+            .collect::<Vec<_>>(),
+        None => data["contents"]["twoColumnBrowseResultsRenderer"]["tabs"][0]["tabRenderer"][
+            "content"]["sectionListRenderer"]["contents"]
+            .as_array()
+            .ok_or_else(|| crate::Error::layout_change(
+                "the playlist page",
+                "contents.twoColumnBrowseResultsRenderer.tabs[0].tabRenderer.content.sectionListRenderer.contents",
+            ))?
+            .iter()
+            .filter_map(|section| section["itemSectionRenderer"]["contents"].as_array())
+            .flatten()
+            .filter_map(|content| content["playlistVideoListRenderer"]["contents"].as_array())
+            .flatten()
+            .collect(),
+    };
+
+    let mut videos = Vec::with_capacity(items.len());
+    let mut continuation = None;
+    for item in items {
+        if let Some(token) = continuation_token(item) {
+            continuation = Some(token);
+            continue;
+        }
+        let renderer = &item["playlistVideoRenderer"];
+        if renderer.is_null() {
+            continue;
+        }
+        videos.push(parse_playlist_video(renderer)?);
     }
-    
-    (results, continuation_id)
+
+    Ok((videos, continuation))
+}
+
+fn parse_playlist_video(renderer: &Value) -> crate::Result<PlaylistVideo> {
+    let mut video = PlaylistVideo::deserialize(renderer)?;
+
+    video.owner_channel_id = browse_channel_id(&renderer["shortBylineText"]["runs"][0]);
+
+    // `1.2K views • 2 years ago`
+    let video_info = renderer["videoInfo"]["runs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|run| run["text"].as_str())
+        .map(str::trim)
+        .filter(|text| !text.is_empty() && *text != "•")
+        .collect::<Vec<_>>();
+    video.view_count_text = video_info
+        .iter()
+        .find(|text| text.contains("view"))
+        .map(|text| text.to_string());
+    video.published_time_text = video_info
+        .iter()
+        .find(|text| !text.contains("view"))
+        .map(|text| text.to_string());
+
+    let is_members_only = renderer["badges"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .any(|badge| badge["metadataBadgeRenderer"]["style"].as_str() == Some("BADGE_STYLE_TYPE_MEMBERS_ONLY"));
+    let unplayable_text = text(&renderer["unplayableText"]).unwrap_or_default().to_lowercase();
+    video.availability = match video.title.as_str() {
+        _ if is_members_only => Availability::MembersOnly,
+        _ if video.is_playable => Availability::Available,
+        "[Private video]" => Availability::Private,
+        "[Deleted video]" => Availability::Deleted,
+        _ if unplayable_text.contains("country") => Availability::RegionBlocked,
+        _ => Availability::Unavailable,
+    };
+
+    Ok(video)
 }
 
 pub(crate) fn parese_playlist_metadata(obj_data: &str) -> Result<PlaylistInfo, crate::Error> {
     let initial_data: Value = serde_json::from_str(&obj_data)?;
     let playlist_info_v = initial_data["microformat"]["microformatDataRenderer"].clone();
-    let playlist_info: PlaylistInfo = serde_json::from_value(playlist_info_v).unwrap();
+    let mut playlist_info: PlaylistInfo = serde_json::from_value(playlist_info_v)?;
+    parse_playlist_header(&initial_data, &mut playlist_info);
     Ok(playlist_info)
 }
 
-pub(crate) fn parese_channel_videos(obj_data: &str, channel_name: String) -> (Vec<ChannelVideo>, Option<String>) {
-    let initial_data: Value = serde_json::from_str(&obj_data).unwrap();
-    let mut videos = initial_data["contents"][
-        "twoColumnBrowseResultsRenderer"][
-        "tabs"][1]["tabRenderer"]["content"]["richGridRenderer"]["contents"].clone();
-    if videos.is_null() {
-        videos = initial_data["onResponseReceivedActions"][0]["appendContinuationItemsAction"]["continuationItems"].clone();
+/// Completes the [`PlaylistInfo`] with the playlist header, or the sidebar of older layouts.
+fn parse_playlist_header(initial_data: &Value, playlist_info: &mut PlaylistInfo) {
+    let header = &initial_data["header"]["playlistHeaderRenderer"];
+    let sidebar_items = &initial_data["sidebar"]["playlistSidebarRenderer"]["items"];
+    let primary_info = &sidebar_items[0]["playlistSidebarPrimaryInfoRenderer"];
+    let owner = match header["ownerText"] {
+        Value::Null => &sidebar_items[1]["playlistSidebarSecondaryInfoRenderer"]["videoOwner"][
+            "videoOwnerRenderer"]["title"],
+        ref owner => owner,
+    };
+
+    playlist_info.owner_name = text(owner).filter(|name| !name.is_empty());
+    playlist_info.owner_channel_id = browse_channel_id(&owner["runs"][0]);
+
+    // the sidebar stats are: `<n> videos`, `<n> views`, `Last updated on <date>`
+    let stats = primary_info["stats"].as_array().map(Vec::as_slice).unwrap_or_default();
+    let stat = |header_key: &str, index: usize| text(&header[header_key])
+        .or_else(|| stats.get(index).and_then(text));
+    playlist_info.video_count = stat("numVideosText", 0).as_deref().and_then(parse_count);
+    playlist_info.view_count = stat("viewCountText", 1).as_deref().and_then(parse_count);
+    playlist_info.last_updated_text = header["byline"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|byline| text(&byline["playlistBylineRenderer"]["text"]))
+        .find(|text| text.contains("pdated"))
+        .or_else(|| stats.get(2).and_then(text));
+    playlist_info.last_updated = playlist_info.last_updated_text
+        .as_deref()
+        .and_then(parse_updated_date);
+
+    playlist_info.privacy = header["privacy"]
+        .as_str()
+        .or_else(|| primary_info["privacyForm"]["dropdownFormFieldRenderer"]["dropdown"][
+            "dropdownRenderer"]["entries"]
+            .as_array()?
+            .iter()
+            .map(|entry| &entry["privacyDropdownItemRenderer"])
+            .find(|entry| entry["isSelected"].as_bool() == Some(true))?["icon"]["iconType"]
+            .as_str()?
+            .strip_prefix("PRIVACY_"))
+        .or_else(|| primary_info["badges"]
+            .as_array()?
+            .iter()
+            .find_map(|badge| badge["metadataBadgeRenderer"]["icon"]["iconType"].as_str()?.strip_prefix("PRIVACY_")))
+        .and_then(|privacy| serde_json::from_value(Value::from(privacy)).ok())
+        .or_else(|| match initial_data["microformat"]["microformatDataRenderer"]["unlisted"].as_bool()? {
+            true => Some(PlaylistPrivacy::Unlisted),
+            false => None,
+        });
+
+    playlist_info.unavailable_videos_hidden = initial_data["alerts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|alert| alert
+            .as_object()?
+            .values()
+            .find_map(|renderer| text(&renderer["text"])))
+        .any(|text| text.to_lowercase().contains("unavailable videos are hidden"));
+}
+
+/// Parses the date of a text like `Last updated on Apr 5, 2021`.
+/// Returns `None` for relative dates, like `Updated today`.
+pub(crate) fn parse_updated_date(text: &str) -> Option<NaiveDate> {
+    static DATE: Lazy<Regex> = Lazy::new(||
+        Regex::new(r"[A-Z][a-z]{2} \d{1,2}, \d{4}").unwrap()
+    );
+
+    NaiveDate::parse_from_str(DATE.find(text)?.as_str(), "%b %d, %Y").ok()
+}
+
+pub(crate) fn parese_channel_videos(obj_data: &str, channel_name: String) -> crate::Result<(Vec<ChannelVideo>, Option<String>)> {
+    let initial_data: Value = serde_json::from_str(obj_data)?;
+    let (mut videos, continuation) = parse_channel_tab_page(&initial_data, &parse_channel_video)?;
+    for video in &mut videos {
+        video.author.clone_from(&channel_name);
+    }
+    Ok((videos, continuation))
+}
+
+/// Parses a `videoRenderer` of the Videos tab, or returns `None` for items of other types.
+pub(crate) fn parse_channel_video(item: &Value) -> crate::Result<Option<ChannelVideo>> {
+    let renderer = &item["videoRenderer"];
+    if renderer.is_null() {
+        return Ok(None);
+    }
+    serde_json::from_value(renderer.clone())
+        .map(Some)
+        .map_err(|err| crate::Error::LayoutChange {
+            context: "the channel page".into(),
+            path: "videoRenderer".into(),
+            source: Some(err),
+        })
+}
+
+pub(crate) fn parese_channel_metadata(obj_data: &str) -> Result<ChannelInfo, crate::Error> {
+    let initial_data: Value = serde_json::from_str(obj_data)?;
+    let playlist_info_v = initial_data["microformat"]["microformatDataRenderer"].clone();
+    serde_json::from_value(playlist_info_v).map_err(|err| crate::Error::LayoutChange {
+        context: "the channel page".into(),
+        path: "microformat.microformatDataRenderer".into(),
+        source: Some(err),
+    })
+}
+
+/// Parses a response of `youtubei/v1/search`, either of the first page, or of a continuation.
+pub(crate) fn parse_search_page(response: &Value) -> crate::Result<SearchPage> {
+    let sections = match response["contents"]["twoColumnSearchResultsRenderer"]["primaryContents"][
+        "sectionListRenderer"]["contents"].as_array() {
+        Some(sections) => sections,
+        None => response["onResponseReceivedCommands"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find_map(|command| command["appendContinuationItemsAction"]["continuationItems"].as_array())
+            .ok_or_else(|| crate::Error::layout_change(
+                "the search response",
+                "onResponseReceivedCommands[].appendContinuationItemsAction.continuationItems",
+            ))?,
+    };
+
+    let mut page = SearchPage {
+        estimated_results: response["estimatedResults"].as_str().and_then(|count| count.parse().ok()),
+        ..SearchPage::default()
+    };
+    for section in sections {
+        if let Some(token) = continuation_token(section) {
+            page.continuation = Some(token);
+            continue;
+        }
+
+        let items = section["itemSectionRenderer"]["contents"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            // shelves like "Latest from ..." or "People also watched"
+            .flat_map(|item| match item["shelfRenderer"]["content"]["verticalListRenderer"]["items"].as_array() {
+                Some(items) => items.iter().collect(),
+                None => vec![item],
+            });
+        for item in items {
+            if let Some(correction) = parse_search_correction(item) {
+                page.correction = Some(correction);
+            } else if let Some(result) = parse_search_result(item) {
+                page.results.push(result);
+            }
+        }
+    }
+    Ok(page)
+}
+
+fn parse_search_correction(item: &Value) -> Option<SearchCorrection> {
+    let (renderer, is_auto_corrected) = match (&item["showingResultsForRenderer"], &item["didYouMeanRenderer"]) {
+        (Value::Null, Value::Null) => return None,
+        (Value::Null, renderer) => (renderer, false),
+        (renderer, _) => (renderer, true),
+    };
+    let corrected_query = renderer["correctedQueryEndpoint"]["searchEndpoint"]["query"]
+        .as_str()
+        .map(str::to_owned)
+        .or_else(|| text(&renderer["correctedQuery"]))?;
+    Some(SearchCorrection { corrected_query, is_auto_corrected })
+}
+
+fn parse_search_result(item: &Value) -> Option<SearchResult> {
+    if !item["videoRenderer"].is_null() {
+        return parse_search_video(&item["videoRenderer"]).map(SearchResult::Video);
+    }
+    if !item["movieRenderer"].is_null() {
+        return parse_search_video(&item["movieRenderer"]).map(SearchResult::Movie);
+    }
+    if !item["channelRenderer"].is_null() {
+        return parse_search_channel(&item["channelRenderer"]).map(SearchResult::Channel);
+    }
+    if !item["playlistRenderer"].is_null() {
+        let renderer = &item["playlistRenderer"];
+        let byline = &renderer["longBylineText"]["runs"][0];
+        return Some(SearchResult::Playlist(SearchPlaylist {
+            playlist_id: PlaylistIdBuf::from_string(renderer["playlistId"].as_str()?.to_owned()).ok()?,
+            title: text(&renderer["title"]).unwrap_or_default(),
+            author: byline["text"].as_str().map(str::to_owned),
+            channel_id: browse_channel_id(byline),
+            video_count: renderer["videoCount"].as_str().and_then(parse_count),
+            thumbnails: Thumbnail::deserialize_vec(&renderer["thumbnails"][0]).unwrap_or_default(),
+        }));
+    }
+    // newer layouts only use lockups for playlists and mixes
+    parse_channel_playlist(item).map(|playlist| SearchResult::Playlist(SearchPlaylist {
+        playlist_id: playlist.playlist_id,
+        title: playlist.title,
+        author: None,
+        channel_id: None,
+        video_count: playlist.video_count,
+        thumbnails: playlist.thumbnails,
+    }))
+}
+
+fn parse_search_video(renderer: &Value) -> Option<SearchVideo> {
+    let byline = match renderer["ownerText"]["runs"][0] {
+        Value::Null => &renderer["longBylineText"]["runs"][0],
+        ref byline => byline,
+    };
+    let is_live = renderer["badges"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .any(|badge| badge["metadataBadgeRenderer"]["style"].as_str() == Some("BADGE_STYLE_TYPE_LIVE_NOW"));
+
+    Some(SearchVideo {
+        video_id: IdBuf::from_string(renderer["videoId"].as_str()?.to_owned()).ok()?,
+        title: text(&renderer["title"]).unwrap_or_default(),
+        length_seconds: text(&renderer["lengthText"]).as_deref().and_then(parse_length),
+        thumbnails: Thumbnail::deserialize_vec(&renderer["thumbnail"]).unwrap_or_default(),
+        author: byline["text"].as_str().unwrap_or_default().to_owned(),
+        channel_id: browse_channel_id(byline),
+        view_count: text(&renderer["viewCountText"]).as_deref().and_then(parse_count),
+        published_time_text: text(&renderer["publishedTimeText"]),
+        description_snippet: text(&renderer["detailedMetadataSnippets"][0]["snippetText"])
+            .or_else(|| text(&renderer["descriptionSnippet"]))
+            .unwrap_or_default(),
+        is_live,
+    })
+}
+
+fn parse_search_channel(renderer: &Value) -> Option<SearchChannel> {
+    let subscribers = text(&renderer["subscriberCountText"]);
+    // newer layouts show the handle in place of the subscriber count, and the subscriber count
+    // in place of the video count
+    let (handle, subscribers) = match subscribers {
+        Some(handle) if handle.starts_with('@') => (Some(handle), text(&renderer["videoCountText"])),
+        subscribers => (None, subscribers),
+    };
+
+    Some(SearchChannel {
+        channel_id: ChannelIdBuf::from_string(renderer["channelId"].as_str()?.to_owned()).ok()?,
+        title: text(&renderer["title"]).unwrap_or_default(),
+        handle,
+        subscriber_count: subscribers.as_deref().and_then(parse_count),
+        thumbnails: Thumbnail::deserialize_vec(&renderer["thumbnail"]).unwrap_or_default(),
+        description_snippet: text(&renderer["descriptionSnippet"]).unwrap_or_default(),
+    })
+}
+
+/// Extracts the channel id of a run, which links to a channel.
+fn browse_channel_id(run: &Value) -> Option<ChannelIdBuf> {
+    let id = run["navigationEndpoint"]["browseEndpoint"]["browseId"].as_str()?;
+    ChannelIdBuf::from_string(id.to_owned()).ok()
+}
+
+/// Extracts the text of a `{ simpleText }` or `{ runs: [{ text }] }` object.
+pub(crate) fn text(value: &Value) -> Option<String> {
+    if let Some(text) = value["simpleText"].as_str() {
+        return Some(text.to_owned());
     }
-    if videos.is_null() {
-        return (Vec::new(), None);
+    value["runs"]
+        .as_array()
+        .map(|runs| runs.iter().filter_map(|run| run["text"].as_str()).collect())
+}
+
+/// Extracts the chapters from the macro-markers of the watch page's `ytInitialData`.
+pub(crate) fn parse_chapters(initial_data: &Value) -> Vec<Chapter> {
+    let markers_map = &initial_data["playerOverlays"]["playerOverlayRenderer"][
+        "decoratedPlayerBarRenderer"]["decoratedPlayerBarRenderer"]["playerBar"][
+        "multiMarkersPlayerBarRenderer"]["markersMap"];
+
+    for key in ["DESCRIPTION_CHAPTERS", "AUTO_CHAPTERS"] {
+        let chapters = markers_map
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|marker| marker["key"].as_str() == Some(key))
+            .filter_map(|marker| marker["value"]["chapters"].as_array())
+            .flatten()
+            .filter_map(|chapter| {
+                let chapter = &chapter["chapterRenderer"];
+                Some(Chapter {
+                    title: text(&chapter["title"])?,
+                    start_ms: chapter["timeRangeStartMillis"].as_u64()?,
+                    end_ms: 0,
+                    thumbnails: Thumbnail::deserialize_vec(&chapter["thumbnail"]).unwrap_or_default(),
+                })
+            })
+            .collect::<Vec<_>>();
+        if !chapters.is_empty() {
+            return chapters;
+        }
     }
-    let mut videos_raw = videos.as_array().unwrap().to_owned();
-    let mut results = Vec::new();
-    let continuation = videos_raw[videos_raw.len() - 1]["continuationItemRenderer"]["continuationEndpoint"]["continuationCommand"]["token"].clone();
-    let mut continuation_id = None;
-    if let Some(continuation) = continuation.as_str() {
-        let continuation_index = videos_raw.len() - 1;
-        videos_raw = videos_raw[..continuation_index].to_vec();
-        continuation_id = Some(continuation.to_string());
+
+    // The player bar markers are missing for some clients, but the chapters are still listed
+    // in the engagement panel.
+    initial_data["engagementPanels"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|panel| &panel["engagementPanelSectionListRenderer"])
+        .find(|panel| matches!(
+            panel["panelIdentifier"].as_str(),
+            Some(id) if id.starts_with("engagement-panel-macro-markers") && id.ends_with("chapters")
+        ))
+        .and_then(|panel| panel["content"]["macroMarkersListRenderer"]["contents"].as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            let item = &item["macroMarkersListItemRenderer"];
+            Some(Chapter {
+                title: text(&item["title"])?,
+                start_ms: item["onTap"]["watchEndpoint"]["startTimeSeconds"].as_u64()? * 1000,
+                end_ms: 0,
+                thumbnails: Thumbnail::deserialize_vec(&item["thumbnail"]).unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Extracts the chapters from the timestamp lines of a video description.
+///
+/// Like YouTube itself, this only accepts the timestamps as chapters, if there are at least three
+/// of them, the first one starts at `0:00`, and they are in ascending order.
+pub(crate) fn parse_description_chapters(description: &str) -> Vec<Chapter> {
+    static TIMESTAMP: Lazy<Regex> = Lazy::new(||
+        Regex::new(r"(?:(\d{1,2}):)?(\d{1,2}):(\d{2})").unwrap()
+    );
+    let is_separator = |c: char| c.is_whitespace() || "-–—:|•*·()[]".contains(c);
+
+    let mut chapters: Vec<Chapter> = Vec::new();
+    for line in description.lines() {
+        let captures = match TIMESTAMP.captures(line) {
+            Some(captures) => captures,
+            None => continue,
+        };
+        let timestamp = match captures.get(0) {
+            Some(timestamp) => timestamp,
+            None => continue,
+        };
+        let before = line[..timestamp.start()].trim_matches(is_separator);
+        let after = line[timestamp.end()..].trim_matches(is_separator);
+        let title = match (before.is_empty(), after.is_empty()) {
+            (true, false) => after,
+            (false, true) => before,
+            _ => continue,
+        };
+
+        let part = |i: usize| captures
+            .get(i)
+            .and_then(|m| m.as_str().parse::<u64>().ok())
+            .unwrap_or(0);
+        let start_ms = (part(1) * 3600 + part(2) * 60 + part(3)) * 1000;
+
+        let in_order = match chapters.last() {
+            Some(last) => start_ms > last.start_ms,
+            None => start_ms == 0,
+        };
+        if !in_order {
+            return Vec::new();
+        }
+
+        chapters.push(Chapter {
+            title: title.to_owned(),
+            start_ms,
+            end_ms: 0,
+            thumbnails: Vec::new(),
+        });
+    }
+
+    if chapters.len() < 3 {
+        return Vec::new();
+    }
+    chapters
+}
+
+/// Extracts the [`TextRun`]s of a `{ runs: [{ text, navigationEndpoint }] }` object.
+/// A `{ simpleText }` object is treated as a single run without a link.
+pub(crate) fn text_runs(value: &Value) -> Vec<TextRun> {
+    if let Some(text) = value["simpleText"].as_str() {
+        return vec![TextRun { text: text.to_owned(), url: None }];
+    }
+    value["runs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|run| {
+            let endpoint = &run["navigationEndpoint"];
+            let url = endpoint["urlEndpoint"]["url"]
+                .as_str()
+                .or_else(|| endpoint["commandMetadata"]["webCommandMetadata"]["url"].as_str());
+            Some(TextRun {
+                text: run["text"].as_str()?.to_owned(),
+                url: url.map(str::to_owned),
+            })
+        })
+        .collect()
+}
+
+/// Parses an abbreviated count, like `1,234 views`, `1.2K`, or `3M subscribers`.
+/// Returns `None` if the text does not start with a number (i.e. `No views`).
+pub(crate) fn parse_count(text: &str) -> Option<u64> {
+    static COUNT: Lazy<Regex> = Lazy::new(||
+        Regex::new(r"^\s*([\d,.]+)([KMB]?)\b").unwrap()
+    );
+
+    let captures = COUNT.captures(text)?;
+    let multiplier = match &captures[2] {
+        "K" => 1e3,
+        "M" => 1e6,
+        "B" => 1e9,
+        _ => 1.0,
+    };
+    let number = captures[1].replace(',', "");
+    if multiplier == 1.0 {
+        return number.replace('.', "").parse().ok();
     }
-    for video in videos_raw {
-        let pvideo = serde_json::from_value::<ChannelVideo>(video["richItemRenderer"]["content"]["videoRenderer"].clone());
-        if pvideo.is_err() { 
-            continue; 
+    number
+        .parse::<f64>()
+        .ok()
+        .map(|number| (number * multiplier).round() as u64)
+}
+
+/// Extracts the token of a `continuationItemRenderer`.
+pub(crate) fn continuation_token(item: &Value) -> Option<String> {
+    let renderer = &item["continuationItemRenderer"];
+    renderer["continuationEndpoint"]["continuationCommand"]["token"]
+        .as_str()
+        .or_else(|| renderer["button"]["buttonRenderer"]["command"]["continuationCommand"]["token"].as_str())
+        .map(str::to_owned)
+}
+
+/// Requests the next page of `youtubei/v1/<endpoint>` for a continuation token.
+pub(crate) async fn get_continuation(client: &Client, endpoint: &str, continuation: &str) -> crate::Result<Value> {
+    let body = ContinuationReq::new(continuation);
+    let response = client
+        .post(format!("https://www.youtube.com/youtubei/v1/{endpoint}?prettyPrint=false"))
+        .json(&body)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(response.json().await?)
+}
+
+/// Lazily walks the continuations of `youtubei/v1/<endpoint>`, starting at `continuation`.
+///
+/// `parse` extracts the items and the next continuation token of a page. The stream ends after
+/// the first error, or when there is no next continuation. If no `client` is provided, a new one
+/// is created for the first page, and reused for all following ones.
+pub(crate) fn continuation_stream<T, P>(
+    endpoint: &'static str,
+    client: Option<Client>,
+    continuation: Option<String>,
+    parse: P,
+) -> impl Stream<Item=crate::Result<T>>
+    where
+        P: FnMut(&Value) -> crate::Result<(Vec<T>, Option<String>)> {
+    continuation_pages(endpoint, client, continuation, parse)
+        .map_ok(|(items, _)| futures::stream::iter(items.into_iter().map(crate::Result::Ok)))
+        .try_flatten()
+}
+
+/// Like [`continuation_stream`], but yields the items of each page at once, together with the
+/// continuation token of the following page.
+pub(crate) fn continuation_pages<T, P>(
+    endpoint: &'static str,
+    client: Option<Client>,
+    continuation: Option<String>,
+    parse: P,
+) -> impl Stream<Item=crate::Result<(Vec<T>, Option<String>)>>
+    where
+        P: FnMut(&Value) -> crate::Result<(Vec<T>, Option<String>)> {
+    futures::stream::try_unfold(
+        (client, continuation, parse),
+        move |(client, continuation, mut parse): (Option<Client>, Option<String>, P)| async move {
+            let continuation = match continuation {
+                Some(continuation) => continuation,
+                None => return Ok::<_, crate::Error>(None),
+            };
+            let client = match client {
+                Some(client) => client,
+                None => crate_client()?,
+            };
+
+            let page = get_continuation(&client, endpoint, &continuation).await?;
+            let (items, continuation) = parse(&page)?;
+            Ok(Some(((items, continuation.clone()), (Some(client), continuation, parse))))
+        },
+    )
+}
+
+/// Extracts the continuation token of the comment section from the watch page's `ytInitialData`.
+pub(crate) fn comments_continuation(initial_data: &Value) -> Option<String> {
+    let section = initial_data["contents"]["twoColumnWatchNextResults"]["results"]["results"]["contents"]
+        .as_array()?
+        .iter()
+        .map(|content| &content["itemSectionRenderer"])
+        .find(|section| section["sectionIdentifier"].as_str() == Some("comment-item-section"))?;
+
+    section["contents"]
+        .as_array()?
+        .iter()
+        .find_map(continuation_token)
+}
+
+/// Parses a response of `youtubei/v1/next` for a comments (or replies) continuation.
+///
+/// Newer responses only contain a `commentViewModel` per comment, which references the actual
+/// content in `frameworkUpdates.entityBatchUpdate.mutations` by its keys.
+pub(crate) fn parse_comment_page(response: &Value) -> crate::Result<CommentPage> {
+    let endpoints = response["onResponseReceivedEndpoints"]
+        .as_array()
+        .ok_or_else(|| crate::Error::layout_change("the comments response", "onResponseReceivedEndpoints"))?;
+    let entities: std::collections::HashMap<&str, &Value> = response["frameworkUpdates"]["entityBatchUpdate"]["mutations"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|mutation| {
+            let (_, payload) = mutation["payload"].as_object()?.iter().next()?;
+            Some((payload["key"].as_str().or_else(|| mutation["entityKey"].as_str())?, payload))
+        })
+        .collect();
+
+    let mut page = CommentPage::default();
+    let items = endpoints
+        .iter()
+        .filter_map(|endpoint| endpoint["reloadContinuationItemsCommand"]["continuationItems"]
+            .as_array()
+            .or_else(|| endpoint["appendContinuationItemsAction"]["continuationItems"].as_array())
+        )
+        .flatten();
+
+    for item in items {
+        if let Some(header) = item.get("commentsHeaderRenderer") {
+            let sort_token = |i: usize| header["sortMenu"]["sortFilterSubMenuRenderer"]["subMenuItems"][i][
+                "serviceEndpoint"]["continuationCommand"]["token"]
+                .as_str()
+                .map(str::to_owned);
+            page.top_continuation = sort_token(0);
+            page.newest_continuation = sort_token(1);
+            page.comment_count = text(&header["countText"]).as_deref().and_then(parse_count);
+        } else if let Some(thread) = item.get("commentThreadRenderer") {
+            let mut comment = match thread.get("commentViewModel") {
+                Some(view_model) => parse_comment_view_model(&view_model["commentViewModel"], &entities)?,
+                None => parse_comment(&thread["comment"]["commentRenderer"])?,
+            };
+            comment.replies_continuation = thread["replies"]["commentRepliesRenderer"]["contents"]
+                .as_array()
+                .and_then(|contents| contents.iter().find_map(continuation_token));
+            page.comments.push(comment);
+        } else if let Some(comment) = item.get("commentRenderer") {
+            page.comments.push(parse_comment(comment)?);
+        } else if let Some(view_model) = item.get("commentViewModel") {
+            page.comments.push(parse_comment_view_model(view_model, &entities)?);
+        } else if let Some(token) = continuation_token(item) {
+            page.continuation = Some(token);
         }
-        results.push(pvideo.unwrap().add_author(channel_name.clone()));
     }
-    
-    (results, continuation_id)
+
+    Ok(page)
 }
 
-pub(crate) fn parese_channel_metadata(obj_data: &str) -> Result<ChannelInfo, crate::Error> {
-    let initial_data: Value = serde_json::from_str(&obj_data)?;
-    let playlist_info_v = initial_data["microformat"]["microformatDataRenderer"].clone();
-    let playlist_info: ChannelInfo = serde_json::from_value(playlist_info_v).unwrap();
-    Ok(playlist_info)
+fn parse_comment(renderer: &Value) -> crate::Result<Comment> {
+    let id = renderer["commentId"]
+        .as_str()
+        .ok_or_else(|| crate::Error::layout_change("a comment", "commentRenderer.commentId"))?;
+
+    Ok(Comment {
+        id: id.to_owned(),
+        author: text(&renderer["authorText"]).unwrap_or_default(),
+        author_channel_id: renderer["authorEndpoint"]["browseEndpoint"]["browseId"]
+            .as_str()
+            .and_then(|id| ChannelIdBuf::from_string(id.to_owned()).ok()),
+        author_thumbnails: Thumbnail::deserialize_vec(&renderer["authorThumbnail"]).unwrap_or_default(),
+        text: text_runs(&renderer["contentText"]),
+        like_count: text(&renderer["voteCount"])
+            .as_deref()
+            .and_then(parse_count)
+            .unwrap_or(0),
+        published_time_text: text(&renderer["publishedTimeText"]).unwrap_or_default(),
+        reply_count: renderer["replyCount"].as_u64().unwrap_or(0),
+        is_pinned: renderer.get("pinnedCommentBadge").is_some(),
+        is_hearted: renderer["actionButtons"]["commentActionButtonsRenderer"]["creatorHeart"][
+            "creatorHeartRenderer"]["isHearted"]
+            .as_bool()
+            .unwrap_or(false),
+        is_author_channel_owner: renderer["authorIsChannelOwner"].as_bool().unwrap_or(false),
+        replies_continuation: None,
+    })
+}
+
+fn parse_comment_view_model(view_model: &Value, entities: &std::collections::HashMap<&str, &Value>) -> crate::Result<Comment> {
+    let entity = view_model["commentKey"]
+        .as_str()
+        .and_then(|key| entities.get(key))
+        .ok_or_else(|| crate::Error::layout_change("a comment", "commentViewModel.commentKey"))?;
+    let properties = &entity["properties"];
+    let id = properties["commentId"]
+        .as_str()
+        .or_else(|| view_model["commentId"].as_str())
+        .ok_or_else(|| crate::Error::layout_change("a comment", "commentEntityPayload.properties.commentId"))?;
+    let author = &entity["author"];
+    let toolbar = &entity["toolbar"];
+    let toolbar_state = view_model["toolbarStateKey"]
+        .as_str()
+        .and_then(|key| entities.get(key));
+
+    Ok(Comment {
+        id: id.to_owned(),
+        author: author["displayName"].as_str().unwrap_or_default().to_owned(),
+        author_channel_id: author["channelId"]
+            .as_str()
+            .and_then(|id| ChannelIdBuf::from_string(id.to_owned()).ok()),
+        author_thumbnails: entity["avatar"]["image"]["sources"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|source| serde_json::from_value(source.clone()).ok())
+            .collect(),
+        text: attributed_text_runs(&properties["content"]).unwrap_or_default(),
+        like_count: toolbar["likeCountNotliked"]
+            .as_str()
+            .and_then(parse_count)
+            .unwrap_or(0),
+        published_time_text: properties["publishedTime"].as_str().unwrap_or_default().to_owned(),
+        reply_count: toolbar["replyCount"]
+            .as_str()
+            .and_then(parse_count)
+            .unwrap_or(0),
+        is_pinned: view_model.get("pinnedText").is_some(),
+        is_hearted: toolbar_state
+            .is_some_and(|state| state["heartState"] == "TOOLBAR_HEART_STATE_HEARTED"),
+        is_author_channel_owner: author["isCreator"].as_bool().unwrap_or(false),
+        replies_continuation: None,
+    })
 }
 
-pub async fn search_videos(query: &str) -> crate::Result<Vec<ChannelVideo>> {
-    let client = crate_client().unwrap();
-    let mut req_url = Url::parse("https://www.youtube.com/results").unwrap();
-    req_url.query_pairs_mut().append_pair("search_query", query);
-    let req = client.get(req_url).send().await?;
-    let body = req.text().await?;
-    let init_str = initial_data(&body).unwrap();
-    let init_obj: Value = serde_json::from_str(&init_str).unwrap();
+/// Parses a length text, like `1:02:03`, into seconds.
+pub(crate) fn parse_length(text: &str) -> Option<u64> {
+    text
+        .trim()
+        .split(':')
+        .try_fold(0u64, |seconds, part| seconds.checked_mul(60)?.checked_add(part.parse().ok()?))
+}
+
+/// Extracts the first page of related videos from the watch page's `ytInitialData`.
+pub(crate) fn parse_related_videos(initial_data: &Value) -> (Vec<RelatedVideo>, Option<String>) {
+    let results = initial_data["contents"]["twoColumnWatchNextResults"]["secondaryResults"][
+        "secondaryResults"]["results"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|result| match result["itemSectionRenderer"]["contents"].as_array() {
+            // with filter chips, the results are wrapped in an additional section
+            Some(contents) => contents.iter().collect(),
+            None => vec![result],
+        });
+    related_videos_from_items(results)
+}
+
+/// Parses a response of `youtubei/v1/next` for a related videos continuation.
+pub(crate) fn parse_related_videos_continuation(response: &Value) -> crate::Result<(Vec<RelatedVideo>, Option<String>)> {
+    let items = response["onResponseReceivedEndpoints"]
+        .as_array()
+        .ok_or_else(|| crate::Error::layout_change("the related videos response", "onResponseReceivedEndpoints"))?
+        .iter()
+        .filter_map(|endpoint| endpoint["appendContinuationItemsAction"]["continuationItems"].as_array())
+        .flatten();
+    Ok(related_videos_from_items(items))
+}
+
+fn related_videos_from_items<'a>(items: impl Iterator<Item=&'a Value>) -> (Vec<RelatedVideo>, Option<String>) {
     let mut videos = Vec::new();
-    let root_data = init_obj["contents"]["twoColumnSearchResultsRenderer"]["primaryContents"]["sectionListRenderer"]["contents"][0].clone();
-    let mut videos_raw = root_data["itemSectionRenderer"]["contents"][1]["shelfRenderer"]["content"]["verticalListRenderer"]["items"].clone();
-    if videos_raw.is_null() {
-        videos_raw = root_data["itemSectionRenderer"]["contents"].clone();
-    }
-    let video_raw_vec = videos_raw.as_array().unwrap();
-    for video_raw in video_raw_vec {
-        if video_raw["videoRenderer"].is_null() {
+    let mut continuation = None;
+
+    for item in items {
+        if let Some(token) = continuation_token(item) {
+            continuation = Some(token);
             continue;
         }
-        let pvideo = serde_json::from_value::<ChannelVideo>(video_raw["videoRenderer"].clone());
-        if pvideo.is_err() {
+
+        // playlists, mixes, ads, ... are skipped
+        videos.extend(match item.get("lockupViewModel") {
+            Some(lockup) => parse_related_lockup(lockup),
+            None => parse_compact_video(&item["compactVideoRenderer"]),
+        });
+    }
+
+    (videos, continuation)
+}
+
+fn parse_compact_video(renderer: &Value) -> Option<RelatedVideo> {
+    let video_id = IdBuf::from_string(renderer["videoId"].as_str()?.to_owned()).ok()?;
+    let byline = &renderer["longBylineText"]["runs"][0];
+    let is_live = renderer["badges"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .any(|badge| badge["metadataBadgeRenderer"]["style"].as_str() == Some("BADGE_STYLE_TYPE_LIVE_NOW"));
+
+    Some(RelatedVideo {
+        video_id,
+        title: text(&renderer["title"]).unwrap_or_default(),
+        length_seconds: text(&renderer["lengthText"]).as_deref().and_then(parse_length),
+        thumbnails: Thumbnail::deserialize_vec(&renderer["thumbnail"]).unwrap_or_default(),
+        author: byline["text"].as_str().unwrap_or_default().to_owned(),
+        channel_id: browse_channel_id(byline),
+        view_count: text(&renderer["viewCountText"]).as_deref().and_then(parse_count),
+        published_time_text: text(&renderer["publishedTimeText"]),
+        is_live,
+    })
+}
+
+/// Parses a video `lockupViewModel`, which replaced the `compactVideoRenderer`. Its metadata
+/// consists of rows of plain texts: the channel first, followed by the views and the upload date.
+fn parse_related_lockup(lockup: &Value) -> Option<RelatedVideo> {
+    if lockup["contentType"].as_str() != Some("LOCKUP_CONTENT_TYPE_VIDEO") {
+        return None;
+    }
+    let video_id = IdBuf::from_string(lockup["contentId"].as_str()?.to_owned()).ok()?;
+    let thumbnail = &lockup["contentImage"]["thumbnailViewModel"];
+    let badge = find_key(&thumbnail["overlays"], "thumbnailBadgeViewModel");
+    let is_live = badge.is_some_and(|badge| badge["badgeStyle"] == "THUMBNAIL_OVERLAY_BADGE_STYLE_LIVE");
+    let metadata = &lockup["metadata"]["lockupMetadataViewModel"];
+    let parts = metadata["metadata"]["contentMetadataViewModel"]["metadataRows"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|row| row["metadataParts"].as_array().into_iter().flatten())
+        .map(|part| &part["text"])
+        .collect::<Vec<_>>();
+    let channel = parts.first();
+
+    Some(RelatedVideo {
+        video_id,
+        title: metadata["title"]["content"].as_str().unwrap_or_default().to_owned(),
+        length_seconds: badge
+            .filter(|_| !is_live)
+            .and_then(|badge| badge["text"].as_str())
+            .and_then(parse_length),
+        thumbnails: thumbnail["image"]["sources"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|source| serde_json::from_value(source.clone()).ok())
+            .collect(),
+        author: channel
+            .and_then(|channel| channel["content"].as_str())
+            .unwrap_or_default()
+            .to_owned(),
+        channel_id: channel
+            .and_then(|channel| channel["commandRuns"][0]["onTap"]["innertubeCommand"]["browseEndpoint"]["browseId"].as_str())
+            .and_then(|id| ChannelIdBuf::from_string(id.to_owned()).ok()),
+        view_count: parts.get(1).and_then(|views| views["content"].as_str()).and_then(parse_count),
+        published_time_text: parts.get(2).and_then(|published| published["content"].as_str()).map(str::to_owned),
+        is_live,
+    })
+}
+
+/// Finds the renderer with the given name in the primary column of the watch page's `ytInitialData`.
+pub(crate) fn watch_renderer<'a>(initial_data: &'a Value, name: &str) -> Option<&'a Value> {
+    initial_data["contents"]["twoColumnWatchNextResults"]["results"]["results"]["contents"]
+        .as_array()?
+        .iter()
+        .map(|content| &content[name])
+        .find(|renderer| !renderer.is_null())
+}
+
+/// Extracts the like count from the `videoPrimaryInfoRenderer`.
+/// Returns `None` if the likes are hidden.
+pub(crate) fn parse_like_count(primary_info: &Value) -> Option<u64> {
+    static NUMBER: Lazy<Regex> = Lazy::new(||
+        Regex::new(r"\d[\d,.]*[KMB]?").unwrap()
+    );
+
+    let buttons = primary_info["videoActions"]["menuRenderer"]["topLevelButtons"].as_array()?;
+    buttons
+        .iter()
+        .find_map(|button| {
+            let view_model = &button["segmentedLikeDislikeButtonViewModel"]["likeButtonViewModel"][
+                "likeButtonViewModel"]["toggleButtonViewModel"]["toggleButtonViewModel"][
+                "defaultButtonViewModel"]["buttonViewModel"];
+            let renderer = match button["segmentedLikeDislikeButtonRenderer"]["likeButton"]["toggleButtonRenderer"] {
+                Value::Null => &button["toggleButtonRenderer"],
+                ref renderer => renderer,
+            };
+            let renderer = Some(renderer)
+                .filter(|renderer| renderer["defaultIcon"]["iconType"].as_str() == Some("LIKE"));
+
+            // the accessibility labels contain the exact count, i.e. `1,234 likes`
+            view_model["accessibilityText"]
+                .as_str()
+                .or_else(|| renderer?["defaultText"]["accessibility"]["accessibilityData"]["label"].as_str())
+                .or_else(|| view_model["title"].as_str())
+        })
+        .and_then(|label| NUMBER.find(label))
+        .and_then(|number| parse_count(number.as_str()))
+}
+
+/// Extracts the full description, including links, from the `videoSecondaryInfoRenderer`.
+pub(crate) fn parse_full_description(secondary_info: &Value) -> Option<Vec<TextRun>> {
+    match attributed_text_runs(&secondary_info["attributedDescription"]) {
+        Some(runs) => Some(runs),
+        None => secondary_info
+            .get("description")
+            .map(text_runs)
+            .filter(|runs| !runs.is_empty()),
+    }
+}
+
+/// Splits an attributed text (i.e. of a view model) into runs, where its links start and end.
+pub(crate) fn attributed_text_runs(attributed: &Value) -> Option<Vec<TextRun>> {
+    let content = attributed["content"].as_str()?;
+
+    // the command runs index into the content by UTF-16 code units
+    let content = content.encode_utf16().collect::<Vec<_>>();
+    let mut runs = Vec::new();
+    let mut position = 0;
+    let mut links = attributed["commandRuns"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|run| {
+            let start = usize::try_from(run["startIndex"].as_u64()?).ok()?;
+            let end = start.checked_add(usize::try_from(run["length"].as_u64()?).ok()?)?;
+            let endpoint = &run["onTap"]["innertubeCommand"];
+            let url = endpoint["urlEndpoint"]["url"]
+                .as_str()
+                .or_else(|| endpoint["commandMetadata"]["webCommandMetadata"]["url"].as_str())?;
+            Some((start, end.min(content.len()), url))
+        })
+        .filter(|&(start, end, _)| start < end)
+        .collect::<Vec<_>>();
+    links.sort_by_key(|&(start, ..)| start);
+
+    let push = |runs: &mut Vec<TextRun>, range: std::ops::Range<usize>, url: Option<&str>| {
+        if !range.is_empty() {
+            runs.push(TextRun {
+                text: String::from_utf16_lossy(&content[range]),
+                url: url.map(str::to_owned),
+            });
+        }
+    };
+    for (start, end, url) in links {
+        if start < position {
+            // overlapping runs
             continue;
         }
-        let mut video = pvideo.unwrap();
-        if video.author.is_empty() {
-            video.add_author(video_raw["videoRenderer"]["ownerText"]["runs"][0]["text"].as_str().unwrap().to_string());
+        push(&mut runs, position..start, None);
+        push(&mut runs, start..end, Some(url));
+        position = end;
+    }
+    push(&mut runs, position..content.len(), None);
+
+    Some(runs)
+}
+
+/// Extracts the value of a row (i.e. `Category` or `License`) below the description.
+pub(crate) fn parse_metadata_row(secondary_info: &Value, title: &str) -> Option<String> {
+    secondary_info["metadataRowContainer"]["metadataRowContainerRenderer"]["rows"]
+        .as_array()?
+        .iter()
+        .map(|row| &row["metadataRowRenderer"])
+        .find(|row| self::text(&row["title"]).as_deref() == Some(title))
+        .and_then(|row| self::text(&row["contents"][0]))
+}
+
+/// Extracts all hashtags (including the `#`) from a text, in order of appearance.
+pub(crate) fn parse_hashtags(text: &str) -> Vec<String> {
+    static HASHTAG: Lazy<Regex> = Lazy::new(||
+        Regex::new(r"(?:^|\s)(#[\p{L}\p{N}_]+)").unwrap()
+    );
+
+    HASHTAG
+        .captures_iter(text)
+        .map(|captures| captures[1].to_owned())
+        .collect()
+}
+
+/// Reads a number, which YouTube sends either as a JSON number or as a string.
+fn lenient_u64(value: &Value) -> Option<u64> {
+    value
+        .as_u64()
+        .or_else(|| value.as_str()?.parse().ok())
+}
+
+/// Reads a number, which YouTube sends either as a JSON number or as a string.
+fn lenient_f64(value: &Value) -> Option<f64> {
+    value
+        .as_f64()
+        .or_else(|| value.as_str()?.parse().ok())
+}
+
+/// Extracts the "most replayed" markers from the watch page's `ytInitialData`.
+///
+/// Newer pages send them as a `macroMarkersListEntity` mutation, older ones as the `HEATSEEKER`
+/// entry of the player bar's markers map. Markers with missing fields are skipped.
+pub(crate) fn parse_heatmap(initial_data: &Value) -> Vec<HeatMarker> {
+    let entities = initial_data["frameworkUpdates"]["entityBatchUpdate"]["mutations"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|mutation| &mutation["payload"]["macroMarkersListEntity"]["markersList"])
+        .filter(|list| list["markerType"].as_str() == Some("MARKER_TYPE_HEATMAP"))
+        .filter_map(|list| list["markers"].as_array())
+        .flatten()
+        .filter_map(|marker| Some(HeatMarker {
+            start_ms: lenient_u64(&marker["startMillis"])?,
+            duration_ms: lenient_u64(&marker["durationMillis"])?,
+            intensity: lenient_f64(&marker["intensityScoreNormalized"])?,
+        }))
+        .collect::<Vec<_>>();
+    if !entities.is_empty() {
+        return entities;
+    }
+
+    initial_data["playerOverlays"]["playerOverlayRenderer"]["decoratedPlayerBarRenderer"][
+        "decoratedPlayerBarRenderer"]["playerBar"]["multiMarkersPlayerBarRenderer"]["markersMap"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|marker| marker["key"].as_str() == Some("HEATSEEKER"))
+        .filter_map(|marker| marker["value"]["heatmap"]["heatmapRenderer"]["heatMarkers"].as_array())
+        .flatten()
+        .map(|marker| &marker["heatMarkerRenderer"])
+        .filter_map(|marker| Some(HeatMarker {
+            start_ms: lenient_u64(&marker["timeRangeStartMillis"])?,
+            duration_ms: lenient_u64(&marker["markerDurationMillis"])?,
+            intensity: lenient_f64(&marker["heatMarkerIntensityScoreNormalized"])?,
+        }))
+        .collect()
+}
+
+/// The playlist panel next to the player of a watch page, which is used for mixes and albums.
+pub(crate) struct PlaylistPanel {
+    pub(crate) title: String,
+    /// Mixes never end.
+    pub(crate) is_infinite: bool,
+    pub(crate) total_videos: Option<u64>,
+    pub(crate) videos: Vec<PlaylistVideo>,
+}
+
+/// Extracts the playlist panel from either the `ytInitialData` of a watch page, or a
+/// `youtubei/v1/next` response.
+pub(crate) fn parse_playlist_panel(data: &Value) -> crate::Result<PlaylistPanel> {
+    let panel = &data["contents"]["twoColumnWatchNextResults"]["playlist"]["playlist"];
+    let items = panel["contents"]
+        .as_array()
+        .ok_or_else(|| crate::Error::layout_change("the watch page", "contents.twoColumnWatchNextResults.playlist.playlist.contents"))?;
+
+    let videos = items
+        .iter()
+        .map(|item| &item["playlistPanelVideoRenderer"])
+        .filter_map(|renderer| {
+            let video_id = IdBuf::from_string(renderer["videoId"].as_str()?.to_owned()).ok()?;
+            let byline = &renderer["shortBylineText"]["runs"][0];
+            let is_playable = renderer["unplayableText"].is_null();
+            Some(PlaylistVideo {
+                index: renderer["navigationEndpoint"]["watchEndpoint"]["index"]
+                    .as_u64()
+                    .map(|index| index + 1)
+                    .or_else(|| text(&renderer["indexText"])?.parse().ok())
+                    .unwrap_or_default(),
+                video_id,
+                title: text(&renderer["title"]).unwrap_or_default(),
+                length_seconds: text(&renderer["lengthText"]).as_deref().and_then(parse_length),
+                thumbnails: Thumbnail::deserialize_vec(&renderer["thumbnail"]).unwrap_or_default(),
+                author: byline["text"].as_str().unwrap_or_default().to_owned(),
+                owner_channel_id: browse_channel_id(byline),
+                is_playable,
+                availability: match is_playable {
+                    true => Availability::Available,
+                    false => Availability::Unavailable,
+                },
+                set_video_id: None,
+                view_count_text: None,
+                published_time_text: None,
+            })
+        })
+        .collect();
+
+    Ok(PlaylistPanel {
+        title: text(&panel["title"])
+            .or_else(|| panel["title"].as_str().map(str::to_owned))
+            .unwrap_or_default(),
+        is_infinite: panel["isInfinite"].as_bool().unwrap_or(false),
+        total_videos: panel["totalVideos"].as_u64(),
+        videos,
+    })
+}
+
+/// Finds the first value with the given key in a depth-first search.
+pub(crate) fn find_key<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(object) => object
+            .get(key)
+            .or_else(|| object.values().find_map(|value| find_key(value, key))),
+        Value::Array(array) => array.iter().find_map(|value| find_key(value, key)),
+        _ => None,
+    }
+}
+
+/// Removes YouTube's `/redirect?q=<url>` wrapper of external links.
+pub(crate) fn unredirect(url: &str) -> String {
+    Url::parse(url)
+        .ok()
+        .filter(|parsed| parsed.path() == "/redirect")
+        .and_then(|parsed| parsed
+            .query_pairs()
+            .find(|(key, _)| key == "q")
+            .map(|(_, url)| url.into_owned()))
+        .unwrap_or_else(|| url.to_owned())
+}
+
+/// Collects the items of the selected tab of a channel page's `ytInitialData`, or of a
+/// `youtubei/v1/browse` continuation response.
+///
+/// Wrappers like `richItemRenderer` and `gridRenderer` are removed, so each item is an object
+/// with the actual renderer (i.e. `videoRenderer`) as its only key.
+pub(crate) fn channel_tab_items(data: &Value) -> Option<Vec<&Value>> {
+    let contents = match data["onResponseReceivedActions"].as_array() {
+        Some(actions) => actions
+            .iter()
+            .filter_map(|action| action["appendContinuationItemsAction"]["continuationItems"]
+                .as_array()
+                .or_else(|| action["reloadContinuationItemsCommand"]["continuationItems"].as_array()))
+            .flatten()
+            .collect::<Vec<_>>(),
+        None => {
+            let content = &data["contents"]["twoColumnBrowseResultsRenderer"]["tabs"]
+                .as_array()?
+                .iter()
+                .map(|tab| &tab["tabRenderer"])
+                .find(|tab| tab["selected"].as_bool() == Some(true))?["content"];
+            match content["richGridRenderer"]["contents"].as_array() {
+                Some(contents) => contents.iter().collect(),
+                None => content["sectionListRenderer"]["contents"]
+                    .as_array()?
+                    .iter()
+                    .flat_map(|section| match section["itemSectionRenderer"]["contents"].as_array() {
+                        Some(contents) => contents.iter().collect(),
+                        None => vec![section],
+                    })
+                    .collect(),
+            }
+        }
+    };
+
+    Some(contents
+        .into_iter()
+        .flat_map(|item| {
+            if let Some(items) = item["gridRenderer"]["items"].as_array() {
+                return items.iter().collect();
+            }
+            match item["richItemRenderer"]["content"] {
+                Value::Null => vec![item],
+                ref content => vec![content],
+            }
+        })
+        .collect())
+}
+
+/// Parses a page of a channel tab with `parse_item`, skipping all items of other types.
+pub(crate) fn parse_channel_tab_page<T, F>(data: &Value, parse_item: &F) -> crate::Result<(Vec<T>, Option<String>)>
+    where
+        F: Fn(&Value) -> crate::Result<Option<T>> {
+    let items = channel_tab_items(data)
+        .ok_or_else(|| crate::Error::layout_change("the channel page", "contents.twoColumnBrowseResultsRenderer.tabs[selected].tabRenderer.content"))?;
+
+    let mut results = Vec::with_capacity(items.len());
+    let mut continuation = None;
+    for item in items {
+        match continuation_token(item) {
+            Some(token) => continuation = Some(token),
+            None => results.extend(parse_item(item)?),
         }
-        videos.push(video);
     }
-    Ok(videos)
-}
\ No newline at end of file
+    Ok((results, continuation))
+}
+
+/// Requests a channel tab page, and then lazily walks its continuations.
+///
+/// If a `chip` is provided, the tab is reloaded with the filter chip with that text (or at that
+/// position, if no chip has the text) first.
+pub(crate) fn channel_tab_stream<T, F>(
+    client: Client,
+    url: String,
+    chip: Option<(&'static str, usize)>,
+    parse_item: F,
+) -> impl Stream<Item=crate::Result<T>>
+    where
+        F: Fn(&Value) -> crate::Result<Option<T>> {
+    futures::stream::once(async move {
+        let body = client.get(url).send().await?.error_for_status()?.text().await?;
+        let mut data: Value = serde_json::from_str(
+            &initial_data(&body)
+                .ok_or_else(|| crate::Error::layout_change("the channel page", "ytInitialData"))?
+        )?;
+        if let Some((text, position)) = chip {
+            let token = chip_continuation(&data, text, position)
+                .ok_or_else(|| crate::Error::layout_change("the channel tab", format!("feedFilterChipBarRenderer.contents[text = {text}]")))?;
+            data = get_continuation(&client, "browse", &token).await?;
+        }
+        let (items, continuation) = parse_channel_tab_page(&data, &parse_item)?;
+
+        let following = continuation_stream(
+            "browse",
+            Some(client),
+            continuation,
+            move |page| parse_channel_tab_page(page, &parse_item),
+        );
+        Ok::<_, crate::Error>(futures::stream::iter(items.into_iter().map(crate::Result::Ok)).chain(following))
+    })
+        .try_flatten()
+}
+
+/// Finds the continuation token of a filter chip (i.e. `Popular`) of the selected channel tab.
+pub(crate) fn chip_continuation(data: &Value, text: &str, position: usize) -> Option<String> {
+    let chips = find_key(data, "feedFilterChipBarRenderer")
+        .and_then(|bar| bar["contents"].as_array())
+        .map(|chips| chips
+            .iter()
+            .map(|chip| &chip["chipCloudChipRenderer"])
+            .map(|chip| (
+                self::text(&chip["text"]),
+                chip["navigationEndpoint"]["continuationCommand"]["token"].as_str(),
+            ))
+            .collect::<Vec<_>>())
+        .or_else(|| find_key(data, "chipBarViewModel")
+            .and_then(|bar| bar["chips"].as_array())
+            .map(|chips| chips
+                .iter()
+                .map(|chip| &chip["chipViewModel"])
+                .map(|chip| (
+                    chip["text"].as_str().map(str::to_owned),
+                    chip["tapCommand"]["innertubeCommand"]["continuationCommand"]["token"].as_str(),
+                ))
+                .collect()))?;
+
+    chips
+        .iter()
+        .find(|(chip_text, _)| chip_text.as_deref() == Some(text))
+        .or_else(|| chips.get(position))
+        .and_then(|(_, token)| token.map(str::to_owned))
+}
+
+/// Estimates the point in time of a relative date text, like `3 weeks ago` or `Streamed 2 days ago`.
+/// Absolute dates, like `Apr 5, 2021`, are interpreted as midnight UTC.
+pub(crate) fn parse_relative_time(text: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    static RELATIVE: Lazy<Regex> = Lazy::new(||
+        Regex::new(r"(\d+)\s+(second|minute|hour|day|week|month|year)s?\s+ago").unwrap()
+    );
+
+    let captures = match RELATIVE.captures(text) {
+        Some(captures) => captures,
+        None => return parse_updated_date(text)?
+            .and_hms_opt(0, 0, 0)
+            .map(|date| date.and_utc()),
+    };
+    let amount = i32::try_from(captures[1].parse::<i64>().ok()?).ok()?;
+    let unit = match &captures[2] {
+        "second" => chrono::Duration::seconds(1),
+        "minute" => chrono::Duration::minutes(1),
+        "hour" => chrono::Duration::hours(1),
+        "day" => chrono::Duration::days(1),
+        "week" => chrono::Duration::weeks(1),
+        "month" => chrono::Duration::days(30),
+        _ => chrono::Duration::days(365),
+    };
+    now.checked_sub_signed(unit.checked_mul(amount)?)
+}
+
+fn video_id(value: &Value) -> Option<IdBuf> {
+    IdBuf::from_string(value.as_str()?.to_owned()).ok()
+}
+
+/// Parses a `reelItemRenderer`, or a `shortsLockupViewModel` of the Shorts tab.
+pub(crate) fn parse_channel_short(item: &Value) -> Option<ChannelShort> {
+    let reel = &item["reelItemRenderer"];
+    if !reel.is_null() {
+        return Some(ChannelShort {
+            video_id: video_id(&reel["videoId"])?,
+            title: text(&reel["headline"]).unwrap_or_default(),
+            view_count_text: text(&reel["viewCountText"]),
+            thumbnails: Thumbnail::deserialize_vec(&reel["thumbnail"]).unwrap_or_default(),
+        });
+    }
+
+    let lockup = &item["shortsLockupViewModel"];
+    let overlay = &lockup["overlayMetadata"];
+    Some(ChannelShort {
+        video_id: video_id(&lockup["onTap"]["innertubeCommand"]["reelWatchEndpoint"]["videoId"])?,
+        title: overlay["primaryText"]["content"].as_str().unwrap_or_default().to_owned(),
+        view_count_text: overlay["secondaryText"]["content"].as_str().map(str::to_owned),
+        thumbnails: lockup["thumbnail"]["sources"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|source| serde_json::from_value(source.clone()).ok())
+            .collect(),
+    })
+}
+
+/// Parses a `videoRenderer` of the Live tab.
+pub(crate) fn parse_channel_stream(item: &Value) -> Option<ChannelStream> {
+    let renderer = &item["videoRenderer"];
+    let is_live = renderer["badges"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .chain(renderer["thumbnailOverlays"].as_array().into_iter().flatten())
+        .any(|badge| badge["metadataBadgeRenderer"]["style"].as_str() == Some("BADGE_STYLE_TYPE_LIVE_NOW")
+            || badge["thumbnailOverlayTimeStatusRenderer"]["style"].as_str() == Some("LIVE"));
+    let upcoming = &renderer["upcomingEventData"];
+
+    let status = if !upcoming.is_null() {
+        StreamStatus::Upcoming {
+            start_time: lenient_u64(&upcoming["startTime"])
+                .and_then(|start| chrono::DateTime::from_timestamp(start as i64, 0)),
+        }
+    } else if is_live {
+        StreamStatus::Live
+    } else {
+        StreamStatus::Past
+    };
+
+    Some(ChannelStream {
+        video_id: video_id(&renderer["videoId"])?,
+        title: text(&renderer["title"]).unwrap_or_default(),
+        status,
+        length_seconds: text(&renderer["lengthText"]).as_deref().and_then(parse_length),
+        thumbnails: Thumbnail::deserialize_vec(&renderer["thumbnail"]).unwrap_or_default(),
+        view_count_text: text(&renderer["viewCountText"]),
+        published_time_text: text(&renderer["publishedTimeText"]),
+    })
+}
+
+/// Parses a `gridPlaylistRenderer`, or a playlist `lockupViewModel` of the Playlists tab.
+pub(crate) fn parse_channel_playlist(item: &Value) -> Option<ChannelPlaylist> {
+    let grid = match item["gridPlaylistRenderer"] {
+        Value::Null => &item["playlistRenderer"],
+        ref grid => grid,
+    };
+    if !grid.is_null() {
+        return Some(ChannelPlaylist {
+            playlist_id: PlaylistIdBuf::from_string(grid["playlistId"].as_str()?.to_owned()).ok()?,
+            title: text(&grid["title"]).unwrap_or_default(),
+            video_count: text(&grid["videoCountText"])
+                .or_else(|| text(&grid["videoCountShortText"]))
+                .or_else(|| grid["videoCount"].as_str().map(str::to_owned))
+                .as_deref()
+                .and_then(parse_count),
+            thumbnails: Thumbnail::deserialize_vec(&grid["thumbnail"])
+                .or_else(|_| Thumbnail::deserialize_vec(&grid["thumbnails"][0]))
+                .unwrap_or_default(),
+        });
+    }
+
+    let lockup = &item["lockupViewModel"];
+    if lockup["contentType"].as_str() != Some("LOCKUP_CONTENT_TYPE_PLAYLIST") {
+        return None;
+    }
+    let thumbnail = &lockup["contentImage"]["collectionThumbnailViewModel"]["primaryThumbnail"][
+        "thumbnailViewModel"];
+    Some(ChannelPlaylist {
+        playlist_id: PlaylistIdBuf::from_string(lockup["contentId"].as_str()?.to_owned()).ok()?,
+        title: lockup["metadata"]["lockupMetadataViewModel"]["title"]["content"]
+            .as_str()
+            .unwrap_or_default()
+            .to_owned(),
+        video_count: find_key(&thumbnail["overlays"], "thumbnailBadgeViewModel")
+            .and_then(|badge| badge["text"].as_str())
+            .and_then(parse_count),
+        thumbnails: thumbnail["image"]["sources"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|source| serde_json::from_value(source.clone()).ok())
+            .collect(),
+    })
+}
+
+/// Parses a `backstagePostThreadRenderer` of the Community tab.
+pub(crate) fn parse_community_post(item: &Value) -> Option<CommunityPost> {
+    let post = match item["backstagePostThreadRenderer"]["post"]["backstagePostRenderer"] {
+        Value::Null => &item["backstagePostThreadRenderer"]["post"]["sharedPostRenderer"],
+        ref post => post,
+    };
+    let attachment = &post["backstageAttachment"];
+
+    let images = attachment["postMultiImageRenderer"]["images"]
+        .as_array()
+        .map(|images| images.iter().collect::<Vec<_>>())
+        .unwrap_or_else(|| vec![attachment])
+        .into_iter()
+        .filter_map(|image| Thumbnail::deserialize_vec(&image["backstageImageRenderer"]["image"]).ok())
+        .collect();
+    let poll = &attachment["pollRenderer"];
+
+    Some(CommunityPost {
+        id: post["postId"].as_str()?.to_owned(),
+        text: text_runs(&post["contentText"]),
+        images,
+        poll: poll["choices"].as_array().map(|choices| Poll {
+            choices: choices
+                .iter()
+                .filter_map(|choice| text(&choice["text"]))
+                .collect(),
+            total_votes_text: text(&poll["totalVotes"]),
+        }),
+        video_id: video_id(&attachment["videoRenderer"]["videoId"]),
+        like_count: text(&post["voteCount"]).as_deref().and_then(parse_count),
+        published_time_text: text(&post["publishedTimeText"]),
+    })
+}
+
+/// Extracts the information of the About tab from either an `aboutChannelViewModel`, or the
+/// `channelAboutFullMetadataRenderer` of older layouts.
+pub(crate) fn parse_channel_about(data: &Value) -> Option<ChannelAbout> {
+    if let Some(about) = find_key(data, "aboutChannelViewModel") {
+        let content = |key: &str| about[key]["content"].as_str().or_else(|| about[key].as_str());
+        return Some(ChannelAbout {
+            description: about["description"].as_str().unwrap_or_default().to_owned(),
+            subscriber_count: about["subscriberCountText"].as_str().and_then(parse_count),
+            view_count: about["viewCountText"].as_str().and_then(parse_count),
+            video_count: about["videoCountText"].as_str().and_then(parse_count),
+            joined_date: content("joinedDateText").and_then(parse_updated_date),
+            country: about["country"].as_str().map(str::to_owned),
+            links: about["links"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|link| &link["channelExternalLinkViewModel"])
+                .filter_map(|link| {
+                    let url = link["link"]["commandRuns"][0]["onTap"]["innertubeCommand"]["urlEndpoint"]["url"]
+                        .as_str()
+                        .map(unredirect)
+                        .or_else(|| link["link"]["content"].as_str().map(|url| format!("https://{url}")))?;
+                    Some(ChannelLink {
+                        title: link["title"]["content"].as_str().unwrap_or_default().to_owned(),
+                        url,
+                    })
+                })
+                .collect(),
+        });
+    }
+
+    let about = find_key(data, "channelAboutFullMetadataRenderer")?;
+    Some(ChannelAbout {
+        description: text(&about["description"]).unwrap_or_default(),
+        subscriber_count: None,
+        view_count: text(&about["viewCountText"]).as_deref().and_then(parse_count),
+        video_count: None,
+        joined_date: text(&about["joinedDateText"]).as_deref().and_then(parse_updated_date),
+        country: text(&about["country"]),
+        links: about["primaryLinks"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|link| Some(ChannelLink {
+                title: text(&link["title"]).unwrap_or_default(),
+                url: unredirect(link["navigationEndpoint"]["urlEndpoint"]["url"].as_str()?),
+            }))
+            .collect(),
+    })
+}
+
+/// The continuation token of the About panel, which newer channel pages load lazily.
+pub(crate) fn channel_about_continuation(data: &Value) -> Option<String> {
+    data["onResponseReceivedEndpoints"]
+        .as_array()?
+        .iter()
+        .find_map(|endpoint| find_key(&endpoint["showEngagementPanelEndpoint"], "continuationItemRenderer"))
+        .and_then(|renderer| continuation_token(&serde_json::json!({ "continuationItemRenderer": renderer })))
+}
@@ -347,3 +347,353 @@ impl<T> core::cmp::PartialOrd<T> for Id<'_>
         )
     }
 }
+
+/// Alias for an owned [`ChannelId`].
+pub type ChannelIdBuf = ChannelId<'static>;
+
+/// A pattern matching the canonical id of a channel (`^UC[a-zA-Z0-9_-]{22}$`).
+#[cfg(feature = "regex")]
+pub static CHANNEL_ID_PATTERN: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(||
+    Regex::new("^UC[a-zA-Z0-9_-]{22}$").unwrap()
+);
+
+/// A wrapper around a Cow<'a, str> that makes sure the canonical channel id, which is contained,
+/// always has the correct format (`^UC[a-zA-Z0-9_-]{22}$`, defined in [CHANNEL_ID_PATTERN]).
+///
+/// Handles (`@name`), custom urls (`/c/name`) and legacy usernames (`/user/name`) are no channel
+/// ids. They can be resolved to one with [`ChannelResolver`](crate::channel::ChannelResolver).
+///
+/// The ownership model is the same as the one of [`Id`].
+#[derive(Clone, Debug, Serialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ChannelId<'a>(Cow<'a, str>);
+
+#[allow(clippy::should_implement_trait)]
+impl<'a> ChannelId<'a> {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "regex")] {
+            #[inline]
+            pub fn from_str(id: &'a str) -> Result<Self> {
+                match CHANNEL_ID_PATTERN.is_match(id) {
+                    true => Ok(Self(Cow::Borrowed(id))),
+                    false => Err(Error::BadIdFormat)
+                }
+            }
+        } else {
+            #[inline]
+            pub fn from_str(id: &'a str) -> Option<Self> {
+                match Self::check_str(id) {
+                    Ok(_) => Some(Self(Cow::Borrowed(id))),
+                    Err(_) => None
+                }
+            }
+
+            #[inline]
+            fn check_str(id: &'_ str) -> Result<(), ()> {
+                let only_allowed_chars = id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+                if id.len() == 24 && id.starts_with("UC") && only_allowed_chars {
+                    Ok(())
+                } else {
+                    Err(())
+                }
+            }
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn into_owned(self) -> ChannelIdBuf {
+        ChannelId(Cow::Owned(self.0.into_owned()))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_owned(&self) -> ChannelIdBuf {
+        self
+            .clone()
+            .into_owned()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_borrowed(&'a self) -> Self {
+        Self(Cow::Borrowed(&self.0))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self.0.as_ref()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn channel_url(&self) -> Url {
+        let mut url = Url::parse("https://www.youtube.com/channel")
+            .unwrap();
+        url
+            .path_segments_mut()
+            .unwrap()
+            .push(self.as_str());
+        url
+    }
+}
+
+impl ChannelIdBuf {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "regex")] {
+            #[inline]
+            pub fn from_string(id: String) -> Result<Self, String> {
+                match CHANNEL_ID_PATTERN.is_match(id.as_str()) {
+                    true => Ok(Self(Cow::Owned(id))),
+                    false => Err(id)
+                }
+            }
+        } else {
+            #[inline]
+            pub fn from_string(id: String) -> Result<Self, String> {
+                match Self::check_str(&id) {
+                    Ok(_) => Ok(Self(Cow::Owned(id))),
+                    Err(_) => Err(id)
+                }
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ChannelId<'static> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+        where
+            D: Deserializer<'de> {
+        let raw = String::deserialize(deserializer)?;
+        Self::from_string(raw)
+            .map_err(|s| D::Error::invalid_value(
+                Unexpected::Str(&s),
+                &"expected a valid youtube channel identifier",
+            ))
+    }
+}
+
+impl core::fmt::Display for ChannelId<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl core::ops::Deref for ChannelId<'_> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl core::convert::AsRef<str> for ChannelId<'_> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// Alias for an owned [`PlaylistId`].
+pub type PlaylistIdBuf = PlaylistId<'static>;
+
+/// A pattern matching the id of a playlist, including generated ones like mixes or albums.
+#[cfg(feature = "regex")]
+pub static PLAYLIST_ID_PATTERN: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(||
+    Regex::new("^(?:(?:PL|UU|FL|RD|OLAK5uy_)[a-zA-Z0-9_-]{2,}|LL[a-zA-Z0-9_-]*)$").unwrap()
+);
+
+/// The kind of a playlist, which is encoded in the prefix of its id.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PlaylistKind {
+    /// A playlist created by a user (`PL...`).
+    Playlist,
+    /// The uploads of a channel (`UU...`).
+    Uploads,
+    /// The favorites of a channel (`FL...`).
+    Favorites,
+    /// A mix, which is generated by YouTube (`RD...`).
+    Mix,
+    /// An album, which is generated by YouTube Music (`OLAK5uy_...`).
+    Album,
+    /// The liked videos (`LL...`).
+    Liked,
+}
+
+/// A wrapper around a Cow<'a, str> that makes sure the playlist id, which is contained, always
+/// has a known format (defined in [PLAYLIST_ID_PATTERN]).
+///
+/// The ownership model is the same as the one of [`Id`].
+#[derive(Clone, Debug, Serialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PlaylistId<'a>(Cow<'a, str>);
+
+#[allow(clippy::should_implement_trait)]
+impl<'a> PlaylistId<'a> {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "regex")] {
+            #[inline]
+            pub fn from_str(id: &'a str) -> Result<Self> {
+                match PLAYLIST_ID_PATTERN.is_match(id) {
+                    true => Ok(Self(Cow::Borrowed(id))),
+                    false => Err(Error::BadIdFormat)
+                }
+            }
+        } else {
+            #[inline]
+            pub fn from_str(id: &'a str) -> Option<Self> {
+                match Self::check_str(id) {
+                    Ok(_) => Some(Self(Cow::Borrowed(id))),
+                    Err(_) => None
+                }
+            }
+
+            #[inline]
+            fn check_str(id: &'_ str) -> Result<(), ()> {
+                let only_allowed_chars = id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+                let known_prefix = ["PL", "UU", "FL", "RD", "OLAK5uy_"]
+                    .iter()
+                    .any(|prefix| id.len() >= prefix.len() + 2 && id.starts_with(prefix));
+
+                if only_allowed_chars && (known_prefix || id.starts_with("LL")) {
+                    Ok(())
+                } else {
+                    Err(())
+                }
+            }
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn kind(&self) -> PlaylistKind {
+        match &self.0[..2] {
+            "PL" => PlaylistKind::Playlist,
+            "UU" => PlaylistKind::Uploads,
+            "FL" => PlaylistKind::Favorites,
+            "RD" => PlaylistKind::Mix,
+            "LL" => PlaylistKind::Liked,
+            _ => PlaylistKind::Album,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn into_owned(self) -> PlaylistIdBuf {
+        PlaylistId(Cow::Owned(self.0.into_owned()))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_owned(&self) -> PlaylistIdBuf {
+        self
+            .clone()
+            .into_owned()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_borrowed(&'a self) -> Self {
+        Self(Cow::Borrowed(&self.0))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self.0.as_ref()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn playlist_url(&self) -> Url {
+        Url::parse_with_params(
+            "https://www.youtube.com/playlist",
+            &[("list", self.as_str())],
+        ).unwrap()
+    }
+
+    /// The url of a video, which is played as part of the playlist.
+    #[inline]
+    #[must_use]
+    pub fn watch_url(&self, video_id: Id<'_>) -> Url {
+        Url::parse_with_params(
+            "https://www.youtube.com/watch",
+            &[("v", video_id.as_str()), ("list", self.as_str())],
+        ).unwrap()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn embed_url(&self) -> Url {
+        Url::parse_with_params(
+            "https://www.youtube.com/embed/videoseries",
+            &[("list", self.as_str())],
+        ).unwrap()
+    }
+}
+
+impl PlaylistIdBuf {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "regex")] {
+            #[inline]
+            pub fn from_string(id: String) -> Result<Self, String> {
+                match PLAYLIST_ID_PATTERN.is_match(id.as_str()) {
+                    true => Ok(Self(Cow::Owned(id))),
+                    false => Err(id)
+                }
+            }
+        } else {
+            #[inline]
+            pub fn from_string(id: String) -> Result<Self, String> {
+                match Self::check_str(&id) {
+                    Ok(_) => Ok(Self(Cow::Owned(id))),
+                    Err(_) => Err(id)
+                }
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PlaylistId<'static> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+        where
+            D: Deserializer<'de> {
+        let raw = String::deserialize(deserializer)?;
+        Self::from_string(raw)
+            .map_err(|s| D::Error::invalid_value(
+                Unexpected::Str(&s),
+                &"expected a valid youtube playlist identifier",
+            ))
+    }
+}
+
+impl core::fmt::Display for PlaylistId<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl core::ops::Deref for PlaylistId<'_> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl core::convert::AsRef<str> for PlaylistId<'_> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
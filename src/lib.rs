@@ -207,11 +207,13 @@ pub use reqwest;
 pub use crate::descrambler::VideoDescrambler;
 #[cfg(feature = "std")]
 pub use crate::error::Error;
+#[cfg(feature = "std")]
+pub use crate::resource::Resource;
 #[cfg(feature = "fetch")]
 pub use crate::fetcher::VideoFetcher;
-pub use crate::id::{Id, IdBuf};
+pub use crate::id::{ChannelId, ChannelIdBuf, Id, IdBuf, PlaylistId, PlaylistIdBuf, PlaylistKind};
 #[cfg(feature = "regex")]
-pub use crate::id::{EMBED_URL_PATTERN, ID_PATTERN, ID_PATTERNS, SHARE_URL_PATTERN, WATCH_URL_PATTERN};
+pub use crate::id::{CHANNEL_ID_PATTERN, EMBED_URL_PATTERN, ID_PATTERN, ID_PATTERNS, PLAYLIST_ID_PATTERN, SHARE_URL_PATTERN, WATCH_URL_PATTERN};
 #[cfg(feature = "callback")]
 pub use crate::stream::callback::{Callback, CallbackArguments, OnCompleteType, OnProgressType};
 #[cfg(feature = "stream")]
@@ -242,6 +244,8 @@ pub mod blocking;
 pub mod error;
 #[doc(hidden)]
 pub mod id;
+#[cfg(feature = "std")]
+pub mod resource;
 #[doc(hidden)]
 #[cfg(feature = "stream")]
 pub mod stream;
@@ -268,15 +272,42 @@ pub mod playlist_info;
 
 pub mod channel_info;
 
+#[cfg(feature = "fetch")]
+pub mod comment_info;
+#[cfg(feature = "descramble")]
+pub mod clip;
+#[cfg(feature = "fetch")]
+pub mod diagnostics;
+#[cfg(feature = "fetch")]
+pub mod feed;
+#[cfg(feature = "fetch")]
+pub mod music;
+#[cfg(feature = "fetch")]
+pub mod proxy;
+#[cfg(feature = "fetch")]
+pub mod search;
+#[cfg(feature = "fetch")]
+pub mod search_info;
+
 pub mod channel;
 
-pub use crate::channel::Channel;
+pub use crate::channel::{Channel, ChannelResolver};
+#[cfg(feature = "descramble")]
+pub use crate::clip::Clip;
+#[cfg(feature = "fetch")]
+pub use crate::diagnostics::DiagnosticsBundle;
+#[cfg(feature = "fetch")]
+pub use crate::feed::ChannelFeed;
+#[cfg(feature = "fetch")]
+pub use crate::proxy::{ProxyEntry, ProxyPool};
 
 pub use crate::playlist::Playlist;
 
 pub use crate::playlist::crate_client;  
 
-pub use crate::helper::search_videos;
+pub use crate::search::Search;
+#[allow(deprecated)]
+pub use crate::search::search_videos;
 
 /// The absolute most straightforward way of downloading a YouTube video in high quality!
 ///
@@ -0,0 +1,689 @@
+//! Metadata of songs, albums and artists from YouTube Music (`music.youtube.com`).
+//!
+//! Every track is a regular YouTube video, so its [`Track::video_id`] can be used with
+//! [`Video`](crate::Video) and [`Stream`](crate::Stream) to download it.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use reqwest::{header, Client};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{crate_client, ChannelId, ChannelIdBuf, Error, Id, IdBuf, PlaylistId, PlaylistIdBuf};
+use crate::helper::{find_key, parse_count, parse_length, text};
+use crate::playlist_info::req_json::MusicReq;
+use crate::video_info::player_response::video_details::Thumbnail;
+
+/// A song, or any other video, as it's shown by YouTube Music.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Track {
+    pub video_id: IdBuf,
+    pub title: String,
+    pub artists: Vec<ArtistRef>,
+    pub album: Option<AlbumRef>,
+    /// The 1-based position of the track on its album.
+    pub track_number: Option<u32>,
+    /// The release year of the album.
+    pub year: Option<u32>,
+    pub length_seconds: Option<u64>,
+    pub is_explicit: bool,
+    pub thumbnails: Vec<Thumbnail>,
+    /// Only requested by [`Track::from_id`].
+    pub lyrics: Option<Lyrics>,
+}
+
+/// The lyrics of a [`Track`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Lyrics {
+    pub text: String,
+    /// The provider of the lyrics, like `Source: Musixmatch`.
+    pub source: Option<String>,
+}
+
+/// An album, single or EP, including its tracks.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Album {
+    /// The id of the album page (`MPREb_...`).
+    pub browse_id: String,
+    /// The playlist of the album's tracks (`OLAK5uy_...`).
+    pub playlist_id: Option<PlaylistIdBuf>,
+    pub title: String,
+    /// `Album`, `Single` or `EP`.
+    pub kind: Option<String>,
+    pub artists: Vec<ArtistRef>,
+    pub year: Option<u32>,
+    pub description: Option<String>,
+    pub is_explicit: bool,
+    pub thumbnails: Vec<Thumbnail>,
+    /// Tracks, which are unavailable (and thus have no video id), are left out.
+    pub tracks: Vec<Track>,
+}
+
+/// An album, single or EP, as it's listed in the discography of an [`Artist`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AlbumSummary {
+    pub browse_id: String,
+    pub title: String,
+    /// `Album`, `Single` or `EP`, if shown.
+    pub kind: Option<String>,
+    pub year: Option<u32>,
+    pub is_explicit: bool,
+    pub thumbnails: Vec<Thumbnail>,
+}
+
+/// The page of an artist.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Artist {
+    pub channel_id: ChannelIdBuf,
+    pub name: String,
+    pub description: Option<String>,
+    pub subscriber_count: Option<u64>,
+    pub thumbnails: Vec<Thumbnail>,
+    /// The most popular tracks of the artist.
+    pub top_tracks: Vec<Track>,
+    /// The latest albums. Use [`Artist::discography`] for all of them.
+    pub albums: Vec<AlbumSummary>,
+    /// The latest singles and EPs. Use [`Artist::discography`] for all of them.
+    pub singles: Vec<AlbumSummary>,
+    /// The pages, which list all albums and all singles.
+    more_albums: Option<BrowseEndpoint>,
+    more_singles: Option<BrowseEndpoint>,
+}
+
+/// A reference to an artist, like the ones in the byline of a track.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ArtistRef {
+    pub name: String,
+    /// `None` for artists without a page, like `Various Artists`.
+    pub channel_id: Option<ChannelIdBuf>,
+}
+
+/// A reference to the album of a track.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AlbumRef {
+    pub title: String,
+    pub browse_id: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct BrowseEndpoint {
+    browse_id: String,
+    params: Option<String>,
+}
+
+impl Track {
+    /// Requests the metadata of a track, including its lyrics, and its position on the album.
+    ///
+    /// ### Errors
+    /// - When one of the requests fails.
+    /// - When a response has an unexpected format.
+    pub async fn from_id(video_id: Id<'_>) -> crate::Result<Self> {
+        let (next, client) = first_music_request("next", &MusicReq::next(video_id.as_str())).await?;
+        let (mut track, lyrics_id) = parse_next(&next)?;
+
+        if let Some(lyrics_id) = lyrics_id {
+            let response = music_request(&client, "browse", &MusicReq::browse(&lyrics_id, None)).await?;
+            track.lyrics = parse_lyrics(&response);
+        }
+        if let Some(album) = track.album.clone() {
+            let response = music_request(&client, "browse", &MusicReq::browse(&album.browse_id, None)).await?;
+            let album = parse_album(&album.browse_id, &response)?;
+            let album_track = album.tracks.iter().find(|t| t.video_id == track.video_id);
+            track.track_number = album_track.and_then(|t| t.track_number);
+            track.year = album.year.or(track.year);
+        }
+        Ok(track)
+    }
+
+    /// Parses a raw response of the YouTube Music `youtubei/v1/next` endpoint. The lyrics and
+    /// the track number are not part of it.
+    ///
+    /// ### Errors
+    /// - When the response is no valid json, or has an unexpected format.
+    pub fn from_next_json(json: &str) -> crate::Result<Self> {
+        parse_next(&serde_json::from_str(json)?).map(|(track, _)| track)
+    }
+
+    /// Requests the video of the track, which provides its streams.
+    ///
+    /// ### Errors
+    /// - When [`Video::from_id`](crate::Video::from_id) fails.
+    #[cfg(feature = "download")]
+    pub async fn video(&self) -> crate::Result<crate::Video> {
+        crate::Video::from_id(self.video_id.clone()).await
+    }
+}
+
+impl Lyrics {
+    /// Parses a raw response of the YouTube Music `youtubei/v1/browse` endpoint for a lyrics id
+    /// (`MPLYt_...`). Returns `None`, if there are no lyrics.
+    ///
+    /// ### Errors
+    /// - When the response is no valid json.
+    pub fn from_json(json: &str) -> crate::Result<Option<Self>> {
+        Ok(parse_lyrics(&serde_json::from_str(json)?))
+    }
+}
+
+impl Album {
+    /// Requests an album by the id of its page (`MPREb_...`).
+    ///
+    /// ### Errors
+    /// - When the request fails.
+    /// - When the response has an unexpected format.
+    pub async fn from_browse_id(browse_id: &str) -> crate::Result<Self> {
+        let (response, _) = first_music_request("browse", &MusicReq::browse(browse_id, None)).await?;
+        parse_album(browse_id, &response)
+    }
+
+    /// Requests an album by the id of its playlist (`OLAK5uy_...`).
+    ///
+    /// ### Errors
+    /// - When the playlist is no album.
+    /// - When one of the requests fails.
+    /// - When a response has an unexpected format.
+    pub async fn from_playlist_id(playlist_id: PlaylistId<'_>) -> crate::Result<Self> {
+        let url = url::Url::parse_with_params("https://music.youtube.com/playlist", &[("list", playlist_id.as_str())])?;
+        let (response, client) = crate::proxy::send(|client| client.get(url.as_str())).await?;
+        let html = response
+            .error_for_status()?
+            .text()
+            .await?;
+        let browse_id = Self::browse_id_from_playlist_page(&html)?;
+
+        let response = music_request(&client, "browse", &MusicReq::browse(&browse_id, None)).await?;
+        parse_album(&browse_id, &response)
+    }
+
+    /// Extracts the id of the album page (`MPREb_...`) from the html of the YouTube Music page of
+    /// an album playlist (`https://music.youtube.com/playlist?list=OLAK5uy_...`).
+    ///
+    /// The id is taken from the header, or the canonical url, of the playlist, so links to other
+    /// albums (i.e. recommendations) on the same page are ignored.
+    ///
+    /// ### Errors
+    /// - When the page contains no initial data.
+    /// - When the playlist is no album ([`Error::LayoutChange`]).
+    pub fn browse_id_from_playlist_page(html: &str) -> crate::Result<String> {
+        let pages = music_initial_data(html);
+        if pages.is_empty() {
+            return Err(Error::layout_change("the album playlist page", "initialData"));
+        }
+        pages
+            .iter()
+            .find_map(album_browse_id)
+            .ok_or_else(|| Error::layout_change(
+                "the album playlist page",
+                "header.browseEndpoint.browseId | microformat.microformatDataRenderer.urlCanonical",
+            ))
+    }
+
+    /// Parses a raw response of the YouTube Music `youtubei/v1/browse` endpoint for an album.
+    ///
+    /// ### Errors
+    /// - When the response is no valid json, or has an unexpected format.
+    pub fn from_json(browse_id: &str, json: &str) -> crate::Result<Self> {
+        parse_album(browse_id, &serde_json::from_str(json)?)
+    }
+}
+
+impl Artist {
+    /// Requests the page of an artist.
+    ///
+    /// ### Errors
+    /// - When the request fails.
+    /// - When the response has an unexpected format.
+    pub async fn from_channel_id(channel_id: ChannelId<'_>) -> crate::Result<Self> {
+        let body = MusicReq::browse(channel_id.as_str(), None);
+        let (response, _) = first_music_request("browse", &body).await?;
+        parse_artist(channel_id.as_owned(), &response)
+    }
+
+    /// Parses a raw response of the YouTube Music `youtubei/v1/browse` endpoint for an artist.
+    ///
+    /// ### Errors
+    /// - When the response is no valid json, or has an unexpected format.
+    pub fn from_json(channel_id: ChannelId<'_>, json: &str) -> crate::Result<Self> {
+        parse_artist(channel_id.as_owned(), &serde_json::from_str(json)?)
+    }
+
+    /// Requests all albums, followed by all singles and EPs of the artist.
+    ///
+    /// For artists with only a few releases, the artist page already lists all of them, and no
+    /// request is made.
+    ///
+    /// ### Errors
+    /// - When one of the requests fails.
+    /// - When a response has an unexpected format.
+    pub async fn discography(&self) -> crate::Result<Vec<AlbumSummary>> {
+        let client = crate_client()?;
+        let mut discography = Vec::new();
+        for (more, shown) in [(&self.more_albums, &self.albums), (&self.more_singles, &self.singles)] {
+            match more {
+                Some(more) => {
+                    let body = MusicReq::browse(&more.browse_id, more.params.clone());
+                    discography.extend(parse_discography_page(&music_request(&client, "browse", &body).await?)?);
+                }
+                None => discography.extend(shown.iter().cloned()),
+            }
+        }
+        Ok(discography)
+    }
+}
+
+/// Parses a raw response of the YouTube Music `youtubei/v1/browse` endpoint for the page, which
+/// lists all albums, or all singles, of an artist.
+///
+/// ### Errors
+/// - When the response is no valid json, or has an unexpected format.
+pub fn parse_discography(json: &str) -> crate::Result<Vec<AlbumSummary>> {
+    parse_discography_page(&serde_json::from_str(json)?)
+}
+
+async fn music_request(client: &Client, endpoint: &str, body: &MusicReq) -> crate::Result<Value> {
+    let response = music_request_builder(client, endpoint, body)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(response.json().await?)
+}
+
+/// Sends the first request of an operation through the proxy pool, and returns the client of the
+/// proxy, which answered, for the following requests.
+async fn first_music_request(endpoint: &str, body: &MusicReq) -> crate::Result<(Value, Client)> {
+    let (response, client) = crate::proxy::send(|client| music_request_builder(client, endpoint, body)).await?;
+    Ok((response.error_for_status()?.json().await?, client))
+}
+
+fn music_request_builder(client: &Client, endpoint: &str, body: &MusicReq) -> reqwest::RequestBuilder {
+    client
+        .post(format!("https://music.youtube.com/youtubei/v1/{endpoint}?prettyPrint=false"))
+        .header(header::ORIGIN, "https://music.youtube.com")
+        .header(header::REFERER, "https://music.youtube.com/")
+        .json(body)
+}
+
+fn parse_next(response: &Value) -> crate::Result<(Track, Option<String>)> {
+    let tabs = find_key(response, "watchNextTabbedResultsRenderer")
+        .and_then(|renderer| renderer["tabs"].as_array())
+        .ok_or_else(|| Error::layout_change("the music watch page", "watchNextTabbedResultsRenderer.tabs"))?;
+
+    let renderers = tabs
+        .first()
+        .and_then(|tab| find_key(tab, "playlistPanelRenderer"))
+        .and_then(|panel| panel["contents"].as_array())
+        .into_iter()
+        .flatten()
+        .map(|item| &item["playlistPanelVideoRenderer"])
+        .filter(|renderer| renderer.is_object())
+        .collect::<Vec<_>>();
+    let renderer = renderers
+        .iter()
+        .find(|renderer| renderer["selected"].as_bool() == Some(true))
+        .or_else(|| renderers.first())
+        .ok_or_else(|| Error::layout_change("the music watch page", "tabs[0].playlistPanelRenderer.contents[].playlistPanelVideoRenderer"))?;
+
+    let byline = Byline::parse(&[runs(&renderer["longBylineText"])]);
+    let track = Track {
+        video_id: IdBuf::from_string(renderer["videoId"].as_str().unwrap_or_default().to_owned())
+            .map_err(|_| Error::layout_change("the music watch page", "playlistPanelVideoRenderer.videoId"))?,
+        title: text(&renderer["title"]).unwrap_or_default(),
+        artists: byline.artists,
+        album: byline.album,
+        track_number: None,
+        year: byline.year,
+        length_seconds: text(&renderer["lengthText"]).as_deref().and_then(parse_length),
+        is_explicit: is_explicit(&renderer["badges"]),
+        thumbnails: thumbnails(&renderer["thumbnail"]),
+        lyrics: None,
+    };
+
+    let lyrics_id = tabs
+        .iter()
+        .filter_map(|tab| tab["tabRenderer"]["endpoint"]["browseEndpoint"]["browseId"].as_str())
+        .find(|id| id.starts_with("MPLY"))
+        .map(str::to_owned);
+    Ok((track, lyrics_id))
+}
+
+fn parse_lyrics(response: &Value) -> Option<Lyrics> {
+    let shelf = find_key(response, "musicDescriptionShelfRenderer")?;
+    Some(Lyrics {
+        text: text(&shelf["description"]).filter(|lyrics| !lyrics.is_empty())?,
+        source: text(&shelf["footer"]),
+    })
+}
+
+/// Extracts the responses, which YouTube Music embeds into its pages as
+/// `initialData.push({path: '...', params: JSON.parse('...'), data: '...'})`.
+fn music_initial_data(html: &str) -> Vec<Value> {
+    static DATA: Lazy<Regex> = Lazy::new(||
+        Regex::new(r"initialData\.push\(\{path: ?'[^']*', ?params: ?JSON\.parse\('[^']*'\), ?data: ?'([^']*)'").unwrap()
+    );
+
+    DATA
+        .captures_iter(html)
+        .filter_map(|captures| serde_json::from_str(&unescape_js(&captures[1])?).ok())
+        .collect()
+}
+
+/// Unescapes the content of a single-quoted JavaScript string literal.
+fn unescape_js(escaped: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(escaped.len());
+    let mut chars = escaped.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'x' => unescaped.push(char::from_u32(parse_hex(&mut chars, 2)?)?),
+            'u' => {
+                let unit = parse_hex(&mut chars, 4)?;
+                // characters outside of the BMP are escaped as surrogate pairs
+                let c = match unit {
+                    0xD800..=0xDBFF => {
+                        let low = chars.as_str().strip_prefix("\\u")?;
+                        chars = low.chars();
+                        let low = parse_hex(&mut chars, 4).filter(|low| (0xDC00..=0xDFFF).contains(low))?;
+                        char::from_u32(0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00))?
+                    }
+                    unit => char::from_u32(unit)?,
+                };
+                unescaped.push(c);
+            }
+            'n' => unescaped.push('\n'),
+            'r' => unescaped.push('\r'),
+            't' => unescaped.push('\t'),
+            c => unescaped.push(c),
+        }
+    }
+    Some(unescaped)
+}
+
+/// Parses the next `len` characters as a hexadecimal number.
+fn parse_hex(chars: &mut std::str::Chars<'_>, len: usize) -> Option<u32> {
+    let digits = chars.by_ref().take(len).collect::<String>();
+    match digits.len() == len {
+        true => u32::from_str_radix(&digits, 16).ok(),
+        false => None,
+    }
+}
+
+/// The id of the album page, which is linked in the header of an album playlist, or is its
+/// canonical url.
+fn album_browse_id(page: &Value) -> Option<String> {
+    let header = ["musicResponsiveHeaderRenderer", "musicDetailHeaderRenderer", "musicImmersiveHeaderRenderer"]
+        .iter()
+        .find_map(|renderer| find_key(&page["header"], renderer).or_else(|| find_key(&page["contents"], renderer)));
+    let from_header = header
+        .and_then(find_album_link)
+        .map(str::to_owned);
+
+    from_header.or_else(|| page["microformat"]["microformatDataRenderer"]["urlCanonical"]
+        .as_str()?
+        .split_once("/browse/")
+        .map(|(_, browse_id)| browse_id)
+        .filter(|browse_id| browse_id.starts_with("MPREb_"))
+        .map(str::to_owned))
+}
+
+/// The first `browseEndpoint` to an album page (`MPREb_...`) in `value`.
+fn find_album_link(value: &Value) -> Option<&str> {
+    match value {
+        Value::Object(object) => object
+            .get("browseEndpoint")
+            .and_then(|endpoint| endpoint["browseId"].as_str())
+            .filter(|browse_id| browse_id.starts_with("MPREb_"))
+            .or_else(|| object.values().find_map(find_album_link)),
+        Value::Array(array) => array.iter().find_map(find_album_link),
+        _ => None,
+    }
+}
+
+fn parse_album(browse_id: &str, response: &Value) -> crate::Result<Album> {
+    let header = find_key(response, "musicResponsiveHeaderRenderer")
+        .or_else(|| find_key(response, "musicDetailHeaderRenderer"))
+        .ok_or_else(|| Error::layout_change("the album page", "musicResponsiveHeaderRenderer"))?;
+
+    let subtitle = runs(&header["subtitle"]);
+    let strapline = runs(&header["straplineTextOne"]);
+    // the old layout lists the artists in the subtitle, after the kind, and the new one in the
+    // strapline, so only the strapline may contain unlinked artists
+    let byline = match strapline {
+        [] => Byline::parse(&[&[], subtitle]),
+        strapline => Byline::parse(&[strapline, subtitle]),
+    };
+    let kind = subtitle
+        .first()
+        .and_then(|run| run["text"].as_str())
+        .filter(|kind| parse_year(kind).is_none())
+        .map(str::to_owned);
+    let playlist_id = find_key(header, "playlistId")
+        .and_then(Value::as_str)
+        .or_else(|| response["microformat"]["microformatDataRenderer"]["urlCanonical"]
+            .as_str()
+            .and_then(|url| url.split("list=").nth(1)))
+        .and_then(|id| PlaylistIdBuf::from_string(id.to_owned()).ok());
+    let description = match find_key(&header["description"], "musicDescriptionShelfRenderer") {
+        Some(shelf) => text(&shelf["description"]),
+        None => text(&header["description"]),
+    };
+
+    let title = text(&header["title"]).unwrap_or_default();
+    let album = AlbumRef { title: title.clone(), browse_id: browse_id.to_owned() };
+    let tracks = find_key(response, "musicShelfRenderer")
+        .and_then(|shelf| shelf["contents"].as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|item| parse_list_item(&item["musicResponsiveListItemRenderer"]))
+        .map(|mut track| {
+            if track.artists.is_empty() {
+                track.artists = byline.artists.clone();
+            }
+            track.album = Some(album.clone());
+            track.year = byline.year;
+            track
+        })
+        .collect();
+
+    Ok(Album {
+        browse_id: browse_id.to_owned(),
+        playlist_id,
+        title,
+        kind,
+        artists: byline.artists,
+        year: byline.year,
+        description,
+        is_explicit: is_explicit(&header["subtitleBadge"]),
+        thumbnails: thumbnails(&header["thumbnail"]),
+        tracks,
+    })
+}
+
+fn parse_artist(channel_id: ChannelIdBuf, response: &Value) -> crate::Result<Artist> {
+    let header = find_key(response, "musicImmersiveHeaderRenderer")
+        .or_else(|| find_key(response, "musicVisualHeaderRenderer"))
+        .ok_or_else(|| Error::layout_change("the artist page", "musicImmersiveHeaderRenderer"))?;
+    let sections = find_key(response, "sectionListRenderer")
+        .and_then(|list| list["contents"].as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let mut artist = Artist {
+        channel_id,
+        name: text(&header["title"]).unwrap_or_default(),
+        description: text(&header["description"]),
+        subscriber_count: text(&header["subscriptionButton"]["subscribeButtonRenderer"]["subscriberCountText"])
+            .as_deref()
+            .and_then(parse_count),
+        thumbnails: thumbnails(&header["thumbnail"]),
+        top_tracks: Vec::new(),
+        albums: Vec::new(),
+        singles: Vec::new(),
+        more_albums: None,
+        more_singles: None,
+    };
+
+    for section in sections {
+        if let Some(contents) = section["musicShelfRenderer"]["contents"].as_array() {
+            artist.top_tracks = contents
+                .iter()
+                .filter_map(|item| parse_list_item(&item["musicResponsiveListItemRenderer"]))
+                .collect();
+            continue;
+        }
+
+        let carousel = &section["musicCarouselShelfRenderer"];
+        let carousel_header = &carousel["header"]["musicCarouselShelfBasicHeaderRenderer"];
+        let title = text(&carousel_header["title"]).unwrap_or_default().to_lowercase();
+        let (shown, more) = match title.as_str() {
+            "albums" => (&mut artist.albums, &mut artist.more_albums),
+            "singles" | "singles & eps" => (&mut artist.singles, &mut artist.more_singles),
+            _ => continue,
+        };
+        *shown = carousel["contents"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|item| parse_album_summary(&item["musicTwoRowItemRenderer"]))
+            .collect();
+        let endpoint = &carousel_header["moreContentButton"]["buttonRenderer"]["navigationEndpoint"]["browseEndpoint"];
+        *more = endpoint["browseId"].as_str().map(|browse_id| BrowseEndpoint {
+            browse_id: browse_id.to_owned(),
+            params: endpoint["params"].as_str().map(str::to_owned),
+        });
+    }
+
+    Ok(artist)
+}
+
+fn parse_discography_page(response: &Value) -> crate::Result<Vec<AlbumSummary>> {
+    let items = find_key(response, "gridRenderer")
+        .and_then(|grid| grid["items"].as_array())
+        .ok_or_else(|| Error::layout_change("the discography page", "gridRenderer.items"))?;
+    Ok(
+        items
+            .iter()
+            .filter_map(|item| parse_album_summary(&item["musicTwoRowItemRenderer"]))
+            .collect()
+    )
+}
+
+/// Parses a track of an album, or of the top tracks of an artist.
+fn parse_list_item(renderer: &Value) -> Option<Track> {
+    let columns = renderer["flexColumns"]
+        .as_array()?
+        .iter()
+        .map(|column| runs(&column["musicResponsiveListItemFlexColumnRenderer"]["text"]))
+        .collect::<Vec<_>>();
+    let (title, columns) = columns.split_first()?;
+
+    let video_id = renderer["playlistItemData"]["videoId"]
+        .as_str()
+        .or_else(|| title.first()?["navigationEndpoint"]["watchEndpoint"]["videoId"].as_str())?;
+    let byline = Byline::parse(columns);
+    let length = renderer["fixedColumns"][0]["musicResponsiveListItemFixedColumnRenderer"]["text"].clone();
+
+    Some(Track {
+        video_id: IdBuf::from_string(video_id.to_owned()).ok()?,
+        title: title.iter().filter_map(|run| run["text"].as_str()).collect(),
+        artists: byline.artists,
+        album: byline.album,
+        track_number: text(&renderer["index"]).and_then(|index| index.trim().parse().ok()),
+        year: byline.year,
+        length_seconds: text(&length).as_deref().and_then(parse_length),
+        is_explicit: is_explicit(&renderer["badges"]),
+        thumbnails: thumbnails(&renderer["thumbnail"]),
+        lyrics: None,
+    })
+}
+
+fn parse_album_summary(renderer: &Value) -> Option<AlbumSummary> {
+    let title = &renderer["title"]["runs"][0];
+    let browse_id = title["navigationEndpoint"]["browseEndpoint"]["browseId"].as_str()?;
+    let subtitle = runs(&renderer["subtitle"]);
+    Some(AlbumSummary {
+        browse_id: browse_id.to_owned(),
+        title: title["text"].as_str()?.to_owned(),
+        kind: subtitle
+            .first()
+            .and_then(|run| run["text"].as_str())
+            .filter(|kind| parse_year(kind).is_none())
+            .map(str::to_owned),
+        year: subtitle.iter().rev().find_map(|run| parse_year(run["text"].as_str()?)),
+        is_explicit: is_explicit(&renderer["subtitleBadges"]),
+        thumbnails: thumbnails(&renderer["thumbnailRenderer"]),
+    })
+}
+
+/// The artists, album and year of a track, which are shown as text runs, like
+/// `Artist & Artist • Album • 2019`.
+#[derive(Default)]
+struct Byline {
+    artists: Vec<ArtistRef>,
+    album: Option<AlbumRef>,
+    year: Option<u32>,
+}
+
+impl Byline {
+    /// Parses the runs of one or more columns. Unlinked runs are only considered artists in the
+    /// first part of the first column.
+    fn parse(columns: &[&[Value]]) -> Self {
+        let mut byline = Self::default();
+        for (i, column) in columns.iter().enumerate() {
+            for (j, part) in column.split(|run| run["text"].as_str() == Some(" • ")).enumerate() {
+                for run in part {
+                    let run_text = run["text"].as_str().unwrap_or_default();
+                    let endpoint = &run["navigationEndpoint"]["browseEndpoint"];
+                    let page_type = endpoint["browseEndpointContextSupportedConfigs"][
+                        "browseEndpointContextMusicConfig"]["pageType"].as_str();
+                    let browse_id = endpoint["browseId"].as_str();
+
+                    match (page_type, browse_id) {
+                        (Some("MUSIC_PAGE_TYPE_ARTIST" | "MUSIC_PAGE_TYPE_USER_CHANNEL"), _) => byline.artists.push(ArtistRef {
+                            name: run_text.to_owned(),
+                            channel_id: browse_id.and_then(|id| ChannelIdBuf::from_string(id.to_owned()).ok()),
+                        }),
+                        (Some("MUSIC_PAGE_TYPE_ALBUM"), Some(browse_id)) => byline.album = Some(AlbumRef {
+                            title: run_text.to_owned(),
+                            browse_id: browse_id.to_owned(),
+                        }),
+                        _ if parse_year(run_text).is_some() => byline.year = parse_year(run_text),
+                        (None, _) if i == 0 && j == 0 && !matches!(run_text.trim(), "" | "&" | "," | "•") => {
+                            byline.artists.push(ArtistRef { name: run_text.to_owned(), channel_id: None })
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        byline
+    }
+}
+
+fn runs(value: &Value) -> &[Value] {
+    value["runs"].as_array().map(Vec::as_slice).unwrap_or_default()
+}
+
+fn parse_year(text: &str) -> Option<u32> {
+    let text = text.trim();
+    match text.len() == 4 && text.bytes().all(|b| b.is_ascii_digit()) {
+        true => text.parse().ok(),
+        false => None,
+    }
+}
+
+fn is_explicit(badges: &Value) -> bool {
+    badges
+        .as_array()
+        .into_iter()
+        .flatten()
+        .any(|badge| badge["musicInlineBadgeRenderer"]["icon"]["iconType"].as_str() == Some("MUSIC_EXPLICIT_BADGE"))
+}
+
+fn thumbnails(value: &Value) -> Vec<Thumbnail> {
+    find_key(value, "thumbnails")
+        .and_then(|thumbnails| serde_json::from_value(thumbnails.clone()).ok())
+        .unwrap_or_default()
+}
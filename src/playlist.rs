@@ -1,90 +1,355 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
 
+use futures::{Stream, StreamExt, TryStreamExt};
 use reqwest::Client;
 use serde::Serialize;
-use crate::{fetcher::{recommended_cookies, recommended_headers}, helper::{initial_data, parese_playlist_metadata, parese_playlist_videos}, playlist_info::{playlist_video::PlaylistVideo, req_json::ContinuationReq, PlaylistInfo}};
+use serde_json::Value;
+use crate::{fetcher::recommended_client_builder, helper::{continuation_pages, continuation_stream, initial_data, parese_playlist_metadata, parese_playlist_videos, parse_playlist_panel, PlaylistPanel}, playlist_info::{playlist_video::PlaylistVideo, req_json::{ContinuationReq, NextReq}, PlaylistInfo}, Id, IdBuf, PlaylistId, PlaylistIdBuf, PlaylistKind, Resource};
 
+/// The number of videos, which are requested for a mix, if no other length is given.
+pub const DEFAULT_MIX_LENGTH: usize = 100;
 
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+
+#[derive(Clone, Serialize, derivative::Derivative)]
+#[derivative(Debug, PartialEq)]
 pub struct Playlist {
     playlist_info: PlaylistInfo,
     videos: Vec<PlaylistVideo>,
+    continuation: Option<String>,
+    #[serde(skip)]
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    client: Client,
 }
 
+/// A client with the recommended headers and cookies, or the client of the next healthy proxy, if
+/// a global [`ProxyPool`](crate::ProxyPool) is installed.
 pub fn crate_client() -> crate::Result<reqwest::Client> {
-    let cookie_jar = recommended_cookies();
-    let headers = recommended_headers();
-
-    let client = Client::builder()
-        .default_headers(headers)
-        .cookie_provider(std::sync::Arc::new(cookie_jar))
-        .build()?;
-    Ok(client)
+    match crate::ProxyPool::global() {
+        Some(pool) => pool
+            .pick()
+            .map(|proxy| proxy.client().clone())
+            .ok_or(crate::Error::NoHealthyProxy),
+        None => Ok(recommended_client_builder().build()?),
+    }
 }
 
 impl Playlist {
-
-    pub(crate) async fn get_videos(init_obj: String) -> crate::Result<Vec<PlaylistVideo>> {
-        let mut vec_videos = Vec::new();
-        let mut init_obj = init_obj;
-        loop {
-            let (videos, continuation) = parese_playlist_videos(&init_obj);
-            vec_videos.extend(videos);
-            if continuation.is_none() {
-                break;
+    /// Requests a playlist including all of its videos.
+    ///
+    /// For big playlists, this can take quite a while, since the videos are requested in pages of
+    /// 100. Use [`Playlist::from_id_lazy`] together with [`Playlist::videos_stream`] to process
+    /// the videos while they are requested.
+    ///
+    /// Mixes (`RD...`) are endless, so only the first [`DEFAULT_MIX_LENGTH`] videos are requested.
+    /// Use [`Playlist::from_mix_id`] to request more or less videos.
+    ///
+    /// ### Errors
+    /// - When [`Playlist::from_id_lazy`] fails.
+    /// - When requesting or parsing one of the following pages fails.
+    pub async fn from_id(id: PlaylistId<'_>) -> crate::Result<Self> {
+        if id.kind() == PlaylistKind::Mix {
+            return Self::from_mix_id(id, DEFAULT_MIX_LENGTH).await;
+        }
+        let mut playlist = match Self::from_id_lazy(id.as_borrowed()).await {
+            // album playlists are not always available as a playlist page
+            Err(err) if id.kind() == PlaylistKind::Album => {
+                log::debug!("album {} has no playlist page, falling back to the watch page: {:?}", id.as_str(), err);
+                // if the fallback fails too, the error of the playlist page is the relevant one
+                return Self::from_watch_panel(id.as_borrowed(), usize::MAX).await.map_err(|fallback_err| {
+                    log::warn!("the watch page fallback of album {} failed: {:?}", id.as_str(), fallback_err);
+                    err
+                });
             }
-            let continuation = continuation.unwrap();
-            let init_obj_r = Playlist::get_from_continuation(&continuation).await;
-            if init_obj_r.is_err() { break; }
-            init_obj = init_obj_r.unwrap();
+            playlist => playlist?,
+        };
+        if playlist.videos.is_empty() && id.kind() == PlaylistKind::Album {
+            return Self::from_watch_panel(id, usize::MAX).await;
         }
-        Ok(vec_videos)
+        let pages: Vec<_> = playlist
+            .pages()
+            .try_collect()
+            .await?;
+        playlist.videos.extend(pages.into_iter().flat_map(|(videos, _)| videos));
+        playlist.continuation = None;
+        Ok(playlist)
     }
 
-    pub async fn from_id(id: &str) -> crate::Result<Self> {
-        let client = crate_client().unwrap();
-        let req = client.get(format!("https://www.youtube.com/playlist?list={}", id)).send().await?;
+    /// Requests a playlist, but only the first page of its videos.
+    /// The remaining videos can be requested with [`Playlist::videos_stream`], or page by page with
+    /// [`Playlist::pages`].
+    ///
+    /// Mixes don't have a playlist page, so this is the same as calling [`Playlist::from_mix_id`]
+    /// with [`DEFAULT_MIX_LENGTH`].
+    ///
+    /// ### Errors
+    /// - When the playlist does not exist.
+    /// - When the playlist page has an unexpected format.
+    pub async fn from_id_lazy(id: PlaylistId<'_>) -> crate::Result<Self> {
+        if id.kind() == PlaylistKind::Mix {
+            return Self::from_mix_id(id, DEFAULT_MIX_LENGTH).await;
+        }
+        let (req, client) = crate::proxy::send(|client| client.get(id.playlist_url())).await?;
         if !req.status().is_success() {
             return Err(crate::Error::BadIdFormat);
         }
         let body = req.text().await?;
-        let init_obj = initial_data(&body);
-        if init_obj.is_none() {
-            return Err(crate::Error::Custom("Failed to parse initial data".into()));
+        let init_obj = initial_data(&body)
+            .ok_or_else(|| crate::Error::layout_change("the playlist page", "ytInitialData"))?;
+        Self::from_initial_data_with_client(&init_obj, client)
+    }
+
+    /// Creates a playlist from the `ytInitialData` of a playlist page, without performing any
+    /// requests. Only the first page of videos is part of `ytInitialData`.
+    ///
+    /// ### Errors
+    /// - When `initial_data` has an unexpected format.
+    pub fn from_initial_data(initial_data: &str) -> crate::Result<Self> {
+        Self::from_initial_data_with_client(initial_data, crate_client()?)
+    }
+
+    fn from_initial_data_with_client(initial_data: &str, client: Client) -> crate::Result<Self> {
+        let playlist_info = parese_playlist_metadata(initial_data)?;
+        let (videos, continuation) = parese_playlist_videos(&serde_json::from_str::<Value>(initial_data)?)?;
+        Ok(Self { playlist_info, videos, continuation, client })
+    }
+
+    /// Requests up to `max_length` videos of a mix (`RD...`).
+    ///
+    /// Mixes don't have a playlist page, so the videos are taken from the playlist panel of the
+    /// watch page instead. Since mixes are generated on the fly, the videos are numbered in the
+    /// order they were requested.
+    ///
+    /// ### Errors
+    /// - When the mix does not exist.
+    /// - When a watch page or response has an unexpected format.
+    pub async fn from_mix_id(id: PlaylistId<'_>, max_length: usize) -> crate::Result<Self> {
+        Self::from_watch_panel(id, max_length).await
+    }
+
+    /// Creates a playlist from the `ytInitialData` of a watch page with the playlist panel (i.e.
+    /// `https://www.youtube.com/watch?list=<id>`), and the raw `youtubei/v1/next` responses of the
+    /// following windows of the panel, without performing any requests.
+    ///
+    /// Stops at `max_length` videos, or when there are no more responses, or when a response
+    /// contains no new videos.
+    ///
+    /// ### Errors
+    /// - When `initial_data` or one of the responses has an unexpected format.
+    pub fn from_watch_panel_data<'a>(
+        id: PlaylistId<'_>,
+        initial_data: &str,
+        next_responses: impl IntoIterator<Item=&'a str>,
+        max_length: usize,
+    ) -> crate::Result<Self> {
+        let mut pages = PanelPages::new(parse_playlist_panel(&serde_json::from_str(initial_data)?)?, max_length);
+        let mut next_responses = next_responses.into_iter();
+        while pages.next_request(id.as_borrowed()).is_some() {
+            let response = match next_responses.next() {
+                Some(response) => response,
+                None => break,
+            };
+            if !pages.push(parse_playlist_panel(&serde_json::from_str(response)?)?.videos) {
+                break;
+            }
         }
-        let init_obj = init_obj.unwrap();
-        let playlist_info = parese_playlist_metadata(&init_obj)?;
-        let vec_videos = Playlist::get_videos(init_obj).await?;
-        Ok(Self { videos: vec_videos, playlist_info })
+        Ok(pages.into_playlist(id, crate_client()?))
+    }
+
+    async fn from_watch_panel(id: PlaylistId<'_>, max_length: usize) -> crate::Result<Self> {
+        let mut url = url::Url::parse_with_params("https://www.youtube.com/watch", &[("list", id.as_str())])?;
+        // mixes of a single video are identified by `RD<video id>`
+        if let Some(video_id) = id.strip_prefix("RD").filter(|video_id| Id::from_str(video_id).is_ok()) {
+            url.query_pairs_mut().append_pair("v", video_id);
+        }
+
+        let (response, client) = crate::proxy::send(|client| client.get(url.as_str())).await?;
+        let body = response.error_for_status()?.text().await?;
+        let init_obj = initial_data(&body)
+            .ok_or_else(|| crate::Error::layout_change("the watch page", "ytInitialData"))?;
+        let mut pages = PanelPages::new(parse_playlist_panel(&serde_json::from_str(&init_obj)?)?, max_length);
+
+        while let Some(request) = pages.next_request(id.as_borrowed()) {
+            let response = client
+                .post("https://www.youtube.com/youtubei/v1/next?prettyPrint=false")
+                .json(&request)
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<Value>()
+                .await?;
+            if !pages.push(parse_playlist_panel(&response)?.videos) {
+                break;
+            }
+        }
+        Ok(pages.into_playlist(id, client))
+    }
+
+    /// The id of the playlist, taken from [`PlaylistInfo::page_url`].
+    pub fn id(&self) -> Option<PlaylistIdBuf> {
+        match Resource::parse(&self.playlist_info.page_url).ok()? {
+            Resource::Playlist { id } => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Whether the playlist is a mix (`RD...`), which is generated by YouTube.
+    #[inline]
+    pub fn is_mix(&self) -> bool {
+        self.id().is_some_and(|id| id.kind() == PlaylistKind::Mix)
+    }
+
+    /// Whether the playlist is an album (`OLAK5uy_...`), which is generated by YouTube Music.
+    /// The [`PlaylistVideo::index`] of each video is its track number.
+    #[inline]
+    pub fn is_album(&self) -> bool {
+        self.id().is_some_and(|id| id.kind() == PlaylistKind::Album)
     }
 
+    /// Requests the playlist of a playlist url, or of a video url with a playlist context.
+    ///
+    /// ### Errors
+    /// - When `url` does not contain a playlist id ([`Error::BadIdFormat`](crate::Error::BadIdFormat)).
+    /// - When [`Playlist::from_id`] fails.
     pub async fn from_url(url: &url::Url) -> crate::Result<Self> {
-        let hash_query: HashMap<_, _> = url.query_pairs().into_owned().collect();
-        let id_raw = hash_query.get("list");
-        if id_raw.is_none() {
-            return Err(crate::Error::BadIdFormat);
+        match Resource::parse(url.as_str())? {
+            Resource::Playlist { id } | Resource::Video { playlist_id: Some(id), .. } => Self::from_id(id).await,
+            _ => Err(crate::Error::BadIdFormat),
         }
-        let id = id_raw.unwrap();
-        Self::from_id(id).await
     }
 
-    pub(crate) async fn get_from_continuation(continuation: &str) -> crate::Result<String> {
-        let body = ContinuationReq::new(continuation);
-        let client = crate_client().unwrap();
-        let req = client.post("https://www.youtube.com/youtubei/v1/browse?prettyPrint=false")
-        .json(&body).send().await?;
-        let body = req.text().await?;
+    pub(crate) async fn get_from_continuation(client: &Client, continuation: &str) -> crate::Result<String> {
+        let req = client
+            .post("https://www.youtube.com/youtubei/v1/browse?prettyPrint=false")
+            .json(&ContinuationReq::new(continuation))
+            .send()
+            .await?;
+        let body = crate::Error::check_response(req)?.text().await?;
         Ok(body)
     }
 
+    /// Lazily requests the videos of a playlist, starting at a continuation token, which was
+    /// previously saved from [`Playlist::continuation`] or [`Playlist::pages`].
+    pub fn videos_from_continuation(continuation: String) -> impl Stream<Item=crate::Result<PlaylistVideo>> {
+        continuation_stream("browse", None, Some(continuation), parese_playlist_videos)
+    }
+
+    /// Lazily requests the pages of a playlist, starting at a continuation token, which was
+    /// previously saved from [`Playlist::continuation`] or [`Playlist::pages`].
+    ///
+    /// Each page is yielded together with the continuation token of the following one.
+    pub fn pages_from_continuation(continuation: String) -> impl Stream<Item=crate::Result<(Vec<PlaylistVideo>, Option<String>)>> {
+        continuation_pages("browse", None, Some(continuation), parese_playlist_videos)
+    }
+
+    /// Lazily requests the pages of videos after the already requested ones.
+    ///
+    /// Each page is yielded together with the continuation token of the following one, so a walk
+    /// over a big playlist can be resumed with [`Playlist::pages_from_continuation`] after it
+    /// failed. The stream ends after the first error.
+    pub fn pages(&self) -> impl Stream<Item=crate::Result<(Vec<PlaylistVideo>, Option<String>)>> {
+        continuation_pages("browse", Some(self.client.clone()), self.continuation.clone(), parese_playlist_videos)
+    }
+
+    /// Lazily requests the videos of the playlist, page by page.
+    ///
+    /// The stream first yields the already requested videos, which are cloned one by one, and then
+    /// requests the following pages with [`Playlist::pages`]. It ends after the first error.
+    pub fn videos_stream(&self) -> impl Stream<Item=crate::Result<PlaylistVideo>> + '_ {
+        futures::stream::iter(self.videos.iter().cloned().map(Ok))
+            .chain(Self::flatten_pages(self.pages()))
+    }
+
+    /// Like [`Playlist::videos_stream`], but moves the already requested videos into the stream
+    /// instead of cloning them.
+    pub fn into_videos_stream(self) -> impl Stream<Item=crate::Result<PlaylistVideo>> {
+        let pages = Self::flatten_pages(self.pages());
+        futures::stream::iter(self.videos.into_iter().map(Ok))
+            .chain(pages)
+    }
+
+    fn flatten_pages(
+        pages: impl Stream<Item=crate::Result<(Vec<PlaylistVideo>, Option<String>)>>,
+    ) -> impl Stream<Item=crate::Result<PlaylistVideo>> {
+        pages
+            .map_ok(|(videos, _)| futures::stream::iter(videos.into_iter().map(Ok)))
+            .try_flatten()
+    }
 
     pub fn playlist_info(&self) -> PlaylistInfo {
         self.playlist_info.clone()
     }
 
-    pub fn videos(&self) -> Vec<PlaylistVideo> {
-        self.videos.clone()
+    /// The videos, which have already been requested.
+    /// This includes all videos, if the playlist was created using [`Playlist::from_id`].
+    #[inline]
+    pub fn videos(&self) -> &[PlaylistVideo] {
+        &self.videos
+    }
+
+    /// The continuation token of the first video, which has not been requested yet, or `None`, if
+    /// all videos have been requested.
+    #[inline]
+    pub fn continuation(&self) -> Option<&str> {
+        self.continuation.as_deref()
     }
 
-}
\ No newline at end of file
+}
+
+/// The videos of a playlist panel, which are collected window by window, since each response only
+/// contains a window of the playlist around the requested video.
+struct PanelPages {
+    panel: PlaylistPanel,
+    seen: HashSet<IdBuf>,
+    max_length: usize,
+}
+
+impl PanelPages {
+    fn new(mut panel: PlaylistPanel, max_length: usize) -> Self {
+        let videos = std::mem::take(&mut panel.videos);
+        let mut pages = Self { panel, seen: HashSet::new(), max_length };
+        pages.push(videos);
+        pages
+    }
+
+    /// Appends the new videos of a window, and returns whether there were any.
+    fn push(&mut self, videos: Vec<PlaylistVideo>) -> bool {
+        let len = self.panel.videos.len();
+        let seen = &mut self.seen;
+        self.panel.videos.extend(videos.into_iter().filter(|video| seen.insert(video.video_id.clone())));
+        self.panel.videos.len() > len
+    }
+
+    /// The request for the window after the last video, or `None`, if all videos were collected.
+    fn next_request(&self, id: PlaylistId<'_>) -> Option<NextReq> {
+        let videos = &self.panel.videos;
+        let complete = self.panel.total_videos.is_none_or(|total| videos.len() as u64 >= total);
+        if videos.len() >= self.max_length || (complete && !self.panel.is_infinite) {
+            return None;
+        }
+        let last = videos.last()?;
+        Some(NextReq::with_playlist(last.video_id.as_str(), id.as_str(), last.index.saturating_sub(1)))
+    }
+
+    fn into_playlist(self, id: PlaylistId<'_>, client: Client) -> Playlist {
+        let PlaylistPanel { title, is_infinite, mut videos, .. } = self.panel;
+        videos.truncate(self.max_length);
+        if is_infinite {
+            for (index, video) in videos.iter_mut().enumerate() {
+                video.index = index as u64 + 1;
+            }
+        }
+
+        let playlist_info = PlaylistInfo {
+            thumbnails: videos.first().map(|video| video.thumbnails.clone()).unwrap_or_default(),
+            title,
+            page_url: id.playlist_url().into(),
+            video_count: match is_infinite {
+                true => None,
+                false => Some(videos.len() as u64),
+            },
+            ..PlaylistInfo::default()
+        };
+        Playlist { playlist_info, videos, continuation: None, client }
+    }
+}
@@ -1,11 +1,13 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 use crate::video_info::player_response::video_details::Thumbnail;
+use crate::ChannelIdBuf;
 
 pub(crate) mod req_json;
 pub mod playlist_video;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct PlaylistInfo {
     #[serde(rename = "thumbnail")]
     #[serde(serialize_with = "Thumbnail::serialize_vec")]
@@ -14,5 +16,32 @@ pub struct PlaylistInfo {
     pub title: String,
     pub description: String,
     #[serde(rename(deserialize = "urlCanonical"))]
-    pub page_url: String
+    pub page_url: String,
+    #[serde(default)]
+    pub owner_name: Option<String>,
+    #[serde(default)]
+    pub owner_channel_id: Option<ChannelIdBuf>,
+    #[serde(default)]
+    pub video_count: Option<u64>,
+    #[serde(default)]
+    pub view_count: Option<u64>,
+    /// The raw last updated text, i.e. `Last updated on Apr 5, 2021` or `Updated today`.
+    #[serde(default)]
+    pub last_updated_text: Option<String>,
+    /// Only available, if YouTube shows an absolute date.
+    #[serde(default)]
+    pub last_updated: Option<NaiveDate>,
+    #[serde(default)]
+    pub privacy: Option<PlaylistPrivacy>,
+    /// Whether YouTube hid unavailable (deleted or private) videos from the playlist.
+    #[serde(default)]
+    pub unavailable_videos_hidden: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PlaylistPrivacy {
+    Public,
+    Unlisted,
+    Private,
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_with::{serde_as, json::JsonString};
 
-use crate::{video_info::player_response::video_details::Thumbnail, IdBuf};
+use crate::{video_info::player_response::video_details::Thumbnail, ChannelIdBuf, IdBuf};
 
 #[serde_as]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -10,16 +10,56 @@ pub struct PlaylistVideo {
     #[serde(deserialize_with = "deserialize_index")]
     pub index: u64,
     pub video_id: IdBuf,
-    #[serde(deserialize_with = "deserialize_run")]
+    /// For unavailable videos, this is a placeholder like `[Deleted video]`.
+    #[serde(deserialize_with = "crate::serde_impl::text::deserialize")]
     pub title: String,
-    #[serde_as(as = "JsonString")]
-    pub length_seconds: u64,
+    /// `None` for unavailable videos.
+    #[serde(default)]
+    #[serde_as(as = "Option<JsonString>")]
+    pub length_seconds: Option<u64>,
     #[serde(rename = "thumbnail")]
     #[serde(deserialize_with = "Thumbnail::deserialize_vec")]
     pub thumbnails: Vec<Thumbnail>,
+    /// Empty for unavailable videos.
     #[serde(rename(deserialize = "shortBylineText"))]
-    #[serde(deserialize_with = "deserialize_run")]
+    #[serde(default, deserialize_with = "deserialize_run")]
     pub author: String,
+    #[serde(skip_deserializing)]
+    pub owner_channel_id: Option<ChannelIdBuf>,
+    #[serde(default)]
+    pub is_playable: bool,
+    #[serde(skip_deserializing)]
+    pub availability: Availability,
+    /// The id of this entry in the playlist, which is needed to edit or remove it.
+    #[serde(default)]
+    pub set_video_id: Option<String>,
+    /// The abbreviated views, like `1.2K views`.
+    #[serde(skip_deserializing)]
+    pub view_count_text: Option<String>,
+    /// The relative publish date, like `2 years ago`.
+    #[serde(skip_deserializing)]
+    pub published_time_text: Option<String>,
+}
+
+/// Whether a [`PlaylistVideo`] can be watched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Availability {
+    #[default]
+    Available,
+    Private,
+    Deleted,
+    MembersOnly,
+    RegionBlocked,
+    /// Unavailable for another, or an unknown reason.
+    Unavailable,
+}
+
+impl PlaylistVideo {
+    /// Whether the video can be watched.
+    #[inline]
+    pub fn is_available(&self) -> bool {
+        self.availability == Availability::Available
+    }
 }
 
 fn deserialize_run<'de, D>(deserializer: D) -> Result<String, D::Error>
@@ -14,6 +14,7 @@ impl ContinuationReq {
                 client: ClientSender {
                     client_name: "WEB".to_string(),
                     client_version: "2.20221124.00.00".to_string(),
+                    hl: None,
                 },
             },
             continuation: continuation.to_string(),
@@ -32,4 +33,119 @@ pub(crate) struct ContextSender {
 pub(crate) struct ClientSender {
     pub(crate) client_name: String,
     pub(crate) client_version: String,
-}
\ No newline at end of file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) hl: Option<String>,
+}
+/// The body of a `youtubei/v1/next` request for a video, or a video of a playlist.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NextReq {
+    pub(crate) context: ContextSender,
+    pub(crate) video_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) playlist_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) playlist_index: Option<u64>,
+}
+
+impl NextReq {
+    pub(crate) fn new(video_id: &str) -> Self {
+        Self {
+            context: ContinuationReq::new("").context,
+            video_id: video_id.to_string(),
+            playlist_id: None,
+            playlist_index: None,
+        }
+    }
+
+    pub(crate) fn with_playlist(video_id: &str, playlist_id: &str, playlist_index: u64) -> Self {
+        Self {
+            playlist_id: Some(playlist_id.to_string()),
+            playlist_index: Some(playlist_index),
+            ..Self::new(video_id)
+        }
+    }
+}
+
+/// The body of a `youtubei/v1/navigation/resolve_url` request.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ResolveUrlReq {
+    pub(crate) context: ContextSender,
+    pub(crate) url: String,
+}
+
+impl ResolveUrlReq {
+    pub(crate) fn new(url: &str) -> Self {
+        Self {
+            context: ContinuationReq::new("").context,
+            url: url.to_string(),
+        }
+    }
+}
+
+/// The body of a `youtubei/v1/search` request.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SearchReq {
+    pub(crate) context: ContextSender,
+    pub(crate) query: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) params: Option<String>,
+}
+
+impl SearchReq {
+    pub(crate) fn new(query: &str, params: Option<String>) -> Self {
+        Self {
+            context: ContinuationReq::new("").context,
+            query: query.to_string(),
+            params,
+        }
+    }
+}
+
+/// The body of a `youtubei/v1/browse` or `youtubei/v1/next` request of the YouTube Music client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MusicReq {
+    pub(crate) context: ContextSender,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) browse_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) params: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) video_id: Option<String>,
+}
+
+impl MusicReq {
+    pub(crate) fn browse(browse_id: &str, params: Option<String>) -> Self {
+        Self {
+            browse_id: Some(browse_id.to_string()),
+            params,
+            ..Self::new()
+        }
+    }
+
+    pub(crate) fn next(video_id: &str) -> Self {
+        Self {
+            video_id: Some(video_id.to_string()),
+            ..Self::new()
+        }
+    }
+
+    fn new() -> Self {
+        Self {
+            context: ContextSender {
+                client: ClientSender {
+                    client_name: "WEB_REMIX".to_string(),
+                    client_version: "1.20240918.01.00".to_string(),
+                    // the shelves of artist pages are told apart by their English titles
+                    hl: Some("en".to_string()),
+                },
+            },
+            browse_id: None,
+            params: None,
+            video_id: None,
+        }
+    }
+}
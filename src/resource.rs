@@ -0,0 +1,273 @@
+use alloc::borrow::Cow;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{ChannelId, ChannelIdBuf, Error, Id, IdBuf, PlaylistId, PlaylistIdBuf, Result};
+
+/// The maximum number of nested redirects, which are followed by [`Resource::parse`].
+const MAX_REDIRECTS: usize = 4;
+
+/// Path segments of `youtube.com`, which are no vanity channel names.
+const RESERVED_PATHS: &[&str] = &[
+    "about", "account", "feed", "feeds", "gaming", "hashtag", "kids", "logout", "music", "premium",
+    "redirect", "results", "signin", "t", "upload",
+];
+
+/// Anything a YouTube url, or a bare id, can point to.
+///
+/// ```
+///# use rustube::{Id, Resource};
+/// let resource = Resource::parse("https://youtu.be/jNQXAC9IVRw?si=abc&t=1m5s").unwrap();
+/// assert_eq!(resource, Resource::Video {
+///     id: Id::from_str("jNQXAC9IVRw").unwrap().into_owned(),
+///     start_seconds: Some(65),
+///     playlist_id: None,
+///     playlist_index: None,
+/// });
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Resource {
+    Video {
+        id: IdBuf,
+        /// The time the video should start at (`t=`, `start=`, `#t=`).
+        start_seconds: Option<u64>,
+        /// The playlist the video was opened from (`list=`).
+        playlist_id: Option<PlaylistIdBuf>,
+        /// The 1-based position of the video in the playlist (`index=`).
+        playlist_index: Option<u64>,
+    },
+    Playlist {
+        id: PlaylistIdBuf,
+    },
+    Channel(ChannelRef),
+    /// A clip of a video. Clip ids have to be resolved to the video they were taken from.
+    Clip {
+        id: String,
+    },
+    /// A live stream (`/live/<video id>`), or the current live stream of a channel
+    /// (`/@handle/live`).
+    Live {
+        video_id: Option<IdBuf>,
+        channel: Option<ChannelRef>,
+    },
+}
+
+/// The different ways a channel is referred to in urls.
+///
+/// Everything except [`ChannelRef::Id`] can be resolved to a [`ChannelId`] using a
+/// [`ChannelResolver`](crate::ChannelResolver) together with [`ChannelRef::url`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChannelRef {
+    Id(ChannelIdBuf),
+    /// A handle, including the leading `@`.
+    Handle(String),
+    /// A custom url (`/c/<name>`), or a vanity url (`/<name>`).
+    Custom(String),
+    /// A legacy username (`/user/<name>`).
+    User(String),
+}
+
+impl ChannelRef {
+    /// The url of the channel page.
+    pub fn url(&self) -> String {
+        match self {
+            Self::Id(id) => id.channel_url().into(),
+            Self::Handle(handle) => format!("https://www.youtube.com/{handle}"),
+            Self::Custom(name) => format!("https://www.youtube.com/c/{name}"),
+            Self::User(name) => format!("https://www.youtube.com/user/{name}"),
+        }
+    }
+}
+
+impl Resource {
+    /// Parses a url, or a bare id, of a video, playlist, channel, clip or live stream.
+    ///
+    /// Besides the usual `youtube.com` urls, this understands `youtu.be`, `m.` / `music.`
+    /// subdomains, `youtube-nocookie.com` embeds, `/shorts/`, `/live/`, `/v/` and `/clip/`
+    /// paths, `attribution_link`s and other redirects, as well as percent-encoded urls.
+    /// Tracking parameters like `si=` or `feature=` are ignored.
+    ///
+    /// Bare ids are recognized by their format: 11 characters for videos, `UC` + 22 characters
+    /// for channels, a leading `@` for handles, and [`PLAYLIST_ID_PATTERN`](crate::PLAYLIST_ID_PATTERN)
+    /// for playlists.
+    ///
+    /// ### Errors
+    /// - When `input` is neither a known id nor a YouTube url ([`Error::BadIdFormat`]).
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::parse_nested(input, 0)
+    }
+
+    fn parse_nested(input: &str, depth: usize) -> Result<Self> {
+        let input = input.trim();
+        if depth > MAX_REDIRECTS {
+            return Err(Error::BadIdFormat);
+        }
+        if let Some(resource) = Self::parse_bare_id(input) {
+            return Ok(resource);
+        }
+
+        // completely percent-encoded urls, like `https%3A%2F%2Fyoutu.be%2F...`
+        let is_encoded = input.contains("%2F") || input.contains("%2f");
+        if is_encoded && !input.contains('/') {
+            let decoded = url_escape::decode(input);
+            return Self::parse_nested(&decoded, depth + 1);
+        }
+
+        let url = match input.contains("://") {
+            true => Url::parse(input),
+            false => Url::parse(&format!("https://{}", input.trim_start_matches('/'))),
+        }.map_err(|_| Error::BadIdFormat)?;
+
+        let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+        let host = ["www.", "m.", "music.", "gaming."]
+            .iter()
+            .fold(host.as_str(), |host, prefix| host.strip_prefix(prefix).unwrap_or(host));
+
+        match host {
+            "youtu.be" => Self::parse_share_url(&url),
+            "youtube.com" | "youtube-nocookie.com" => Self::parse_youtube_url(&url, depth),
+            // redirects, like `google.com/url?q=...` or `consent.youtube.com/m?continue=...`
+            _ => Self::parse_redirect(&url, depth),
+        }
+    }
+
+    fn parse_bare_id(input: &str) -> Option<Self> {
+        static HANDLE: Lazy<Regex> = Lazy::new(||
+            Regex::new(r"^@[\w.-]{3,30}$").unwrap()
+        );
+
+        if let Ok(id) = Id::from_str(input) {
+            return Some(Self::video(id.into_owned()));
+        }
+        if let Ok(id) = ChannelId::from_str(input) {
+            return Some(Self::Channel(ChannelRef::Id(id.into_owned())));
+        }
+        if HANDLE.is_match(input) {
+            return Some(Self::Channel(ChannelRef::Handle(input.to_owned())));
+        }
+        if let Ok(id) = PlaylistId::from_str(input) {
+            return Some(Self::Playlist { id: id.into_owned() });
+        }
+        None
+    }
+
+    fn parse_share_url(url: &Url) -> Result<Self> {
+        let id = url
+            .path_segments()
+            .and_then(|mut segments| segments.next())
+            .and_then(|id| Id::from_str(id).ok())
+            .ok_or(Error::BadIdFormat)?;
+        Ok(Self::video(id.into_owned()).with_query(url))
+    }
+
+    fn parse_youtube_url(url: &Url, depth: usize) -> Result<Self> {
+        let segments = url
+            .path_segments()
+            .map(|segments| segments.filter(|segment| !segment.is_empty()).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let query = |key: &str| url
+            .query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value);
+        let video_id = |id: &str| Id::from_str(id).map(|id| id.into_owned());
+        let playlist_id = |id: Cow<'_, str>| PlaylistIdBuf::from_string(id.into_owned()).map_err(|_| Error::BadIdFormat);
+
+        let resource = match segments.as_slice() {
+            ["watch"] | [] => match (query("v"), query("list")) {
+                (Some(id), _) => Self::video(video_id(&id)?),
+                (None, Some(list)) => Self::Playlist { id: playlist_id(list)? },
+                (None, None) => return Err(Error::BadIdFormat),
+            },
+            ["embed", "videoseries"] | ["playlist"] => Self::Playlist {
+                id: playlist_id(query("list").ok_or(Error::BadIdFormat)?)?,
+            },
+            ["shorts" | "embed" | "v" | "e" | "watch", id] => Self::video(video_id(id)?),
+            ["live", id] => Self::Live { video_id: Some(video_id(id)?), channel: None },
+            ["clip", id] => return Ok(Self::Clip { id: (*id).to_owned() }),
+            ["attribution_link" | "redirect"] => return Self::parse_redirect(url, depth),
+            [first, rest @ ..] => {
+                let (channel, rest) = match (*first, rest) {
+                    ("channel", [id, rest @ ..]) => (
+                        ChannelRef::Id(ChannelIdBuf::from_string((*id).to_owned()).map_err(|_| Error::BadIdFormat)?),
+                        rest,
+                    ),
+                    ("c", [name, rest @ ..]) => (ChannelRef::Custom(decode(name)), rest),
+                    ("user", [name, rest @ ..]) => (ChannelRef::User(decode(name)), rest),
+                    (handle, rest) if handle.starts_with('@') => (ChannelRef::Handle(decode(handle)), rest),
+                    (name, rest) if !RESERVED_PATHS.contains(&name) => (ChannelRef::Custom(decode(name)), rest),
+                    _ => return Err(Error::BadIdFormat),
+                };
+                match rest.first() {
+                    Some(&"live") => Self::Live { video_id: None, channel: Some(channel) },
+                    _ => Self::Channel(channel),
+                }
+            }
+        };
+        Ok(resource.with_query(url))
+    }
+
+    fn parse_redirect(url: &Url, depth: usize) -> Result<Self> {
+        url
+            .query_pairs()
+            .filter(|(key, _)| ["u", "q", "url", "continue", "next"].contains(&key.as_ref()))
+            .find_map(|(_, target)| {
+                // attribution links contain relative urls (`u=/watch?v=...`)
+                let target = match target.starts_with('/') {
+                    true => Cow::Owned(format!("https://www.youtube.com{target}")),
+                    false => target,
+                };
+                Self::parse_nested(&target, depth + 1).ok()
+            })
+            .ok_or(Error::BadIdFormat)
+    }
+
+    #[inline]
+    fn video(id: IdBuf) -> Self {
+        Self::Video { id, start_seconds: None, playlist_id: None, playlist_index: None }
+    }
+
+    /// Adds the start time and playlist context of the query (and fragment) of `url` to videos.
+    fn with_query(mut self, url: &Url) -> Self {
+        if let Self::Video { ref mut start_seconds, ref mut playlist_id, ref mut playlist_index, .. } = self {
+            for (key, value) in url.query_pairs() {
+                match key.as_ref() {
+                    "t" | "start" | "time_continue" => *start_seconds = parse_time(&value).or(*start_seconds),
+                    "list" => *playlist_id = PlaylistIdBuf::from_string(value.into_owned()).ok(),
+                    "index" => *playlist_index = value.parse().ok(),
+                    _ => {}
+                }
+            }
+            if let Some(time) = url.fragment().and_then(|fragment| fragment.strip_prefix("t=")) {
+                *start_seconds = parse_time(time).or(*start_seconds);
+            }
+        }
+        self
+    }
+}
+
+fn decode(segment: &str) -> String {
+    url_escape::decode(segment).into_owned()
+}
+
+/// Parses times like `90`, `90s`, `1m30s` or `1h2m3s` into seconds.
+fn parse_time(time: &str) -> Option<u64> {
+    static TIME: Lazy<Regex> = Lazy::new(||
+        Regex::new(r"^(?:(\d+)h)?(?:(\d+)m)?(?:(\d+)s?)?$").unwrap()
+    );
+
+    let captures = TIME.captures(time.trim())?;
+    if captures.iter().skip(1).all(|group| group.is_none()) {
+        return None;
+    }
+    let part = |i: usize| captures
+        .get(i)
+        .map_or(Some(0), |part| part.as_str().parse::<u64>().ok());
+    // times are user input, so absurdly large ones are ignored instead of overflowing
+    part(1)?
+        .checked_mul(3600)?
+        .checked_add(part(2)?.checked_mul(60)?)?
+        .checked_add(part(3)?)
+}
@@ -0,0 +1,358 @@
+//! Searching for videos, channels, playlists and movies, and the suggestions of the search box.
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt, TryStreamExt};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::Error;
+use crate::channel_info::channel_video::{ChannelVideo, PublishedTime};
+use crate::helper::{continuation_stream, parse_relative_time, parse_search_page};
+use crate::playlist_info::req_json::SearchReq;
+use crate::search_info::{SearchPage, SearchResult};
+
+/// A search query together with its filters.
+///
+/// ```no_run
+///# use rustube::search::{ResultType, Search, SortBy};
+///# use futures::TryStreamExt;
+///# #[tokio::main]
+///# async fn main() -> rustube::Result<()> {
+/// let results = Search::new("rust programming")
+///     .result_type(ResultType::Video)
+///     .sort_by(SortBy::ViewCount)
+///     .results()
+///     .try_collect::<Vec<_>>()
+///     .await?;
+///# Ok(())
+///# }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Search {
+    pub query: String,
+    pub filter: SearchFilter,
+}
+
+/// The filters of a [`Search`], which are shown in the "Filters" menu of the search page.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SearchFilter {
+    pub result_type: Option<ResultType>,
+    pub upload_date: Option<UploadDate>,
+    pub duration: Option<VideoDuration>,
+    pub features: Vec<Feature>,
+    pub sort_by: SortBy,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ResultType {
+    Video,
+    Channel,
+    Playlist,
+    Movie,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UploadDate {
+    LastHour,
+    Today,
+    ThisWeek,
+    ThisMonth,
+    ThisYear,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VideoDuration {
+    /// Under 4 minutes.
+    Short,
+    /// 4 - 20 minutes.
+    Medium,
+    /// Over 20 minutes.
+    Long,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Feature {
+    Live,
+    FourK,
+    Hd,
+    Subtitles,
+    CreativeCommons,
+    Vr360,
+    Vr180,
+    ThreeD,
+    Hdr,
+    Location,
+    Purchased,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SortBy {
+    #[default]
+    Relevance,
+    UploadDate,
+    ViewCount,
+    Rating,
+}
+
+impl Feature {
+    /// The field of the feature in the filter message.
+    fn field(self) -> u64 {
+        match self {
+            Self::Hd => 4,
+            Self::Subtitles => 5,
+            Self::CreativeCommons => 6,
+            Self::ThreeD => 7,
+            Self::Live => 8,
+            Self::Purchased => 9,
+            Self::FourK => 14,
+            Self::Vr360 => 15,
+            Self::Location => 23,
+            Self::Hdr => 25,
+            Self::Vr180 => 26,
+        }
+    }
+}
+
+impl SearchFilter {
+    /// The `sp` query parameter of the search page, which is a base64 encoded protobuf message.
+    /// `None`, if no filter is set.
+    pub fn params(&self) -> Option<String> {
+        let mut filters = Vec::new();
+        if let Some(upload_date) = self.upload_date {
+            push_field(&mut filters, 1, upload_date as u64 + 1);
+        }
+        if let Some(result_type) = self.result_type {
+            push_field(&mut filters, 2, result_type as u64 + 1);
+        }
+        if let Some(duration) = self.duration {
+            push_field(&mut filters, 3, match duration {
+                VideoDuration::Short => 1,
+                VideoDuration::Long => 2,
+                VideoDuration::Medium => 3,
+            });
+        }
+        let mut features = self.features.iter().map(|feature| feature.field()).collect::<Vec<_>>();
+        features.sort_unstable();
+        features.dedup();
+        for field in features {
+            push_field(&mut filters, field, 1);
+        }
+
+        let mut message = Vec::new();
+        match self.sort_by {
+            SortBy::Relevance => {}
+            SortBy::Rating => push_field(&mut message, 1, 1),
+            SortBy::UploadDate => push_field(&mut message, 1, 2),
+            SortBy::ViewCount => push_field(&mut message, 1, 3),
+        }
+        if !filters.is_empty() {
+            push_varint(&mut message, 2 << 3 | 2);
+            push_varint(&mut message, filters.len() as u64);
+            message.extend(filters);
+        }
+
+        match message.is_empty() {
+            true => None,
+            false => Some(base64::engine::general_purpose::STANDARD.encode(message)),
+        }
+    }
+}
+
+fn push_field(message: &mut Vec<u8>, field: u64, value: u64) {
+    push_varint(message, field << 3);
+    push_varint(message, value);
+}
+
+fn push_varint(message: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        message.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    message.push(value as u8);
+}
+
+impl Search {
+    #[inline]
+    pub fn new(query: impl Into<String>) -> Self {
+        Self { query: query.into(), filter: SearchFilter::default() }
+    }
+
+    #[inline]
+    pub fn with_filter(mut self, filter: SearchFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    #[inline]
+    pub fn result_type(mut self, result_type: ResultType) -> Self {
+        self.filter.result_type = Some(result_type);
+        self
+    }
+
+    #[inline]
+    pub fn upload_date(mut self, upload_date: UploadDate) -> Self {
+        self.filter.upload_date = Some(upload_date);
+        self
+    }
+
+    #[inline]
+    pub fn duration(mut self, duration: VideoDuration) -> Self {
+        self.filter.duration = Some(duration);
+        self
+    }
+
+    #[inline]
+    pub fn feature(mut self, feature: Feature) -> Self {
+        self.filter.features.push(feature);
+        self
+    }
+
+    #[inline]
+    pub fn sort_by(mut self, sort_by: SortBy) -> Self {
+        self.filter.sort_by = sort_by;
+        self
+    }
+
+    /// Requests the first page of results, which also contains the spelling correction and the
+    /// estimated number of results.
+    ///
+    /// ### Errors
+    /// - When the request fails.
+    /// - When the response has an unexpected format.
+    pub async fn first_page(&self) -> crate::Result<SearchPage> {
+        self.first_page_with_client().await.map(|(page, _)| page)
+    }
+
+    /// Requests the first page, and returns the client of the proxy, which requested it, for the
+    /// following pages.
+    async fn first_page_with_client(&self) -> crate::Result<(SearchPage, Client)> {
+        let body = SearchReq::new(&self.query, self.filter.params());
+        let (response, client) = crate::proxy::send(|client| client
+            .post("https://www.youtube.com/youtubei/v1/search?prettyPrint=false")
+            .json(&body)
+        ).await?;
+        let response = response
+            .error_for_status()?
+            .json::<Value>()
+            .await?;
+        Ok((parse_search_page(&response)?, client))
+    }
+
+    /// Lazily requests all results, page by page. The stream ends after the first error.
+    pub fn results(&self) -> impl Stream<Item=crate::Result<SearchResult>> {
+        let search = self.clone();
+        futures::stream::once(async move {
+            let (page, client) = search.first_page_with_client().await?;
+            Ok::<_, crate::Error>(
+                futures::stream::iter(page.results.into_iter().map(Ok))
+                    .chain(continuation_stream("search", Some(client), page.continuation, parse_results))
+            )
+        })
+            .try_flatten()
+    }
+
+    /// Lazily requests the results, starting at a continuation token, which was previously saved
+    /// from [`SearchPage::continuation`].
+    pub fn results_from_continuation(continuation: String) -> impl Stream<Item=crate::Result<SearchResult>> {
+        continuation_stream("search", None, Some(continuation), parse_results)
+    }
+}
+
+fn parse_results(response: &Value) -> crate::Result<(Vec<SearchResult>, Option<String>)> {
+    let page = parse_search_page(response)?;
+    Ok((page.results, page.continuation))
+}
+
+/// Requests the videos of the first page of results for `query`. Live streams are skipped.
+///
+/// ### Errors
+/// - When the request fails.
+/// - When the response has an unexpected format.
+#[deprecated(
+    since = "0.7.0",
+    note = "use `Search::new(query).first_page()`, which also contains channels, playlists and live streams"
+)]
+pub async fn search_videos(query: &str) -> crate::Result<Vec<ChannelVideo>> {
+    let now = DateTime::<Utc>::from(std::time::SystemTime::now());
+    let page = Search::new(query).first_page().await?;
+
+    Ok(
+        page.results
+            .into_iter()
+            .filter_map(|result| match result {
+                SearchResult::Video(video) => Some(ChannelVideo {
+                    length_seconds: Some(video.length_seconds?),
+                    published: video.published_time_text.map(|text| PublishedTime {
+                        estimate: parse_relative_time(&text, now),
+                        text,
+                    }),
+                    video_id: video.video_id,
+                    title: video.title,
+                    thumbnails: video.thumbnails,
+                    author: video.author,
+                }),
+                _ => None,
+            })
+            .collect()
+    )
+}
+
+/// Requests the completions, which the search box suggests for `prefix`, in the order they are
+/// shown.
+///
+/// ### Errors
+/// - When the request fails.
+/// - When the response has an unexpected format.
+pub async fn suggestions(prefix: &str) -> crate::Result<Vec<String>> {
+    localized_suggestions(prefix, None, None).await
+}
+
+/// Like [`suggestions`], but for a specific language (`hl`, i.e. `de`) and region (`gl`, i.e. `AT`).
+///
+/// ### Errors
+/// - When the request fails.
+/// - When the response has an unexpected format.
+pub async fn localized_suggestions(prefix: &str, hl: Option<&str>, gl: Option<&str>) -> crate::Result<Vec<String>> {
+    let mut url = url::Url::parse_with_params(
+        "https://suggestqueries-clients6.youtube.com/complete/search",
+        &[("client", "youtube"), ("ds", "yt"), ("q", prefix)],
+    )?;
+    for (key, value) in [("hl", hl), ("gl", gl)] {
+        if let Some(value) = value {
+            url.query_pairs_mut().append_pair(key, value);
+        }
+    }
+
+    let (response, _) = crate::proxy::send(|client| client.get(url.as_str())).await?;
+    let jsonp = response
+        .error_for_status()?
+        .text()
+        .await?;
+    parse_suggestions(&jsonp)
+}
+
+/// Parses a response of the suggest endpoint, which is either plain json, or wrapped in a JSONP
+/// callback like `window.google.ac.h([...])`.
+///
+/// ### Errors
+/// - When the response has an unexpected format.
+pub fn parse_suggestions(response: &str) -> crate::Result<Vec<String>> {
+    let json = match (response.find('('), response.rfind(')')) {
+        (Some(start), Some(end)) if start < end && !response.trim_start().starts_with('[') => &response[start + 1..end],
+        _ => response,
+    };
+    let value = serde_json::from_str::<Value>(json)?;
+
+    value[1]
+        .as_array()
+        .ok_or_else(|| Error::UnexpectedResponse("the suggestions response contains no completions".into()))?
+        .iter()
+        .map(|suggestion| match suggestion {
+            Value::String(suggestion) => Some(suggestion.clone()),
+            suggestion => suggestion[0].as_str().map(str::to_owned),
+        })
+        .collect::<Option<_>>()
+        .ok_or_else(|| Error::UnexpectedResponse("the suggestions response contains an invalid completion".into()))
+}
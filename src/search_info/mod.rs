@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::helper::parse_search_page;
+use crate::video_info::player_response::video_details::Thumbnail;
+use crate::{ChannelIdBuf, IdBuf, PlaylistIdBuf};
+
+/// A single result of a [`Search`](crate::search::Search).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SearchResult {
+    Video(SearchVideo),
+    Channel(SearchChannel),
+    Playlist(SearchPlaylist),
+    /// A movie, which can be bought or rented on YouTube.
+    Movie(SearchVideo),
+}
+
+/// A video (or movie) in the search results.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchVideo {
+    pub video_id: IdBuf,
+    pub title: String,
+    /// `None` for live streams.
+    pub length_seconds: Option<u64>,
+    pub thumbnails: Vec<Thumbnail>,
+    pub author: String,
+    pub channel_id: Option<ChannelIdBuf>,
+    pub view_count: Option<u64>,
+    /// The relative time, like `2 days ago`.
+    pub published_time_text: Option<String>,
+    /// The part of the description, which matches the query.
+    pub description_snippet: String,
+    pub is_live: bool,
+}
+
+/// A channel in the search results.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchChannel {
+    pub channel_id: ChannelIdBuf,
+    pub title: String,
+    /// The handle, including the leading `@`.
+    pub handle: Option<String>,
+    /// `None`, if the subscriber count is hidden.
+    pub subscriber_count: Option<u64>,
+    pub thumbnails: Vec<Thumbnail>,
+    pub description_snippet: String,
+}
+
+/// A playlist in the search results.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchPlaylist {
+    pub playlist_id: PlaylistIdBuf,
+    pub title: String,
+    pub author: Option<String>,
+    pub channel_id: Option<ChannelIdBuf>,
+    pub video_count: Option<u64>,
+    pub thumbnails: Vec<Thumbnail>,
+}
+
+/// The spelling correction YouTube applied to, or suggests for, a query.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SearchCorrection {
+    pub corrected_query: String,
+    /// `true` for "Showing results for", where the results are the ones of the corrected query,
+    /// and `false` for "Did you mean", where the results are the ones of the original query.
+    pub is_auto_corrected: bool,
+}
+
+/// A single page of search results.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchPage {
+    pub results: Vec<SearchResult>,
+    /// Only available on the first page.
+    pub correction: Option<SearchCorrection>,
+    /// Only available on the first page.
+    pub estimated_results: Option<u64>,
+    /// The token of the next page.
+    pub continuation: Option<String>,
+}
+
+impl SearchPage {
+    /// Parses a raw response of `youtubei/v1/search`.
+    ///
+    /// ### Errors
+    /// - When the response is no valid json, or has an unexpected format.
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        parse_search_page(&serde_json::from_str(json)?)
+    }
+}
@@ -0,0 +1,35 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone};
+use serde::{Deserialize, Deserializer, Serializer};
+use serde::de::{Error, Unexpected};
+
+const DATE_FORMAT: &str = "%F";
+
+/// Deserializes either a full RFC 3339 timestamp, or a `yyyy-mm-dd` date. YouTube used to only
+/// send the date, in which case the timestamp is midnight UTC of that day.
+pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<FixedOffset>, D::Error>
+where
+    D: Deserializer<'de>
+{
+    let date = <&str>::deserialize(deserializer)?;
+    DateTime::parse_from_rfc3339(date)
+        .ok()
+        .or_else(|| {
+            let midnight = NaiveDate::parse_from_str(date, DATE_FORMAT)
+                .ok()?
+                .and_hms_opt(0, 0, 0)?;
+            FixedOffset::east_opt(0)?
+                .from_local_datetime(&midnight)
+                .single()
+        })
+        .ok_or_else(|| D::Error::invalid_value(
+            Unexpected::Str(date),
+            &"a RFC 3339 timestamp or a yyyy-mm-dd date string",
+        ))
+}
+
+pub(crate) fn serialize<S>(date: &DateTime<FixedOffset>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer
+{
+    serializer.serialize_str(&date.to_rfc3339())
+}
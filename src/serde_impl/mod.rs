@@ -3,5 +3,6 @@ pub(crate) mod range;
 pub(crate) mod unix_timestamp_micro_secs;
 pub(crate) mod unix_timestamp_secs;
 pub(crate) mod signature_cipher;
+pub(crate) mod text;
 #[cfg(feature = "microformat")]
-pub(crate) mod date_ymd;
+pub(crate) mod date_time;
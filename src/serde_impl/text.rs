@@ -0,0 +1,57 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A `{ simpleText }` or `{ runs: [{ text }] }` object.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Text {
+    simple_text: Option<String>,
+    #[serde(default)]
+    runs: Vec<Run>,
+}
+
+#[derive(Deserialize)]
+struct Run {
+    text: String,
+}
+
+pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<String, D::Error>
+    where
+        D: Deserializer<'de> {
+    let text = Text::deserialize(deserializer)?;
+    Ok(match text.simple_text {
+        Some(simple_text) => simple_text,
+        None => text.runs.into_iter().map(|run| run.text).collect(),
+    })
+}
+
+/// Serializes a text as `{ simpleText }`, which [`deserialize`] reads again.
+pub(crate) fn serialize<S>(text: &str, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer {
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct SimpleText<'a> { simple_text: &'a str }
+
+    SimpleText { simple_text: text }.serialize(serializer)
+}
+
+pub(crate) mod option {
+    use super::*;
+
+    /// Only used together with `skip_serializing_if = "Option::is_none"`, since `None` can't be
+    /// deserialized again.
+    pub(crate) fn serialize<S>(text: &Option<String>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer {
+        match text {
+            Some(text) => super::serialize(text, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+        where
+            D: Deserializer<'de> {
+        super::deserialize(deserializer).map(Some)
+    }
+}
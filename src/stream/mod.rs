@@ -1,17 +1,23 @@
 use std::ops::Range;
 #[cfg(feature = "download")]
+use std::ops::RangeInclusive;
+#[cfg(feature = "download")]
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "download")]
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use mime::Mime;
 use reqwest::Client;
 use serde_with::{DisplayFromStr, serde_as};
 #[cfg(feature = "download")]
+use std::io::SeekFrom;
+#[cfg(feature = "download")]
 use tokio::{
     fs::File,
-    io::AsyncWriteExt,
+    io::{AsyncSeekExt, AsyncWriteExt},
 };
 #[cfg(feature = "callback")]
 use tokio::sync::mpsc::error::TrySendError;
@@ -24,8 +30,11 @@ use callback::{InternalSender, InternalSignal};
 use callback::Callback;
 
 #[cfg(feature = "download")]
-use crate::{Error, Result};
+use crate::{Error, Result, VideoFetcher};
+#[cfg(feature = "download")]
+use crate::video_info::player_response::streaming_data::url_expiry;
 use crate::{
+    proxy::{PooledProxy, ProxyEntry},
     video_info::player_response::streaming_data::{
         AudioQuality, ColorInfo, FormatType, ProjectionType,
         Quality, QualityLabel, RawFormat, SignatureCipher,
@@ -35,6 +44,11 @@ use crate::{
 
 #[cfg(feature = "callback")]
 pub mod callback;
+#[cfg(feature = "download")]
+pub mod segment_index;
+
+#[cfg(feature = "download")]
+pub use segment_index::{SegmentIndex, SegmentReference};
 
 // todo:
 //  there are different types of streams: video, audio, and video + audio
@@ -43,6 +57,15 @@ pub mod callback;
 #[cfg(all(not(feature = "callback"), feature = "download"))]
 type InternalSender = ();
 
+/// Requests a new url for a [`Stream`], whose url expired, or was rejected with `403 Forbidden`.
+/// See [`Stream::with_url_refresher`].
+#[cfg(feature = "download")]
+pub type UrlRefresher = Arc<dyn Fn(&Stream) -> futures::future::BoxFuture<'static, Result<url::Url>> + Send + Sync>;
+
+/// How often a single download requests a new url, before it gives up.
+#[cfg(feature = "download")]
+const MAX_URL_REFRESHES: usize = 3;
+
 /// A downloadable video Stream, that contains all the important information.
 #[serde_as]
 #[derive(Clone, derivative::Derivative, serde::Deserialize, serde::Serialize)]
@@ -64,6 +87,9 @@ pub struct Stream {
     pub color_info: Option<ColorInfo>,
     #[derivative(PartialEq(compare_with = "atomic_u64_is_eq"))]
     content_length: Arc<AtomicU64>,
+    /// When the url of the stream expires.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
     pub fps: u8,
     pub height: Option<u64>,
     pub high_replication: Option<bool>,
@@ -83,12 +109,28 @@ pub struct Stream {
     #[serde(skip)]
     #[derivative(Debug = "ignore", PartialEq = "ignore")]
     client: Client,
+    #[serde(skip)]
+    #[derivative(PartialEq = "ignore")]
+    proxy: Option<PooledProxy>,
+    #[cfg(feature = "download")]
+    #[serde(skip)]
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    url_refresher: Option<UrlRefresher>,
 }
 
 
 impl Stream {
     // maybe deserialize RawFormat seeded with client and VideoDetails
-    pub(crate) fn from_raw_format(raw_format: RawFormat, client: Client, video_details: Arc<VideoDetails>) -> Self {
+    pub(crate) fn from_raw_format(
+        raw_format: RawFormat,
+        client: Client,
+        proxy: Option<PooledProxy>,
+        expires_at: Option<DateTime<Utc>>,
+        video_details: Arc<VideoDetails>,
+    ) -> Self {
+        // the url may expire earlier than the rest of the streaming data
+        let expires_at = earliest(expires_at, raw_format.signature_cipher.expires_at());
+
         Self {
             is_progressive: is_progressive(&raw_format.mime_type.codecs),
             includes_video_track: includes_video_track(&raw_format.mime_type.codecs, &raw_format.mime_type.mime),
@@ -104,6 +146,7 @@ impl Stream {
             bitrate: raw_format.bitrate,
             color_info: raw_format.color_info,
             content_length: Arc::new(AtomicU64::new(raw_format.content_length.unwrap_or(0))),
+            expires_at,
             fps: raw_format.fps,
             height: raw_format.height,
             high_replication: raw_format.high_replication,
@@ -119,9 +162,27 @@ impl Stream {
             signature_cipher: raw_format.signature_cipher,
             width: raw_format.width,
             client,
+            proxy,
             video_details,
+            #[cfg(feature = "download")]
+            url_refresher: None,
         }
     }
+
+    /// The proxy of the [`ProxyPool`](crate::ProxyPool), which fetched the stream url. The stream
+    /// is always downloaded through this proxy, since the url only works for its IP.
+    #[inline]
+    pub fn proxy(&self) -> Option<&ProxyEntry> {
+        self.proxy.as_ref().map(PooledProxy::entry)
+    }
+
+    /// Whether the url of the stream has expired, either according to [`Stream::expires_at`], or
+    /// to the url itself. Expired streams can still be downloaded, since downloads request a new
+    /// url, but [`SignatureCipher::url`] itself can't be used anymore.
+    #[inline]
+    pub fn is_expired(&self) -> bool {
+        has_passed(earliest(self.expires_at, self.signature_cipher.expires_at()))
+    }
 }
 
 // todo: download in ranges
@@ -129,19 +190,34 @@ impl Stream {
 
 #[cfg(feature = "download")]
 impl Stream {
+    /// Replaces the way a new url is requested, when the url of the stream expired, or was
+    /// rejected with `403 Forbidden` during a download.
+    ///
+    /// By default, the video is fetched and descrambled again with the client (and proxy) of the
+    /// stream, and the url of the stream with the same itag is used.
+    #[inline]
+    pub fn with_url_refresher<F>(mut self, url_refresher: F) -> Self
+    where
+        F: Fn(&Stream) -> futures::future::BoxFuture<'static, Result<url::Url>> + Send + Sync + 'static,
+    {
+        self.url_refresher = Some(Arc::new(url_refresher));
+        self
+    }
+
     /// The content length of the video.
     /// If the content length was not included in the [`RawFormat`], this method will make a `HEAD`
     /// request, to try to figure it out.
     ///
     /// ### Errors:
     /// - When the content length was not included in the [`RawFormat`], and the request fails.
+    /// - When the url of the stream expired, and requesting a new one fails.
     #[inline]
     pub async fn content_length(&self) -> Result<u64> {
         let cl = self.content_length.load(Ordering::SeqCst);
         if cl != 0 { return Ok(cl); }
 
         self.client
-            .head(self.signature_cipher.url.as_str())
+            .head(self.valid_url().await?.as_str())
             .send()
             .await?
             .error_for_status()?
@@ -203,13 +279,75 @@ impl Stream {
         Ok(())
     }
 
+    /// Continues an interrupted download to the provided file path, by appending the missing
+    /// bytes to the file. If the file does not exist yet, the whole stream is downloaded.
+    ///
+    /// Unlike the other downloads, the file is kept, if the download fails, so it can be resumed
+    /// again.
+    pub async fn resume_download_to<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        log::debug!("resume downloading {} to {:?}", self.video_details.video_id, path.as_ref());
+        let mut file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .await?;
+        file.seek(SeekFrom::End(0)).await?;
+
+        let mut url = self.signature_cipher.url.clone();
+        self.download_resuming(&mut url, &mut file, 0, None, &None).await
+    }
+
+    /// Downloads only the fragments of the [`Stream`], which are needed to play `time`, to the
+    /// provided file path, and returns the time range they cover.
+    ///
+    /// This is only possible for DASH streams in the MP4 container, which consist of fragments of
+    /// a few seconds each. The downloaded part starts and ends at fragment boundaries, so it's
+    /// usually a bit longer than `time`.
+    ///
+    /// ### Errors
+    /// - When the stream is no DASH MP4 stream ([`Error::PartialDownloadUnsupported`]).
+    /// - When `time` is not part of the stream ([`Error::TimeRangeNotInStream`]).
+    /// - When a request fails.
+    /// - When the url of the stream expired, and requesting a new one fails.
+    pub async fn download_range_to<P: AsRef<Path>>(&self, path: P, time: Range<Duration>) -> Result<Range<Duration>> {
+        let (init_range, index_range) = match (&self.init_range, &self.index_range) {
+            (Some(init_range), Some(index_range))
+            if self.mime.subtype() == mime::MP4 && init_range.end < index_range.start => (init_range, index_range),
+            _ => return Err(Error::PartialDownloadUnsupported),
+        };
+
+        // the initialization segment is directly followed by the segment index
+        let mut url = self.signature_cipher.url.clone();
+        let header = self
+            .get_range_refreshing(&mut url, init_range.start..=index_range.end)
+            .await?
+            .bytes()
+            .await?;
+        let index_offset = (index_range.start - init_range.start) as usize;
+        let init = header
+            .get(..index_offset)
+            .ok_or_else(|| Error::UnexpectedResponse("the stream header is shorter than its index range".into()))?;
+        let index = SegmentIndex::parse(&header[index_offset..])?;
+        let (bytes, covered) = index
+            .locate(index_range.start, time.clone())
+            .ok_or(Error::TimeRangeNotInStream(time))?;
+
+        log::debug!("download {:?} ({:?}) of {}", bytes, covered, self.video_details.video_id);
+        let mut file = File::create(&path).await?;
+        file.write_all(init).await?;
+        self.download_resuming(&mut url, &mut file, init.len() as u64, Some(bytes), &None).await?;
+        Ok(covered)
+    }
+
     #[allow(unused_mut, clippy::let_and_return)]
     async fn internal_download_to<P: AsRef<Path>>(&self, path: P, channel: Option<InternalSender>) -> Result<PathBuf> {
         log::trace!("download_to: {:?}", path.as_ref());
         log::debug!("start downloading {}", self.video_details.video_id);
         let mut file = File::create(&path).await?;
 
-        let result = match self.download_full(&self.signature_cipher.url, &mut file, &channel, 0).await {
+        let mut url = self.signature_cipher.url.clone();
+        let result = match self.download_resuming(&mut url, &mut file, 0, None, &channel).await {
             Ok(_) => {
                 log::info!(
                     "downloaded {} successfully to {:?}",
@@ -221,8 +359,11 @@ impl Stream {
             Err(Error::Request(e)) if matches!(e.status(), Some(reqwest::StatusCode::NOT_FOUND)) => {
                 log::error!("failed to download {}: {:?}", self.video_details.video_id, e);
                 log::info!("try to download {} using sequenced download", self.video_details.video_id);
-                // Some adaptive streams need to be requested with sequence numbers
-                self.download_full_seq(&mut file, &channel)
+                // Some adaptive streams need to be requested with sequence numbers, which starts
+                // from the beginning again
+                file.set_len(0).await?;
+                file.seek(SeekFrom::Start(0)).await?;
+                self.download_full_seq(&url, &mut file, &channel)
                     .await
                     .map_err(|e| {
                         log::error!(
@@ -248,7 +389,96 @@ impl Stream {
         result
     }
 
-    async fn download_full_seq(&self, file: &mut File, channel: &Option<InternalSender>) -> Result<()> {
+    /// Downloads `bytes` of the stream (or the whole stream) to `file`, where they start at
+    /// `file_start`. If the url expires or is rejected with `403 Forbidden` in the middle of the
+    /// download, a new url is requested, and the download continues after the bytes, that were
+    /// already written to `file`.
+    ///
+    /// `url` is replaced with the new url, but [`SignatureCipher::url`] stays the same.
+    async fn download_resuming(
+        &self,
+        url: &mut url::Url,
+        file: &mut File,
+        file_start: u64,
+        bytes: Option<Range<u64>>,
+        channel: &Option<InternalSender>,
+    ) -> Result<()> {
+        if self.is_expired() || has_passed(url_expiry(url)) {
+            *url = self.refreshed_url().await?;
+        }
+
+        let mut refreshes = 0;
+        loop {
+            file.flush().await?;
+            let written = file.metadata().await?.len().saturating_sub(file_start);
+            let result = match (written, &bytes) {
+                (0, None) => self.download_full(url, file, channel, 0).await,
+                (written, None) => self.download_from(url, written, None, file, channel).await,
+                (written, Some(bytes)) => self.download_from(url, bytes.start + written, Some(bytes.end), file, channel).await,
+            };
+
+            match result {
+                Err(err) if refreshes < MAX_URL_REFRESHES && (is_forbidden(&err) || has_passed(url_expiry(url))) => {
+                    log::warn!(
+                        "the url of {} became invalid after {} bytes: {:?}",
+                        self.video_details.video_id, written, err
+                    );
+                    refreshes += 1;
+                    *url = self.refreshed_url().await?;
+                }
+                // the written bytes may still be buffered by tokio
+                Ok(_) => return Ok(file.flush().await?),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Requests `bytes` of the stream, and retries with a new url, if `url` expired or was
+    /// rejected with `403 Forbidden`.
+    async fn get_range_refreshing(&self, url: &mut url::Url, bytes: RangeInclusive<u64>) -> Result<reqwest::Response> {
+        if self.is_expired() || has_passed(url_expiry(url)) {
+            *url = self.refreshed_url().await?;
+        }
+        match self.get_range(url, bytes.clone()).await {
+            Err(err) if is_forbidden(&err) => {
+                log::warn!("the url of {} became invalid: {:?}", self.video_details.video_id, err);
+                *url = self.refreshed_url().await?;
+                self.get_range(url, bytes).await
+            }
+            result => result,
+        }
+    }
+
+    /// The url of the stream, or a new one, if it expired.
+    async fn valid_url(&self) -> Result<url::Url> {
+        match self.is_expired() {
+            true => self.refreshed_url().await,
+            false => Ok(self.signature_cipher.url.clone()),
+        }
+    }
+
+    /// Fetches and descrambles the video again, and returns the new url of the stream with the
+    /// same itag. The new url is requested with the same client, and therefore the same proxy.
+    ///
+    /// If the stream has a [`UrlRefresher`], it's used instead.
+    async fn refreshed_url(&self) -> Result<url::Url> {
+        log::info!("request a new url for itag {} of {}", self.itag, self.video_details.video_id);
+        if let Some(url_refresher) = &self.url_refresher {
+            return url_refresher(self).await;
+        }
+        VideoFetcher::from_id_with_client(self.video_details.video_id.clone(), self.client.clone())
+            .fetch()
+            .await?
+            .descramble()
+            .await?
+            .into_streams()
+            .into_iter()
+            .find(|stream| stream.itag == self.itag)
+            .map(|stream| stream.signature_cipher.url)
+            .ok_or(Error::StreamUnavailable { itag: self.itag })
+    }
+
+    async fn download_full_seq(&self, url: &url::Url, file: &mut File, channel: &Option<InternalSender>) -> Result<()> {
         // fixme: this implementation is **not** tested yet!
         // To test it, I would need an url of a video, which does require sequenced downloading.
         log::warn!(
@@ -258,10 +488,10 @@ impl Stream {
             id: {}\n\
             url: {}",
             self.video_details.video_id,
-            self.signature_cipher.url.as_str()
+            url.as_str()
         );
 
-        let mut url = self.signature_cipher.url.clone();
+        let mut url = url.clone();
         let base_query = url
             .query()
             .map(str::to_owned)
@@ -281,7 +511,7 @@ impl Stream {
             count = self.download_full(&url, file, channel, count).await?;
         }
 
-        Ok(())
+        Ok(file.flush().await?)
     }
 
     #[inline]
@@ -296,6 +526,40 @@ impl Stream {
         self.write_stream_to_file(res.bytes_stream(), file, channel, count).await
     }
 
+    /// Downloads the bytes from `start` to `end` (or the end of the stream) to `file`.
+    #[inline]
+    async fn download_from(
+        &self,
+        url: &url::Url,
+        start: u64,
+        end: Option<u64>,
+        file: &mut File,
+        channel: &Option<InternalSender>,
+    ) -> Result<usize> {
+        let range = match end {
+            Some(end) if start >= end => return Ok(start as usize),
+            Some(end) => format!("bytes={start}-{}", end - 1),
+            None => format!("bytes={start}-"),
+        };
+        log::trace!("get: {} ({})", url.as_str(), range);
+        let res = self.client
+            .get(url.as_str())
+            .header(reqwest::header::RANGE, range)
+            .send()
+            .await?;
+        // the file is already complete
+        if end.is_none() && res.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            return Ok(start as usize);
+        }
+        let res = res.error_for_status()?;
+        if res.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            return Err(Error::UnexpectedResponse(
+                "the server did not respect the range of a resumed download".into()
+            ));
+        }
+        self.write_stream_to_file(res.bytes_stream(), file, channel, start as usize).await
+    }
+
     #[inline]
     async fn get(&self, url: &url::Url) -> Result<reqwest::Response> {
         log::trace!("get: {}", url.as_str());
@@ -308,6 +572,19 @@ impl Stream {
         )
     }
 
+    #[inline]
+    async fn get_range(&self, url: &url::Url, bytes: RangeInclusive<u64>) -> Result<reqwest::Response> {
+        log::trace!("get: {} ({:?})", url.as_str(), bytes);
+        Ok(
+            self.client
+                .get(url.as_str())
+                .header(reqwest::header::RANGE, format!("bytes={}-{}", bytes.start(), bytes.end()))
+                .send()
+                .await?
+                .error_for_status()?
+        )
+    }
+
     #[inline]
     #[allow(unused_variables, unused_mut)]
     async fn write_stream_to_file(
@@ -417,6 +694,21 @@ impl Stream {
     }
 }
 
+/// The earlier one of two expiry times, if any.
+fn earliest(lhs: Option<DateTime<Utc>>, rhs: Option<DateTime<Utc>>) -> Option<DateTime<Utc>> {
+    lhs.into_iter().chain(rhs).min()
+}
+
+#[inline]
+fn has_passed(time: Option<DateTime<Utc>>) -> bool {
+    time.is_some_and(|time| time <= DateTime::<Utc>::from(std::time::SystemTime::now()))
+}
+
+#[cfg(feature = "download")]
+fn is_forbidden(err: &Error) -> bool {
+    matches!(err, Error::Request(err) if err.status() == Some(reqwest::StatusCode::FORBIDDEN))
+}
+
 #[inline]
 fn is_adaptive(codecs: &[String]) -> bool {
     codecs.len() % 2 != 0
@@ -0,0 +1,139 @@
+use std::ops::Range;
+use std::time::Duration;
+
+use crate::{Error, Result};
+
+/// The segment index (`sidx` box) of a fragmented MP4 stream, which maps the fragments of the
+/// stream to their byte ranges.
+///
+/// It's located at the [`Stream::index_range`](crate::Stream::index_range) of DASH streams.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SegmentIndex {
+    /// The number of ticks per second.
+    pub timescale: u32,
+    /// The start of the first fragment in ticks.
+    pub earliest_presentation_time: u64,
+    /// The distance between the end of the `sidx` box and the first fragment in bytes.
+    pub first_offset: u64,
+    /// The length of the `sidx` box in bytes.
+    pub box_size: u64,
+    pub references: Vec<SegmentReference>,
+}
+
+/// A single fragment of a [`SegmentIndex`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SegmentReference {
+    /// The size of the fragment in bytes.
+    pub size: u32,
+    /// The duration of the fragment in ticks.
+    pub duration: u32,
+}
+
+impl SegmentIndex {
+    /// Parses a `sidx` box, which has to start at the beginning of `bytes`.
+    ///
+    /// ### Errors
+    /// - When `bytes` does not start with a valid `sidx` box.
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let invalid = || Error::UnexpectedResponse("the stream contains no valid segment index".into());
+        let mut reader = Reader { bytes, position: 0 };
+
+        let box_size = reader.u32().ok_or_else(invalid)? as u64;
+        if reader.take(4).ok_or_else(invalid)? != b"sidx" || box_size as usize > bytes.len() {
+            return Err(invalid());
+        }
+        let version = reader.take(4).ok_or_else(invalid)?[0];
+        let _reference_id = reader.u32().ok_or_else(invalid)?;
+        let timescale = reader.u32().ok_or_else(invalid)?;
+        let (earliest_presentation_time, first_offset) = match version {
+            0 => (reader.u32().map(u64::from), reader.u32().map(u64::from)),
+            _ => (reader.u64(), reader.u64()),
+        };
+        let _reserved = reader.take(2).ok_or_else(invalid)?;
+        let reference_count = reader.take(2).ok_or_else(invalid)?;
+        let reference_count = u16::from_be_bytes([reference_count[0], reference_count[1]]);
+
+        let references = (0..reference_count)
+            .map(|_| {
+                let size = reader.u32()?;
+                let duration = reader.u32()?;
+                let _sap = reader.u32()?;
+                // the highest bit marks references to other segment indexes, which YouTube doesn't use
+                match size >> 31 {
+                    0 => Some(SegmentReference { size, duration }),
+                    _ => None,
+                }
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+
+        if timescale == 0 {
+            return Err(invalid());
+        }
+        Ok(Self {
+            timescale,
+            earliest_presentation_time: earliest_presentation_time.ok_or_else(invalid)?,
+            first_offset: first_offset.ok_or_else(invalid)?,
+            box_size,
+            references,
+        })
+    }
+
+    /// Finds the fragments, which are needed to play `time`.
+    ///
+    /// `index_start` is the offset of the `sidx` box in the stream. Returns the byte range of the
+    /// fragments in the stream, and the time range they cover, which starts and ends at fragment
+    /// boundaries. `None`, if `time` is not part of the stream, or the offsets of the index are
+    /// out of range.
+    pub fn locate(&self, index_start: u64, time: Range<Duration>) -> Option<(Range<u64>, Range<Duration>)> {
+        // the offsets and times are taken straight from the response, so they must not overflow
+        let mut offset = index_start.checked_add(self.box_size)?.checked_add(self.first_offset)?;
+        let mut ticks = self.earliest_presentation_time;
+        let mut located: Option<(Range<u64>, Range<Duration>)> = None;
+
+        for reference in &self.references {
+            let bytes = offset..offset.checked_add(reference.size as u64)?;
+            let end_ticks = ticks.checked_add(reference.duration as u64)?;
+            let span = self.duration(ticks)..self.duration(end_ticks);
+            offset = bytes.end;
+            ticks = end_ticks;
+
+            if span.end <= time.start || span.start >= time.end {
+                continue;
+            }
+            located = Some(match located {
+                Some((located_bytes, located_span)) => (located_bytes.start..bytes.end, located_span.start..span.end),
+                None => (bytes, span),
+            });
+        }
+
+        located
+    }
+
+    #[inline]
+    fn duration(&self, ticks: u64) -> Duration {
+        Duration::from_secs(ticks / self.timescale as u64)
+            + Duration::from_nanos((ticks % self.timescale as u64) * 1_000_000_000 / self.timescale as u64)
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let bytes = self.bytes.get(self.position..self.position + n)?;
+        self.position += n;
+        Some(bytes)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4).map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        Some((self.u32()? as u64) << 32 | self.u32()? as u64)
+    }
+}
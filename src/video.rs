@@ -3,7 +3,16 @@ use std::sync::Arc;
 use derive_more::Display;
 
 use crate::{Id, Stream, VideoInfo};
-use crate::video_info::player_response::video_details::VideoDetails;
+use crate::comment_info::{Comment, CommentSort};
+use crate::video_info::chapter::Chapter;
+use crate::video_info::heatmap::Heatmap;
+use crate::video_info::metadata::VideoMetadata;
+use crate::video_info::player_response::{
+    endscreen::EndscreenElement,
+    storyboard::Storyboard,
+    video_details::VideoDetails,
+};
+use crate::video_info::related_video::RelatedVideo;
 
 /// A YouTube downloader, which allows you to download all available formats and qualities of a 
 /// YouTube video. 
@@ -167,6 +176,70 @@ impl Video {
         self.video_info.is_age_restricted
     }
 
+    /// Whether the video can be watched in `country`.
+    /// For more information have a look at [`VideoInfo::is_available_in`].
+    #[inline]
+    #[cfg(feature = "microformat")]
+    pub fn is_available_in(&self, country: &str) -> bool {
+        self.video_info.is_available_in(country)
+    }
+
+    /// The [`VideoMetadata`] of the video.
+    /// For more information have a look at [`VideoInfo::metadata`].
+    #[inline]
+    pub fn metadata(&self) -> VideoMetadata {
+        self.video_info.metadata()
+    }
+
+    /// The [`Chapter`]s of the video.
+    /// For more information have a look at [`VideoInfo::chapters`].
+    #[inline]
+    pub fn chapters(&self) -> Vec<Chapter> {
+        self.video_info.chapters()
+    }
+
+    /// The "most replayed" [`Heatmap`] of the video.
+    /// For more information have a look at [`VideoInfo::heatmap`].
+    #[inline]
+    pub fn heatmap(&self) -> Option<Heatmap> {
+        self.video_info.heatmap()
+    }
+
+    /// The seek preview [`Storyboard`] of the video.
+    /// For more information have a look at [`VideoInfo::storyboard`].
+    #[inline]
+    pub fn storyboard(&self) -> crate::Result<Option<Storyboard>> {
+        self.video_info.storyboard()
+    }
+
+    /// Lazily requests the comments of the video, page by page, in the given order.
+    /// The stream is empty, if the comments are turned off.
+    ///
+    /// The replies of each [`Comment`] can be requested with [`Comment::replies`].
+    ///
+    /// ### Errors
+    /// - When a page can't be requested, or has an unexpected format.
+    /// - When `sort` is [`CommentSort::Newest`], but the comment section can't be sorted.
+    pub fn comments(&self, sort: CommentSort) -> impl futures::Stream<Item=crate::Result<Comment>> {
+        let continuation = self.video_info.initial_data
+            .as_ref()
+            .and_then(crate::helper::comments_continuation);
+        crate::comment_info::comments(continuation, sort)
+    }
+
+    /// Lazily requests the related videos, page by page.
+    /// For more information have a look at [`VideoInfo::related_videos`].
+    #[inline]
+    pub fn related(&self) -> impl futures::Stream<Item=crate::Result<RelatedVideo>> {
+        self.video_info.related_videos()
+    }
+
+    /// The elements shown at the end of the video.
+    #[inline]
+    pub fn endscreen(&self) -> &[EndscreenElement] {
+        self.video_info.endscreen()
+    }
+
     /// The [`Stream`] with the best quality.
     /// This stream is guaranteed to contain both a video as well as an audio track. 
     #[inline]
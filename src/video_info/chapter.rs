@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use crate::video_info::player_response::video_details::Thumbnail;
+
+/// A single chapter of a video.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct Chapter {
+    pub title: String,
+    /// The start of the chapter in milliseconds.
+    pub start_ms: u64,
+    /// The end of the chapter in milliseconds (which is the start of the next chapter, or the end
+    /// of the video).
+    pub end_ms: u64,
+    /// Only available if the chapters were extracted from `ytInitialData`.
+    pub thumbnails: Vec<Thumbnail>,
+}
+
+impl Chapter {
+    /// The duration of the chapter in milliseconds.
+    #[inline]
+    pub fn duration_ms(&self) -> u64 {
+        self.end_ms.saturating_sub(self.start_ms)
+    }
+}
+
+/// Sets the end of each chapter to the start of the following one, and the end of the last chapter
+/// to the end of the video.
+pub(crate) fn close_chapters(chapters: &mut [Chapter], length_ms: u64) {
+    let starts = chapters
+        .iter()
+        .skip(1)
+        .map(|chapter| chapter.start_ms)
+        .chain(core::iter::once(length_ms))
+        .collect::<Vec<_>>();
+
+    for (chapter, end_ms) in chapters.iter_mut().zip(starts) {
+        chapter.end_ms = end_ms.max(chapter.start_ms);
+    }
+}
@@ -0,0 +1,73 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+/// The "most replayed" graph, which is shown above the progress bar of popular videos.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Heatmap {
+    /// The markers, ordered by their start.
+    pub markers: Vec<HeatMarker>,
+}
+
+/// A single segment of a [`Heatmap`].
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+pub struct HeatMarker {
+    pub start_ms: u64,
+    pub duration_ms: u64,
+    /// How often this segment was replayed, relative to the most replayed segment (`0.0..=1.0`).
+    pub intensity: f64,
+}
+
+impl HeatMarker {
+    /// The end of the segment in milliseconds, which saturates at `u64::MAX`.
+    #[inline]
+    pub fn end_ms(&self) -> u64 {
+        self.start_ms.saturating_add(self.duration_ms)
+    }
+}
+
+impl Heatmap {
+    pub(crate) fn new(mut markers: Vec<HeatMarker>) -> Option<Self> {
+        if markers.is_empty() {
+            return None;
+        }
+        markers.sort_by_key(|marker| marker.start_ms);
+        Some(Self { markers })
+    }
+
+    /// The time ranges (in milliseconds) of the `n` most replayed peaks, starting with the most
+    /// replayed one.
+    ///
+    /// A peak is a segment, which was replayed more often than both of its neighbours. Neighbouring
+    /// segments with the same intensity are merged into a single peak.
+    pub fn top_peaks(&self, n: usize) -> Vec<Range<u64>> {
+        // group neighbouring markers with the same intensity
+        let mut plateaus: Vec<(Range<u64>, f64)> = Vec::new();
+        for marker in &self.markers {
+            match plateaus.last_mut() {
+                Some((range, intensity)) if *intensity == marker.intensity => range.end = marker.end_ms(),
+                _ => plateaus.push((marker.start_ms..marker.end_ms(), marker.intensity)),
+            }
+        }
+
+        let mut peaks = plateaus
+            .iter()
+            .enumerate()
+            .filter(|&(i, (_, intensity))| {
+                let lower = |neighbour: Option<&(Range<u64>, f64)>| {
+                    neighbour.is_none_or(|(_, other)| other < intensity)
+                };
+                lower(i.checked_sub(1).and_then(|i| plateaus.get(i))) && lower(plateaus.get(i + 1))
+            })
+            .map(|(_, peak)| peak.clone())
+            .collect::<Vec<_>>();
+
+        // the sort is stable, so peaks with the same intensity stay in chronological order
+        peaks.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+        peaks
+            .into_iter()
+            .take(n)
+            .map(|(range, _)| range)
+            .collect()
+    }
+}
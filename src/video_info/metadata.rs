@@ -0,0 +1,121 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::video_info::player_response::video_details::TextRun;
+use crate::VideoInfo;
+
+/// The metadata of a video, merged from the player response and the watch page's `ytInitialData`.
+///
+/// Some of the information is only part of the microformat, which is deserialized with the
+/// `microformat` feature. Without it, these fields are `None`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct VideoMetadata {
+    pub title: String,
+    /// The full description, with links (urls, timestamps, hashtags, ...) as separate runs.
+    pub description: Vec<TextRun>,
+    pub author: String,
+    pub channel_id: String,
+    pub view_count: u64,
+    /// `None`, if the likes are hidden, or could not be found.
+    pub like_count: Option<u64>,
+    pub length_seconds: u64,
+    pub keywords: Vec<String>,
+    /// The hashtags of the video, including the leading `#`.
+    pub hashtags: Vec<String>,
+    /// Requires the `microformat` feature.
+    pub publish_date: Option<DateTime<FixedOffset>>,
+    /// Requires the `microformat` feature.
+    pub upload_date: Option<DateTime<FixedOffset>>,
+    /// Taken from the microformat, or the rows below the description.
+    pub category: Option<String>,
+    /// The license, if it is shown below the description (i.e. `Creative Commons`).
+    pub license: Option<String>,
+    /// Requires the `microformat` feature.
+    pub is_family_safe: Option<bool>,
+    /// Requires the `microformat` feature.
+    pub is_unlisted: Option<bool>,
+    /// Requires the `microformat` feature.
+    pub is_short: Option<bool>,
+    pub is_live_content: bool,
+    pub is_premiere: bool,
+    pub is_members_only: bool,
+}
+
+impl VideoMetadata {
+    /// The description without any formatting.
+    #[inline]
+    pub fn plain_description(&self) -> String {
+        self.description
+            .iter()
+            .map(|run| run.text.as_str())
+            .collect()
+    }
+
+    pub(crate) fn from_video_info(video_info: &VideoInfo) -> Self {
+        let video_details = &video_info.player_response.video_details;
+        let initial_data = video_info.initial_data.as_ref().unwrap_or(&Value::Null);
+        let primary_info = crate::helper::watch_renderer(initial_data, "videoPrimaryInfoRenderer")
+            .unwrap_or(&Value::Null);
+        let secondary_info = crate::helper::watch_renderer(initial_data, "videoSecondaryInfoRenderer")
+            .unwrap_or(&Value::Null);
+
+        let description = crate::helper::parse_full_description(secondary_info)
+            .unwrap_or_else(|| vec![TextRun { text: video_details.short_description.clone(), url: None }]);
+
+        // the hashtags above the title are not necessarily part of the description
+        let mut hashtags = Vec::<String>::new();
+        let super_title = crate::helper::text(&primary_info["superTitleLink"]).unwrap_or_default();
+        let description_text = description.iter().map(|run| run.text.as_str()).collect::<String>();
+        for hashtag in crate::helper::parse_hashtags(&super_title)
+            .into_iter()
+            .chain(crate::helper::parse_hashtags(&description_text)) {
+            if !hashtags.iter().any(|h| h.eq_ignore_ascii_case(&hashtag)) {
+                hashtags.push(hashtag);
+            }
+        }
+
+        let date_text = crate::helper::text(&primary_info["dateText"]).unwrap_or_default();
+        let is_members_only = primary_info["badges"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .any(|badge| badge["metadataBadgeRenderer"]["style"].as_str() == Some("BADGE_STYLE_TYPE_MEMBERS_ONLY"));
+
+        #[allow(unused_mut)]
+        let mut metadata = Self {
+            title: video_details.title.clone(),
+            description,
+            author: video_details.author.clone(),
+            channel_id: video_details.channel_id.clone(),
+            view_count: video_details.view_count,
+            like_count: crate::helper::parse_like_count(primary_info),
+            length_seconds: video_details.length_seconds,
+            keywords: video_details.key_words.clone(),
+            hashtags,
+            publish_date: None,
+            upload_date: None,
+            category: crate::helper::parse_metadata_row(secondary_info, "Category"),
+            license: crate::helper::parse_metadata_row(secondary_info, "License"),
+            is_family_safe: None,
+            is_unlisted: None,
+            is_short: None,
+            is_live_content: video_details.is_live_content,
+            is_premiere: date_text.starts_with("Premiere"),
+            is_members_only,
+        };
+
+        #[cfg(feature = "microformat")]
+        if let Some(ref microformat) = video_info.player_response.microformat {
+            let renderer = &microformat.player_microformat_renderer;
+            metadata.publish_date = Some(renderer.publish_date);
+            metadata.upload_date = Some(renderer.upload_date);
+            metadata.category = Some(renderer.category.clone());
+            metadata.is_family_safe = renderer.is_family_safe;
+            metadata.is_unlisted = Some(renderer.is_unlisted);
+            metadata.is_short = renderer.is_shorts_eligible;
+        }
+
+        metadata
+    }
+}
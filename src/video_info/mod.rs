@@ -3,9 +3,19 @@
 use serde::{Deserialize, Serialize};
 use serde_with::{json::JsonString, serde_as};
 
-use player_response::PlayerResponse;
+use futures::{Stream, StreamExt};
+
+use chapter::Chapter;
+use heatmap::Heatmap;
+use metadata::VideoMetadata;
+use player_response::{endscreen::EndscreenElement, PlayerResponse, storyboard::Storyboard};
+use related_video::RelatedVideo;
 
 pub mod player_response;
+pub mod chapter;
+pub mod heatmap;
+pub mod metadata;
+pub mod related_video;
 
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -17,4 +27,108 @@ pub struct VideoInfo {
 
     #[serde(skip)]
     pub is_age_restricted: bool,
+    /// The `ytInitialData` object of the watch page, if it could be extracted.
+    #[serde(skip)]
+    pub initial_data: Option<serde_json::Value>,
+}
+
+impl VideoInfo {
+    /// The chapters of the video.
+    ///
+    /// The chapters are taken from the macro-markers in `ytInitialData` (either the player bar, or
+    /// the chapters engagement panel). If there are none, the timestamp lines in the description
+    /// are used instead. Returns an empty `Vec`, if the video has no chapters.
+    pub fn chapters(&self) -> Vec<Chapter> {
+        let video_details = &self.player_response.video_details;
+
+        let mut chapters = self.initial_data
+            .as_ref()
+            .map(crate::helper::parse_chapters)
+            .unwrap_or_default();
+        if chapters.is_empty() {
+            chapters = crate::helper::parse_description_chapters(&video_details.short_description);
+        }
+
+        chapter::close_chapters(&mut chapters, video_details.length_seconds * 1000);
+        chapters
+    }
+
+    /// The [`VideoMetadata`] of the video, which combines the player response with the
+    /// information of the watch page (likes, the full description, ...).
+    #[inline]
+    pub fn metadata(&self) -> VideoMetadata {
+        VideoMetadata::from_video_info(self)
+    }
+
+    /// The "most replayed" [`Heatmap`] of the video, or `None` if YouTube doesn't show one (i.e.
+    /// for new or unpopular videos).
+    pub fn heatmap(&self) -> Option<Heatmap> {
+        self.initial_data
+            .as_ref()
+            .map(crate::helper::parse_heatmap)
+            .and_then(Heatmap::new)
+    }
+
+    /// The seek preview [`Storyboard`] of the video, or `None` if the video has none (i.e. live
+    /// streams).
+    ///
+    /// ### Errors
+    /// - When the storyboard spec has an unexpected format.
+    pub fn storyboard(&self) -> crate::Result<Option<Storyboard>> {
+        let spec = match self.player_response.storyboards {
+            Some(ref storyboards) => storyboards.player_storyboard_spec_renderer.as_ref(),
+            None => None,
+        };
+
+        spec
+            .map(|renderer| Storyboard::from_spec(
+                &renderer.spec,
+                self.player_response.video_details.length_seconds * 1000,
+            ))
+            .transpose()
+    }
+
+    /// The related videos, which are shown next to the player.
+    ///
+    /// The first page is already part of the watch page, so only the following pages are
+    /// requested lazily.
+    pub fn related_videos(&self) -> impl Stream<Item=crate::Result<RelatedVideo>> {
+        let (videos, continuation) = self.initial_data
+            .as_ref()
+            .map(crate::helper::parse_related_videos)
+            .unwrap_or_default();
+
+        futures::stream::iter(videos.into_iter().map(Ok))
+            .chain(crate::helper::continuation_stream(
+                "next",
+                None,
+                continuation,
+                crate::helper::parse_related_videos_continuation,
+            ))
+    }
+
+    /// Whether the video can be watched in `country`, which is an ISO 3166-1 alpha-2 code, like
+    /// `"DE"`.
+    ///
+    /// Videos, without a list of available countries, are assumed to be available everywhere.
+    #[cfg(feature = "microformat")]
+    pub fn is_available_in(&self, country: &str) -> bool {
+        let available_countries = match self.player_response.microformat {
+            Some(ref microformat) => &microformat.player_microformat_renderer.available_countries,
+            None => return true,
+        };
+
+        available_countries.is_empty() || available_countries
+            .iter()
+            .any(|available| available.eq_ignore_ascii_case(country))
+    }
+
+    /// The elements shown at the end of the video.
+    #[inline]
+    pub fn endscreen(&self) -> &[EndscreenElement] {
+        self.player_response.endscreen
+            .as_ref()
+            .map(|endscreen| endscreen.endscreen_renderer.elements.as_slice())
+            .unwrap_or_default()
+    }
 }
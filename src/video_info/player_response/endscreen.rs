@@ -0,0 +1,136 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_with::{json::JsonString, serde_as};
+
+use crate::video_info::player_response::video_details::Thumbnail;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Endscreen {
+    pub endscreen_renderer: EndscreenRenderer,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EndscreenRenderer {
+    #[serde(default)]
+    #[serde(serialize_with = "EndscreenElement::serialize_vec")]
+    #[serde(deserialize_with = "EndscreenElement::deserialize_vec")]
+    pub elements: Vec<EndscreenElement>,
+    #[serde(default)]
+    #[serde_as(as = "Option<JsonString>")]
+    pub start_ms: Option<u64>,
+}
+
+/// A single element (video, playlist, channel, link, ...) shown at the end of a video.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EndscreenElement {
+    pub id: Option<String>,
+    pub style: EndscreenElementStyle,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "crate::serde_impl::text::option::serialize")]
+    #[serde(deserialize_with = "crate::serde_impl::text::option::deserialize")]
+    pub title: Option<String>,
+    #[serde(default, rename = "image")]
+    #[serde(serialize_with = "Thumbnail::serialize_vec")]
+    #[serde(deserialize_with = "Thumbnail::deserialize_vec")]
+    pub thumbnails: Vec<Thumbnail>,
+    #[serde_as(as = "JsonString")]
+    pub start_ms: u64,
+    #[serde_as(as = "JsonString")]
+    pub end_ms: u64,
+    /// The position and size of the element, relative to the size of the player.
+    #[serde(default)]
+    pub left: f64,
+    #[serde(default)]
+    pub top: f64,
+    #[serde(default)]
+    pub width: f64,
+    #[serde(default)]
+    pub aspect_ratio: f64,
+    #[serde(default)]
+    pub endpoint: EndscreenEndpoint,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EndscreenElementStyle {
+    Video,
+    Playlist,
+    Channel,
+    Website,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct EndscreenEndpoint {
+    pub watch_endpoint: Option<WatchEndpoint>,
+    pub browse_endpoint: Option<BrowseEndpoint>,
+    pub url_endpoint: Option<UrlEndpoint>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchEndpoint {
+    pub video_id: Option<String>,
+    pub playlist_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct BrowseEndpoint {
+    pub browse_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlEndpoint {
+    /// a absolute or relative url
+    pub url: String,
+}
+
+impl EndscreenElement {
+    /// The id the element points to: the video id, playlist id, or channel id, or the url of
+    /// a website.
+    pub fn target_id(&self) -> Option<&str> {
+        let endpoint = &self.endpoint;
+        match self.style {
+            EndscreenElementStyle::Playlist => endpoint.watch_endpoint
+                .as_ref()
+                .and_then(|watch| watch.playlist_id.as_deref()),
+            _ => endpoint.watch_endpoint
+                .as_ref()
+                .and_then(|watch| watch.video_id.as_deref()),
+        }
+            .or_else(|| endpoint.browse_endpoint.as_ref().map(|browse| browse.browse_id.as_str()))
+            .or_else(|| endpoint.url_endpoint.as_ref().map(|url| url.url.as_str()))
+    }
+
+    fn deserialize_vec<'de, D>(deserializer: D) -> Result<Vec<Self>, <D as Deserializer<'de>>::Error> where
+        D: Deserializer<'de> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Element { endscreen_element_renderer: EndscreenElement }
+
+        Ok(
+            Vec::<Element>::deserialize(deserializer)?
+                .into_iter()
+                .map(|element| element.endscreen_element_renderer)
+                .collect()
+        )
+    }
+
+    fn serialize_vec<S>(elements: &[Self], serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Element<'a> { endscreen_element_renderer: &'a EndscreenElement }
+
+        serializer.collect_seq(elements.iter().map(|element| Element { endscreen_element_renderer: element }))
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, FixedOffset, Utc};
 use serde::{Deserialize, Serialize};
 use serde_with::{json::JsonString, serde_as};
 
@@ -15,6 +15,7 @@ pub struct Microformat {
 #[serde(rename_all = "camelCase")]
 pub struct PlayerMicroformatRenderer {
     // TODO: use something specific for a ISO 3166-1 alpha-2 identifier
+    #[serde(default)]
     pub available_countries: Vec<String>,
     // TODO: maybe also an enum
     pub category: String,
@@ -23,22 +24,25 @@ pub struct PlayerMicroformatRenderer {
     pub external_channel_id: String,
     #[serde(default)]
     pub has_ypc_metadate: bool,
+    pub is_family_safe: Option<bool>,
+    /// Whether YouTube would show the video as a Short.
+    pub is_shorts_eligible: Option<bool>,
     pub is_unlisted: bool,
     pub length_seconds: String,
-    pub live_brodcast_details: Option<LiveBroadcastDetails>,
+    pub live_broadcast_details: Option<LiveBroadcastDetails>,
     pub owner_channel_name: String,
     pub owner_profile_url: String,
-    #[serde(with = "crate::serde_impl::date_ymd")]
-    pub publish_date: NaiveDate,
+    #[serde(with = "crate::serde_impl::date_time")]
+    pub publish_date: DateTime<FixedOffset>,
     #[serde(rename = "thumbnail")]
     #[serde(serialize_with = "Thumbnail::serialize_vec")]
     #[serde(deserialize_with = "Thumbnail::deserialize_vec")]
     pub thumbnails: Vec<Thumbnail>,
     pub title: SimpleText,
-    #[serde(with = "crate::serde_impl::date_ymd")]
-    pub upload_date: NaiveDate,
+    #[serde(with = "crate::serde_impl::date_time")]
+    pub upload_date: DateTime<FixedOffset>,
     #[serde_as(as = "JsonString")]
-    pub view_count: i32,
+    pub view_count: u64,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
@@ -60,6 +64,7 @@ pub struct SimpleText {
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct LiveBroadcastDetails {
-    is_live_now: bool,
-    start_simestamp: DateTime<Utc>,
+    pub is_live_now: bool,
+    pub start_timestamp: Option<DateTime<Utc>>,
+    pub end_timestamp: Option<DateTime<Utc>>,
 }
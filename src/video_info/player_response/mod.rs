@@ -1,20 +1,25 @@
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+use serde_with::{DefaultOnError, serde_as};
 
 #[cfg(feature = "microformat")]
 use microformat::Microformat;
+use endscreen::Endscreen;
 use playability_status::PlayabilityStatus;
+use storyboard::Storyboards;
 use streaming_data::StreamingData;
 use video_details::VideoDetails;
 
 pub mod video_details;
+pub mod endscreen;
 pub mod streaming_data;
 pub mod playability_status;
+pub mod storyboard;
 #[cfg(feature = "microformat")]
 pub mod microformat;
 
-
+#[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerResponse {
@@ -24,15 +29,21 @@ pub struct PlayerResponse {
     // auxiliaryUi: _,
     // captions: _,
     // cards: _,
-    // endscreen: _,
     // messages: _,
+    // Neither the endscreen nor the storyboards are required for downloading a video, so a layout
+    // change in them should not break deserializing the whole PlayerResponse.
+    #[serde(default)]
+    #[serde_as(as = "DefaultOnError")]
+    pub endscreen: Option<Endscreen>,
     #[cfg(feature = "microformat")]
     pub microformat: Option<Microformat>,
     pub playability_status: PlayabilityStatus,
     // playbackTracking: _,
     // playerConfig: _,
     // response_context: ResponseContext,
-    // storyboards: _,
+    #[serde(default)]
+    #[serde_as(as = "DefaultOnError")]
+    pub storyboards: Option<Storyboards>,
     pub streaming_data: Option<StreamingData>,
     pub video_details: Arc<VideoDetails>,
     pub tracking_params: String,
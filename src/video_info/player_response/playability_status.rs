@@ -31,6 +31,7 @@ pub enum PlayabilityStatus {
     LoginRequired {
         #[serde(default)]
         messages: Vec<String>,
+        reason: Option<String>,
         error_screen: Option<ErrorScreen>,
         desktop_legacy_age_gate_reason: Option<i64>,
         context_params: String,
@@ -51,6 +52,51 @@ pub enum PlayabilityStatus {
     },
 }
 
+impl PlayabilityStatus {
+    /// The main reason, why the video can't be played, as shown in the player.
+    pub fn reason(&self) -> Option<String> {
+        match self {
+            Self::Ok { .. } => None,
+            Self::Unplayable { reason, .. }
+            | Self::LiveStreamOffline { reason, .. }
+            | Self::Error { reason, .. } => Some(reason.clone()),
+            Self::LoginRequired { reason, error_screen, messages, .. } => reason
+                .clone()
+                .or_else(|| Some(error_screen.as_ref()?.player_error_message_renderer.reason.full_text()))
+                .or_else(|| messages.first().cloned()),
+        }
+    }
+
+    /// The additional explanation below the reason, i.e. why a video was removed.
+    pub fn subreason(&self) -> Option<String> {
+        self
+            .error_screen()?
+            .player_error_message_renderer
+            .subreason
+            .as_ref()
+            .map(Reason::full_text)
+            .filter(|subreason| !subreason.is_empty())
+    }
+
+    pub fn error_screen(&self) -> Option<&ErrorScreen> {
+        match self {
+            Self::Unplayable { error_screen, .. }
+            | Self::LoginRequired { error_screen, .. }
+            | Self::Error { error_screen, .. } => error_screen.as_ref(),
+            Self::Ok { .. } | Self::LiveStreamOffline { .. } => None,
+        }
+    }
+
+    pub fn messages(&self) -> &[String] {
+        match self {
+            Self::Ok { messages, .. }
+            | Self::Unplayable { messages, .. }
+            | Self::LoginRequired { messages, .. } => messages,
+            Self::LiveStreamOffline { .. } | Self::Error { .. } => &[],
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct MiniPlayer {
@@ -84,11 +130,11 @@ pub struct PlayerErrorMessageRenderer {
     pub subreason: Option<Reason>,
     pub reason: Reason,
     pub proceed_button: Option<ProceedButton>,
-    #[serde(rename = "thumbnail")]
+    #[serde(rename = "thumbnail", default)]
     #[serde(serialize_with = "Thumbnail::serialize_vec")]
     #[serde(deserialize_with = "Thumbnail::deserialize_vec")]
     pub thumbnails: Vec<Thumbnail>,
-    pub icon: Icon,
+    pub icon: Option<Icon>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
@@ -100,6 +146,15 @@ pub struct Reason {
     pub runs: Vec<Reason>,
 }
 
+impl Reason {
+    /// The text, including the text of all runs.
+    pub fn full_text(&self) -> String {
+        let mut text = self.text.clone().unwrap_or_default();
+        text.extend(self.runs.iter().map(Reason::full_text));
+        text
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct ProceedButton {
@@ -199,7 +254,10 @@ pub struct Icon {
 #[derive(Clone, Copy, Debug, derive_more::Display, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum IconType {
-    ErrorOutline
+    ErrorOutline,
+    Lock,
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
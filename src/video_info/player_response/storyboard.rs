@@ -0,0 +1,209 @@
+#[cfg(feature = "download")]
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::Error;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Storyboards {
+    pub player_storyboard_spec_renderer: Option<PlayerStoryboardSpecRenderer>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerStoryboardSpecRenderer {
+    /// The raw storyboard spec, which can be parsed with [`Storyboard::from_spec`].
+    pub spec: String,
+    pub recommended_level: Option<usize>,
+}
+
+/// The seek preview sprite sheets of a video.
+///
+/// YouTube offers the sprite sheets in several [`StoryboardLevel`]s, where higher levels have
+/// bigger tiles and a shorter interval between the tiles.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct Storyboard {
+    pub levels: Vec<StoryboardLevel>,
+}
+
+/// A single level of a [`Storyboard`].
+///
+/// Each level consists of one or more sprite sheets, which contain up to `columns * rows` tiles.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct StoryboardLevel {
+    pub level: usize,
+    /// The url of the sprite sheets, with `$M` as a placeholder for the sheet index.
+    pub url_template: String,
+    pub tile_width: u64,
+    pub tile_height: u64,
+    /// The total number of tiles across all sheets.
+    pub tile_count: u64,
+    pub columns: u64,
+    pub rows: u64,
+    /// The time between two tiles in milliseconds.
+    pub interval_ms: u64,
+}
+
+/// The position of a single tile inside of a sprite sheet.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct StoryboardTile {
+    pub sheet: u64,
+    pub url: Url,
+    pub x: u64,
+    pub y: u64,
+    pub width: u64,
+    pub height: u64,
+}
+
+impl Storyboard {
+    /// Parses a storyboard spec (`PlayerStoryboardSpecRenderer::spec`).
+    ///
+    /// The spec has the format `<url>|<level 0>|<level 1>|...`, where each level is a `#` separated
+    /// list of `width#height#count#columns#rows#interval#name#sigh`. Since the interval is `0` for
+    /// some levels, the length of the video is required to compute it.
+    ///
+    /// ### Errors
+    /// - When the spec does not have the expected format.
+    pub fn from_spec(spec: &str, length_ms: u64) -> crate::Result<Self> {
+        let mut parts = spec.split('|');
+        let base_url = parts
+            .next()
+            .filter(|url| !url.is_empty())
+            .ok_or_else(|| Error::UnexpectedResponse("the storyboard spec is empty".into()))?;
+
+        let levels = parts
+            .enumerate()
+            .map(|(level, args)| StoryboardLevel::from_spec(base_url, level, args, length_ms))
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        Ok(Self { levels })
+    }
+
+    /// The level with the biggest tiles.
+    #[inline]
+    pub fn best_level(&self) -> Option<&StoryboardLevel> {
+        self.levels.iter().max_by_key(|level| level.tile_width * level.tile_height)
+    }
+}
+
+impl StoryboardLevel {
+    fn from_spec(base_url: &str, level: usize, args: &str, length_ms: u64) -> crate::Result<Self> {
+        let args = args.split('#').collect::<Vec<_>>();
+        let bad_spec = || Error::UnexpectedResponse(format!(
+            "the storyboard spec for level {level} has an unexpected format: `{}`",
+            args.join("#")
+        ).into());
+
+        if args.len() < 8 {
+            return Err(bad_spec());
+        }
+        let number = |i: usize| args[i].parse::<u64>().map_err(|_| bad_spec());
+
+        let (tile_width, tile_height, tile_count) = (number(0)?, number(1)?, number(2)?);
+        let (columns, rows, mut interval_ms) = (number(3)?, number(4)?, number(5)?);
+        if columns == 0 || rows == 0 || tile_count == 0 {
+            return Err(bad_spec());
+        }
+        if interval_ms == 0 {
+            interval_ms = length_ms / tile_count;
+        }
+
+        let separator = if base_url.contains('?') { '&' } else { '?' };
+        let url_template = format!(
+            "{}{separator}sigh={}",
+            base_url
+                .replace("$L", &level.to_string())
+                .replace("$N", args[6]),
+            args[7],
+        );
+
+        Ok(Self {
+            level,
+            url_template,
+            tile_width,
+            tile_height,
+            tile_count,
+            columns,
+            rows,
+            interval_ms,
+        })
+    }
+
+    /// The number of tiles on one sprite sheet.
+    #[inline]
+    pub fn tiles_per_sheet(&self) -> u64 {
+        self.columns * self.rows
+    }
+
+    /// The number of sprite sheets of this level.
+    #[inline]
+    pub fn sheet_count(&self) -> u64 {
+        self.tile_count.div_ceil(self.tiles_per_sheet())
+    }
+
+    /// The url of a sprite sheet.
+    ///
+    /// ### Errors
+    /// - When the spec contained an invalid url.
+    pub fn sheet_url(&self, sheet: u64) -> crate::Result<Url> {
+        Ok(Url::parse(&self.url_template.replace("$M", &sheet.to_string()))?)
+    }
+
+    /// The tile, which shows the frame at `time_ms`.
+    /// Timestamps after the last tile are mapped to the last tile.
+    ///
+    /// ### Errors
+    /// - When the spec contained an invalid url.
+    pub fn tile_at(&self, time_ms: u64) -> crate::Result<StoryboardTile> {
+        let frame = time_ms
+            .checked_div(self.interval_ms)
+            .unwrap_or(0)
+            .min(self.tile_count - 1);
+        let sheet = frame / self.tiles_per_sheet();
+        let index = frame % self.tiles_per_sheet();
+
+        Ok(StoryboardTile {
+            sheet,
+            url: self.sheet_url(sheet)?,
+            x: index % self.columns * self.tile_width,
+            y: index / self.columns * self.tile_height,
+            width: self.tile_width,
+            height: self.tile_height,
+        })
+    }
+}
+
+#[cfg(feature = "download")]
+impl StoryboardLevel {
+    /// Downloads all sprite sheets of this level to `L<level>_M<sheet>.jpg` in the provided
+    /// directory, and returns the paths in sheet order.
+    ///
+    /// ### Errors
+    /// - When a request fails.
+    /// - When writing a sheet to disk fails.
+    pub async fn download_to_dir<P: AsRef<Path>>(&self, dir: P) -> crate::Result<Vec<PathBuf>> {
+        let client = crate::crate_client()?;
+        let mut paths = Vec::with_capacity(self.sheet_count() as usize);
+
+        for sheet in 0..self.sheet_count() {
+            let bytes = client
+                .get(self.sheet_url(sheet)?)
+                .send()
+                .await?
+                .error_for_status()?
+                .bytes()
+                .await?;
+
+            let path = dir
+                .as_ref()
+                .join(format!("L{}_M{sheet}.jpg", self.level));
+            tokio::fs::write(&path, bytes).await?;
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+}
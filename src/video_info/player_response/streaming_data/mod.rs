@@ -72,6 +72,28 @@ pub struct SignatureCipher {
     pub s: Option<String>,
 }
 
+impl SignatureCipher {
+    /// The time the url expires, which is either part of its query (`expire=<unix timestamp>`),
+    /// or, for HLS streams, of its path (`/expire/<unix timestamp>/`).
+    #[inline]
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        url_expiry(&self.url)
+    }
+}
+
+pub(crate) fn url_expiry(url: &Url) -> Option<DateTime<Utc>> {
+    let expire = url
+        .query_pairs()
+        .find(|(key, _)| key == "expire")
+        .map(|(_, value)| value.into_owned())
+        .or_else(|| {
+            let mut segments = url.path_segments()?;
+            segments.find(|segment| *segment == "expire")?;
+            segments.next().map(str::to_owned)
+        })?;
+    DateTime::from_timestamp(expire.parse().ok()?, 0)
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub enum FormatType {
     #[serde(rename = "FORMAT_STREAM_TYPE_OTF")]
@@ -24,12 +24,13 @@ pub struct VideoDetails {
     pub is_unplugged_corpus: bool,
     pub latency_class: Option<LatencyClass>,
     pub live_chunk_readahead: Option<u64>,
-    #[serde(default)]
+    #[serde(default, alias = "keywords")]
     pub key_words: Vec<String>,
     #[serde_as(as = "JsonString")]
     pub length_seconds: u64,
     pub short_description: String,
     #[serde(rename = "thumbnail")]
+    #[serde(serialize_with = "Thumbnail::serialize_vec")]
     #[serde(deserialize_with = "Thumbnail::deserialize_vec")]
     pub thumbnails: Vec<Thumbnail>,
     pub title: String,
@@ -57,7 +58,6 @@ pub struct Thumbnail {
     pub url: String,
 }
 
-
 impl Thumbnail {
     pub(crate) fn deserialize_vec<'de, D>(deserializer: D) -> Result<Vec<Self>, <D as Deserializer<'de>>::Error> where
         D: Deserializer<'de> {
@@ -78,3 +78,11 @@ impl Thumbnail {
         Thumbnails { thumbnails }.serialize(serializer)
     }
 }
+
+/// A piece of formatted text, which may link somewhere.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub struct TextRun {
+    pub text: String,
+    /// a absolute or relative url
+    pub url: Option<String>,
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ChannelIdBuf, IdBuf, video_info::player_response::video_details::Thumbnail};
+
+/// A video from the related videos next to the player.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelatedVideo {
+    pub video_id: IdBuf,
+    pub title: String,
+    /// `None` for live streams.
+    pub length_seconds: Option<u64>,
+    pub thumbnails: Vec<Thumbnail>,
+    pub author: String,
+    pub channel_id: Option<ChannelIdBuf>,
+    pub view_count: Option<u64>,
+    /// The relative time, like `2 days ago`.
+    pub published_time_text: Option<String>,
+    pub is_live: bool,
+}
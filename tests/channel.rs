@@ -0,0 +1,354 @@
+#![cfg(feature = "fetch")]
+
+use rustube::{Channel, ChannelId, ChannelIdBuf, ChannelResolver, Error};
+use rustube::channel_info::{ChannelTabPage, channel_about::ChannelAbout};
+use rustube::channel_info::channel_playlist::ChannelPlaylist;
+use rustube::channel_info::channel_short::ChannelShort;
+use rustube::channel_info::channel_stream::{ChannelStream, StreamStatus};
+use rustube::channel_info::channel_video::ChannelVideo;
+use rustube::channel_info::community_post::CommunityPost;
+
+use common::*;
+
+#[macro_use]
+mod common;
+
+const CHANNEL_ID: &str = "UCBR8-60-B28hp2BmDPdntcQ";
+
+#[test]
+fn channel_id_format() {
+    let id = ChannelId::from_str(CHANNEL_ID).unwrap();
+    assert_eq!(id.as_str(), CHANNEL_ID);
+    assert_eq!(id.channel_url().as_str(), "https://www.youtube.com/channel/UCBR8-60-B28hp2BmDPdntcQ");
+
+    assert!(ChannelId::from_str("@YouTube").is_err());
+    assert!(ChannelId::from_str("UCBR8-60-B28hp2BmDPdntc").is_err());
+    assert!(ChannelId::from_str("ABBR8-60-B28hp2BmDPdntcQ").is_err());
+    assert!(ChannelIdBuf::from_string("UCBR8-60-B28hp2BmDPdnt!Q".to_owned()).is_err());
+
+    let id: ChannelIdBuf = serde_json::from_str(&format!("\"{CHANNEL_ID}\"")).unwrap();
+    assert_eq!(id, ChannelId::from_str(CHANNEL_ID).unwrap());
+    assert!(serde_json::from_str::<ChannelIdBuf>("\"@YouTube\"").is_err());
+}
+
+#[tokio::test]
+async fn resolve_without_requests() {
+    let resolver = ChannelResolver::new();
+
+    for input in [
+        CHANNEL_ID,
+        " UCBR8-60-B28hp2BmDPdntcQ ",
+        "https://www.youtube.com/channel/UCBR8-60-B28hp2BmDPdntcQ",
+        "youtube.com/channel/UCBR8-60-B28hp2BmDPdntcQ/videos?view=0",
+    ] {
+        assert_eq!(resolver.resolve(input).await.unwrap().as_str(), CHANNEL_ID, "{input}");
+    }
+}
+
+#[tokio::test]
+async fn resolve_from_cache() {
+    let resolver = ChannelResolver::new();
+    let id = ChannelIdBuf::from_string(CHANNEL_ID.to_owned()).unwrap();
+    resolver.insert("@YouTube", id.clone());
+
+    assert_eq!(resolver.cached("@YouTube "), Some(id.clone()));
+    assert_eq!(resolver.resolve("@YouTube").await.unwrap(), id);
+    // clones share the cache
+    assert_eq!(resolver.clone().cached("@YouTube"), Some(id));
+    assert_eq!(ChannelResolver::new().cached("@YouTube"), None);
+}
+
+#[test]
+fn resolver_cache_evicts_the_oldest_inputs() {
+    let resolver = ChannelResolver::with_capacity(2);
+    let id = ChannelIdBuf::from_string(CHANNEL_ID.to_owned()).unwrap();
+    resolver.insert("@first", id.clone());
+    resolver.insert("@second", id.clone());
+    // updating a cached input keeps its position
+    resolver.insert("@first", id.clone());
+    resolver.insert("@third", id.clone());
+
+    assert_eq!(resolver.cached("@first"), None);
+    assert_eq!(resolver.cached("@second"), Some(id.clone()));
+    assert_eq!(resolver.cached("@third"), Some(id.clone()));
+
+    let resolver = ChannelResolver::with_capacity(0);
+    resolver.insert("@first", id);
+    assert_eq!(resolver.cached("@first"), None);
+}
+
+#[test]
+fn channel_video_published_time() {
+    use rustube::channel_info::channel_video::ChannelVideo;
+
+    let renderer = |published: serde_json::Value| serde_json::json!({
+        "videoId": "5jlI4uzZGjU",
+        "title": { "runs": [{ "text": "Fixture video" }] },
+        "lengthText": { "simpleText": "1:02:03" },
+        "thumbnail": { "thumbnails": [] },
+        "publishedTimeText": published,
+    });
+
+    let video: ChannelVideo = serde_json::from_value(renderer(serde_json::json!({ "simpleText": "3 weeks ago" }))).unwrap();
+    assert_eq!(video.length_seconds, Some(3723));
+    let published = video.published.unwrap();
+    assert_eq!(published.text, "3 weeks ago");
+    let age = chrono::DateTime::<chrono::Utc>::from(std::time::SystemTime::now()) - published.estimate.unwrap();
+    assert!((age.num_days() - 21).abs() <= 1);
+
+    let video: ChannelVideo = serde_json::from_value(renderer(serde_json::json!({ "simpleText": "Streamed on Apr 5, 2021" }))).unwrap();
+    assert_eq!(video.published.unwrap().estimate.unwrap().to_rfc3339(), "2021-04-05T00:00:00+00:00");
+
+    let mut upcoming = renderer(serde_json::Value::Null);
+    upcoming.as_object_mut().unwrap().remove("publishedTimeText");
+    let video: ChannelVideo = serde_json::from_value(upcoming).unwrap();
+    assert_eq!(video.published, None);
+}
+
+#[test]
+fn channel_from_initial_data() {
+    let channel = Channel::from_initial_data(&fixture("channel_videos.json")).unwrap();
+
+    let info = channel.channel_info();
+    assert_eq!(info.title, "Fixture Channel");
+    assert_eq!(info.channel_url, "https://www.youtube.com/channel/UCBR8-60-B28hp2BmDPdntcQ");
+    assert_eq!(info.available_countries, ["DE", "US"]);
+
+    let videos = channel
+        .videos()
+        .into_iter()
+        .map(|video| (video.video_id.as_str().to_owned(), video.title, video.length_seconds, video.author))
+        .collect::<Vec<_>>();
+    assert_eq!(videos, [
+        ("5jlI4uzZGjU".to_owned(), "Latest video".to_owned(), Some(600), "Fixture Channel".to_owned()),
+        ("JgGuRKgvWQ4".to_owned(), "Older video".to_owned(), Some(3723), "Fixture Channel".to_owned()),
+        ("ASGNUnPINdM".to_owned(), "Live now".to_owned(), None, "Fixture Channel".to_owned()),
+        ("QRS8MkLhQmM".to_owned(), "Oldest video".to_owned(), Some(42), "Fixture Channel".to_owned()),
+    ]);
+
+    let page = ChannelTabPage::<ChannelVideo>::from_json(&fixture("channel_videos.json")).unwrap();
+    assert_eq!(page.items.len(), 4);
+    assert_eq!(page.continuation.as_deref(), Some("CHANNEL_VIDEOS_TOKEN"));
+}
+
+#[test]
+fn shorts_tab() {
+    let page = ChannelTabPage::<ChannelShort>::from_json(&fixture("channel_shorts.json")).unwrap();
+
+    assert_eq!(page.continuation.as_deref(), Some("CHANNEL_SHORTS_TOKEN"));
+    let [lockup, reel] = &page.items[..] else { panic!("expected two shorts, got: {:?}", page.items) };
+    assert_eq!(lockup.video_id.as_str(), "xQDsI2ptfgg");
+    assert_eq!(lockup.title, "A short");
+    assert_eq!(lockup.view_count_text.as_deref(), Some("1.2K views"));
+    assert_eq!(lockup.thumbnails[0].url, "https://i.ytimg.com/vi/xQDsI2ptfgg/oar2.jpg");
+    assert_eq!((lockup.thumbnails[0].width, lockup.thumbnails[0].height), (405, 720));
+    assert_eq!(reel.video_id.as_str(), "MAoOAa_izh0");
+    assert_eq!(reel.title, "A legacy short");
+    assert_eq!(reel.view_count_text.as_deref(), Some("3.4M views"));
+    assert_eq!(reel.thumbnails.len(), 1);
+}
+
+#[test]
+fn live_tab() {
+    let page = ChannelTabPage::<ChannelStream>::from_json(&fixture("channel_streams.json")).unwrap();
+
+    assert_eq!(page.continuation.as_deref(), Some("CHANNEL_STREAMS_TOKEN"));
+    let [live, upcoming, past] = &page.items[..] else { panic!("expected three streams, got: {:?}", page.items) };
+    assert_eq!(live.video_id.as_str(), "FwwgBB8l2vs");
+    assert_eq!(live.status, StreamStatus::Live);
+    assert_eq!(live.view_count_text.as_deref(), Some("1,024 watching"));
+    assert_eq!(live.length_seconds, None);
+
+    assert_eq!(upcoming.title, "Upcoming stream");
+    match upcoming.status {
+        StreamStatus::Upcoming { start_time } => assert_eq!(start_time.unwrap().to_rfc3339(), "2030-01-01T00:00:00+00:00"),
+        status => panic!("expected StreamStatus::Upcoming, got: {:?}", status),
+    }
+
+    assert_eq!(past.status, StreamStatus::Past);
+    assert_eq!(past.length_seconds, Some(7384));
+    assert_eq!(past.published_time_text.as_deref(), Some("Streamed 2 days ago"));
+    assert_eq!(past.view_count_text.as_deref(), Some("5,678 views"));
+}
+
+#[test]
+fn playlists_tab() {
+    let page = ChannelTabPage::<ChannelPlaylist>::from_json(&fixture("channel_playlists.json")).unwrap();
+
+    assert_eq!(page.continuation.as_deref(), Some("CHANNEL_PLAYLISTS_TOKEN"));
+    let [grid, lockup] = &page.items[..] else { panic!("expected two playlists, got: {:?}", page.items) };
+    assert_eq!(grid.playlist_id.as_str(), "PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I");
+    assert_eq!(grid.title, "Grid playlist");
+    assert_eq!(grid.video_count, Some(24));
+    assert_eq!(grid.thumbnails[0].url, "https://i.ytimg.com/vi/5jlI4uzZGjU/hqdefault.jpg");
+    assert_eq!(lockup.playlist_id.as_str(), "PLfixture");
+    assert_eq!(lockup.title, "Lockup playlist");
+    assert_eq!(lockup.video_count, Some(1337));
+    assert_eq!(lockup.thumbnails[0].url, "https://i.ytimg.com/vi/JgGuRKgvWQ4/hqdefault.jpg");
+}
+
+#[test]
+fn community_tab() {
+    let page = ChannelTabPage::<CommunityPost>::from_json(&fixture("channel_community.json")).unwrap();
+
+    assert_eq!(page.continuation.as_deref(), Some("CHANNEL_COMMUNITY_TOKEN"));
+    let [poll, images, video] = &page.items[..] else { panic!("expected three posts, got: {:?}", page.items) };
+    assert_eq!(poll.id, "UgkxPOLL");
+    assert_eq!(poll.plain_text(), "Which one? #poll");
+    assert_eq!(poll.text[1].url.as_deref(), Some("/hashtag/poll"));
+    let choices = &poll.poll.as_ref().unwrap().choices;
+    assert_eq!(choices, &["First", "Second"]);
+    assert_eq!(poll.poll.as_ref().unwrap().total_votes_text.as_deref(), Some("1.2K votes"));
+    assert_eq!(poll.like_count, Some(1500));
+    assert_eq!(poll.published_time_text.as_deref(), Some("2 days ago"));
+
+    assert_eq!(images.images.len(), 2);
+    assert_eq!(images.images[1][1].url, "https://yt3.ggpht.com/second=s1080");
+    assert_eq!(images.like_count, Some(42));
+    assert_eq!(images.poll, None);
+
+    assert_eq!(video.video_id.as_ref().unwrap().as_str(), "5jlI4uzZGjU");
+    assert!(video.images.is_empty());
+}
+
+#[test]
+fn about_tab() {
+    // newer pages load the About panel lazily
+    let page = fixture("channel_about.json");
+    assert_eq!(ChannelAbout::from_json(&page).unwrap(), None);
+    assert_eq!(ChannelAbout::continuation(&page).unwrap().as_deref(), Some("CHANNEL_ABOUT_TOKEN"));
+
+    let response = fixture("channel_about_response.json");
+    assert_eq!(ChannelAbout::continuation(&response).unwrap(), None);
+    let about = ChannelAbout::from_json(&response).unwrap().unwrap();
+    assert_eq!(about.description, "A channel for fixtures.\n\nNew videos every week.");
+    assert_eq!(about.subscriber_count, Some(1_230_000));
+    assert_eq!(about.view_count, Some(456_789_012));
+    assert_eq!(about.video_count, Some(1024));
+    assert_eq!(about.joined_date, chrono::NaiveDate::from_ymd_opt(2011, 4, 5));
+    assert_eq!(about.country.as_deref(), Some("Germany"));
+    let links = about.links.iter().map(|link| (link.title.as_str(), link.url.as_str())).collect::<Vec<_>>();
+    assert_eq!(links, [("Website", "https://example.com/"), ("Shop", "https://shop.example.com")]);
+}
+
+#[test]
+fn legacy_about_tab() {
+    let about = ChannelAbout::from_json(&serde_json::json!({
+        "channelAboutFullMetadataRenderer": {
+            "description": { "simpleText": "Legacy description" },
+            "viewCountText": { "simpleText": "1,234 views" },
+            "joinedDateText": { "runs": [{ "text": "Joined " }, { "text": "Jan 2, 2006" }] },
+            "country": { "simpleText": "United States" },
+            "primaryLinks": [{
+                "title": { "simpleText": "Blog" },
+                "navigationEndpoint": { "urlEndpoint": { "url": "https://www.youtube.com/redirect?q=https%3A%2F%2Fblog.example.com" } },
+            }],
+        },
+    }).to_string()).unwrap().unwrap();
+
+    assert_eq!(about.description, "Legacy description");
+    assert_eq!(about.view_count, Some(1234));
+    assert_eq!(about.joined_date, chrono::NaiveDate::from_ymd_opt(2006, 1, 2));
+    assert_eq!(about.country.as_deref(), Some("United States"));
+    assert_eq!(about.links[0].url, "https://blog.example.com");
+}
+
+#[test]
+fn bad_tab_pages() {
+    assert!(ChannelTabPage::<ChannelShort>::from_json("{}").is_err());
+    assert!(ChannelTabPage::<ChannelShort>::from_json("not json").is_err());
+    assert!(Channel::from_initial_data("{}").is_err());
+}
+
+#[test]
+fn channel_video_length() {
+    let video = |length: &str| serde_json::from_value::<ChannelVideo>(serde_json::json!({
+        "videoId": "5jlI4uzZGjU",
+        "title": { "runs": [{ "text": "Fixture video" }] },
+        "lengthText": { "simpleText": length },
+        "thumbnail": { "thumbnails": [] },
+    }));
+
+    assert_eq!(video("0:42").unwrap().length_seconds, Some(42));
+    assert!(video("LIVE").is_err());
+    assert!(video("").is_err());
+    assert!(video("99999999999999999999:00").is_err());
+    assert!(video("9999999999999999:00:00").is_err());
+
+    // live streams and upcoming videos have no length
+    let mut initial_data: serde_json::Value = serde_json::from_str(&fixture("channel_videos.json")).unwrap();
+    let renderer = "/contents/twoColumnBrowseResultsRenderer/tabs/1/tabRenderer/content/richGridRenderer/contents/0/\
+        richItemRenderer/content/videoRenderer";
+    initial_data.pointer_mut(renderer).unwrap().as_object_mut().unwrap().remove("lengthText");
+    let channel = Channel::from_initial_data(&initial_data.to_string()).unwrap();
+    assert_eq!(channel.videos().len(), 4);
+    assert_eq!(channel.videos()[0].length_seconds, None);
+
+    // videos with unexpected lengths fail the whole page, instead of being skipped
+    initial_data.pointer_mut(renderer).unwrap()["lengthText"] = serde_json::json!({ "simpleText": "LIVE" });
+    let result = Channel::from_initial_data(&initial_data.to_string());
+    assert!(matches!(result, Err(Error::LayoutChange { ref path, .. }) if path == "videoRenderer"), "{:?}", result.map(|_| ()));
+    let result = ChannelTabPage::<ChannelVideo>::from_json(&initial_data.to_string());
+    assert!(matches!(result, Err(Error::LayoutChange { ref path, .. }) if path == "videoRenderer"), "{:?}", result);
+}
+
+#[test]
+fn video_sort_chips() {
+    use rustube::channel_info::channel_video::VideoSort;
+
+    let initial_data = fixture("channel_videos.json");
+    assert_eq!(VideoSort::Latest.continuation(&initial_data).unwrap().as_deref(), Some("LATEST_TOKEN"));
+    assert_eq!(VideoSort::Popular.continuation(&initial_data).unwrap().as_deref(), Some("POPULAR_TOKEN"));
+    assert_eq!(VideoSort::Oldest.continuation(&initial_data).unwrap().as_deref(), Some("OLDEST_TOKEN"));
+
+    // localized chips are found by their position
+    let localized = initial_data
+        .replace("\"Latest\"", "\"Neueste\"")
+        .replace("\"Popular\"", "\"Beliebt\"")
+        .replace("\"Oldest\"", "\"Älteste\"");
+    assert_ne!(localized, initial_data);
+    assert_eq!(VideoSort::Popular.continuation(&localized).unwrap().as_deref(), Some("POPULAR_TOKEN"));
+    assert_eq!(VideoSort::Oldest.continuation(&localized).unwrap().as_deref(), Some("OLDEST_TOKEN"));
+
+    assert_eq!(VideoSort::Popular.continuation(&fixture("channel_shorts.json")).unwrap(), None);
+    assert!(VideoSort::Popular.continuation("{").is_err());
+}
+
+#[test]
+fn videos_since_cutoff() {
+    use chrono::{DateTime, Duration, Utc};
+
+    let now = DateTime::<Utc>::from(std::time::SystemTime::now());
+    let page = ChannelTabPage::<ChannelVideo>::from_json(&fixture("channel_videos.json")).unwrap();
+    let since = |since: DateTime<Utc>| page.items
+        .iter()
+        .take_while(|video| video.is_published_since(since))
+        .map(|video| video.video_id.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(since(now - Duration::days(10)), ["5jlI4uzZGjU"]);
+    // the live stream has no publish time, so it never ends the videos
+    assert_eq!(since(now - Duration::days(20)), ["5jlI4uzZGjU", "JgGuRKgvWQ4", "ASGNUnPINdM"]);
+    assert_eq!(since(now - Duration::days(400)), ["5jlI4uzZGjU", "JgGuRKgvWQ4", "ASGNUnPINdM", "QRS8MkLhQmM"]);
+    assert!(since(now).is_empty());
+}
+
+#[test]
+fn published_time_overflow() {
+    let video = |published: &str| serde_json::from_value::<ChannelVideo>(serde_json::json!({
+        "videoId": "5jlI4uzZGjU",
+        "title": { "runs": [{ "text": "Fixture video" }] },
+        "lengthText": { "simpleText": "0:42" },
+        "thumbnail": { "thumbnails": [] },
+        "publishedTimeText": { "simpleText": published },
+    })).unwrap();
+
+    assert!(video("3 days ago").published.unwrap().estimate.is_some());
+    for published in ["3000000000 years ago", "99999999999999999999 days ago", "9999999 years ago"] {
+        let video = video(published);
+        assert_eq!(video.published.as_ref().unwrap().text, published);
+        assert_eq!(video.published.as_ref().unwrap().estimate, None);
+        // videos without an estimate never end `videos_since`
+        assert!(video.is_published_since(chrono::DateTime::<chrono::Utc>::MAX_UTC));
+    }
+}
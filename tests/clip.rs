@@ -0,0 +1,101 @@
+#![cfg(feature = "download")]
+
+use std::time::Duration;
+
+use rustube::{Clip, Error, Id};
+use rustube::stream::SegmentIndex;
+
+#[macro_use]
+mod common;
+
+#[test]
+fn clip_from_initial_data() {
+    let clip = Clip::from_initial_data(
+        "UgkxTSyxdrzGxEb1T_8_Ia5hXNbtnMLuvSPQ",
+        &common::fixture("clip_initial_data.json"),
+    ).unwrap();
+
+    assert_eq!(clip.id, "UgkxTSyxdrzGxEb1T_8_Ia5hXNbtnMLuvSPQ");
+    assert_eq!(clip.video_id, Id::from_str("jNQXAC9IVRw").unwrap());
+    assert_eq!(clip.title.as_deref(), Some("The elephants"));
+    assert_eq!(clip.time_range(), Duration::from_millis(2500)..Duration::from_millis(9000));
+    assert_eq!(clip.duration(), Duration::from_millis(6500));
+}
+
+#[test]
+fn clip_without_time_range() {
+    let initial_data = r#"{"currentVideoEndpoint":{"watchEndpoint":{"videoId":"jNQXAC9IVRw"}}}"#;
+    let result = Clip::from_initial_data("Ugkx", initial_data);
+    assert!(matches!(result, Err(Error::LayoutChange { .. })), "{:?}", result);
+}
+
+#[test]
+fn clip_without_source_video() {
+    // the only video id belongs to a related video, not to the source video of the clip
+    let initial_data = r#"{
+        "clipConfig": { "startTimeMs": "2500", "endTimeMs": "9000" },
+        "contents": { "compactVideoRenderer": { "videoId": "jNQXAC9IVRw" } }
+    }"#;
+    let result = Clip::from_initial_data("Ugkx", initial_data);
+    assert!(matches!(result, Err(Error::LayoutChange { .. })), "{:?}", result);
+}
+
+#[tokio::test]
+async fn download_stream_of_other_video() {
+    let clip = Clip::from_initial_data(
+        "UgkxTSyxdrzGxEb1T_8_Ia5hXNbtnMLuvSPQ",
+        &common::fixture("clip_initial_data.json"),
+    ).unwrap();
+    let stream = common::stream_fixture("https://rr1---sn-fixture.googlevideo.com/videoplayback", None);
+
+    let result = clip.download_to(&stream, std::env::temp_dir().join("rustube-wrong-video.mp4")).await;
+    assert!(matches!(
+        result,
+        Err(Error::WrongVideo { ref expected, ref found }) if expected.as_str() == "jNQXAC9IVRw" && found.as_str() == "5jlI4uzZGjU"
+    ), "{:?}", result);
+}
+
+/// A version 0 `sidx` box with a timescale of 1000 and three fragments of 2 seconds each.
+fn sidx() -> Vec<u8> {
+    let mut sidx = Vec::new();
+    sidx.extend_from_slice(&(32u32 + 3 * 12).to_be_bytes());
+    sidx.extend_from_slice(b"sidx");
+    sidx.extend_from_slice(&[0, 0, 0, 0]);
+    sidx.extend_from_slice(&1u32.to_be_bytes());
+    sidx.extend_from_slice(&1000u32.to_be_bytes());
+    sidx.extend_from_slice(&0u32.to_be_bytes());
+    sidx.extend_from_slice(&0u32.to_be_bytes());
+    sidx.extend_from_slice(&[0, 0]);
+    sidx.extend_from_slice(&3u16.to_be_bytes());
+    for size in [100u32, 200, 300] {
+        sidx.extend_from_slice(&size.to_be_bytes());
+        sidx.extend_from_slice(&2000u32.to_be_bytes());
+        sidx.extend_from_slice(&0x9000_0000u32.to_be_bytes());
+    }
+    sidx
+}
+
+#[test]
+fn segment_index_locate() {
+    let index = SegmentIndex::parse(&sidx()).unwrap();
+    assert_eq!(index.timescale, 1000);
+    assert_eq!(index.box_size, 68);
+    assert_eq!(index.references.len(), 3);
+
+    // the sidx box starts at byte 700, so the first fragment starts at byte 768
+    let (bytes, time) = index.locate(700, Duration::from_millis(2500)..Duration::from_millis(3000)).unwrap();
+    assert_eq!(bytes, 868..1068);
+    assert_eq!(time, Duration::from_secs(2)..Duration::from_secs(4));
+
+    let (bytes, time) = index.locate(700, Duration::from_millis(1000)..Duration::from_secs(10)).unwrap();
+    assert_eq!(bytes, 768..1368);
+    assert_eq!(time, Duration::ZERO..Duration::from_secs(6));
+
+    assert_eq!(index.locate(700, Duration::from_secs(7)..Duration::from_secs(8)), None);
+}
+
+#[test]
+fn segment_index_invalid() {
+    assert!(SegmentIndex::parse(b"\0\0\0\x08moov").is_err());
+    assert!(SegmentIndex::parse(&sidx()[..40]).is_err());
+}
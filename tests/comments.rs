@@ -0,0 +1,101 @@
+#![cfg(feature = "fetch")]
+
+use common::*;
+use rustube::comment_info::CommentPage;
+
+#[macro_use]
+mod common;
+
+#[test]
+fn first_comment_page() {
+    let page = CommentPage::from_json(&fixture("next_comments.json")).unwrap();
+
+    assert_eq!(page.comment_count, Some(1024));
+    assert_eq!(page.top_continuation.as_deref(), Some("TOP_TOKEN"));
+    assert_eq!(page.newest_continuation.as_deref(), Some("NEWEST_TOKEN"));
+    assert_eq!(page.continuation.as_deref(), Some("NEXT_PAGE_TOKEN"));
+    assert_eq!(page.comments.len(), 2);
+
+    let pinned = &page.comments[0];
+    assert_eq!(pinned.id, "UgwComment1");
+    assert_eq!(pinned.author, "@rustacean");
+    assert_eq!(pinned.author_channel_id.as_deref(), Some("UCBR8-60-B28hp2BmDPdntcQ"));
+    assert_eq!(pinned.plain_text(), "Great video! See the docs");
+    assert!(pinned.text[1].url.as_deref().unwrap().contains("docs.rs"));
+    assert_eq!(pinned.like_count, 1200);
+    assert_eq!(pinned.published_time_text, "2 days ago");
+    assert_eq!(pinned.reply_count, 2);
+    assert!(pinned.is_pinned && pinned.is_hearted && pinned.is_author_channel_owner);
+    assert_eq!(pinned.replies_continuation.as_deref(), Some("REPLIES_TOKEN"));
+
+    let plain = &page.comments[1];
+    assert_eq!(plain.like_count, 0);
+    assert!(!plain.is_pinned && !plain.is_hearted);
+    assert!(plain.replies_continuation.is_none());
+}
+
+#[test]
+fn reply_page() {
+    let page = CommentPage::from_json(&fixture("next_replies.json")).unwrap();
+
+    assert_eq!(page.comments.len(), 1);
+    assert_eq!(page.comments[0].like_count, 7);
+    assert_eq!(page.continuation.as_deref(), Some("MORE_REPLIES_TOKEN"));
+    assert!(page.newest_continuation.is_none());
+}
+
+#[test]
+fn view_model_comment_page() {
+    let page = CommentPage::from_json(&fixture("next_comments_view_model.json")).unwrap();
+
+    assert_eq!(page.comment_count, Some(2048));
+    assert_eq!(page.top_continuation.as_deref(), Some("VM_TOP_TOKEN"));
+    assert_eq!(page.newest_continuation.as_deref(), Some("VM_NEWEST_TOKEN"));
+    assert_eq!(page.continuation.as_deref(), Some("VM_NEXT_PAGE_TOKEN"));
+    assert_eq!(page.comments.len(), 2);
+
+    let pinned = &page.comments[0];
+    assert_eq!(pinned.id, "UgxViewModel1");
+    assert_eq!(pinned.author, "@rustacean");
+    assert_eq!(pinned.author_channel_id.as_deref(), Some("UCBR8-60-B28hp2BmDPdntcQ"));
+    assert_eq!(pinned.author_thumbnails.len(), 1);
+    assert_eq!(pinned.author_thumbnails[0].width, 88);
+    assert_eq!(pinned.plain_text(), "🦀 Great video! See the docs");
+    // the link starts after the emoji, which are two UTF-16 code units
+    assert_eq!(pinned.text[1].text, "the docs");
+    assert!(pinned.text[1].url.as_deref().unwrap().contains("docs.rs"));
+    assert_eq!(pinned.like_count, 3400);
+    assert_eq!(pinned.published_time_text, "2 days ago");
+    assert_eq!(pinned.reply_count, 12);
+    assert!(pinned.is_pinned && pinned.is_hearted && pinned.is_author_channel_owner);
+    assert_eq!(pinned.replies_continuation.as_deref(), Some("VM_REPLIES_TOKEN"));
+
+    let plain = &page.comments[1];
+    assert_eq!(plain.plain_text(), "first");
+    assert_eq!((plain.like_count, plain.reply_count), (0, 0));
+    assert!(!plain.is_pinned && !plain.is_hearted && !plain.is_author_channel_owner);
+    assert!(plain.replies_continuation.is_none());
+}
+
+#[test]
+fn view_model_reply_page() {
+    let page = CommentPage::from_json(&fixture("next_replies_view_model.json")).unwrap();
+
+    assert_eq!(page.comments.len(), 1);
+    assert_eq!(page.comments[0].id, "UgxViewModel1.Reply1");
+    assert_eq!(page.comments[0].plain_text(), "@rustacean thanks");
+    assert_eq!(page.comments[0].like_count, 7);
+    assert_eq!(page.continuation.as_deref(), Some("VM_MORE_REPLIES_TOKEN"));
+}
+
+#[test]
+fn unexpected_comment_page() {
+    assert!(CommentPage::from_json("{}").is_err());
+    assert!(CommentPage::from_json(r#"{"onResponseReceivedEndpoints": [{"appendContinuationItemsAction": {
+        "continuationItems": [{"commentRenderer": {"contentText": {"simpleText": "no id"}}}]
+    }}]}"#).is_err());
+    // the view model references an entity, which is not part of the response
+    assert!(CommentPage::from_json(r#"{"onResponseReceivedEndpoints": [{"appendContinuationItemsAction": {
+        "continuationItems": [{"commentViewModel": {"commentKey": "missing"}}]
+    }}]}"#).is_err());
+}
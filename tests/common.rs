@@ -93,3 +93,41 @@ pub fn random_entry<T>(vec: &Vec<T>) -> &T {
         .gen_range(0..vec.len());
     &vec[i]
 }
+
+pub const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+pub fn fixture(name: &str) -> String {
+    std::fs::read_to_string(std::path::Path::new(FIXTURE_DIR).join(name))
+        .unwrap()
+}
+
+#[cfg(feature = "fetch")]
+pub fn video_info_fixture(initial_data: Option<&str>) -> rustube::VideoInfo {
+    rustube::VideoInfo {
+        player_response: serde_json::from_str(&fixture("player_response.json")).unwrap(),
+        adaptive_fmts_raw: None,
+        is_age_restricted: false,
+        initial_data: initial_data.map(|name| serde_json::from_str(&fixture(name)).unwrap()),
+    }
+}
+
+#[cfg(feature = "stream")]
+pub fn stream_fixture(url: &str, expires_at: Option<chrono::DateTime<chrono::Utc>>) -> rustube::Stream {
+    let player_response: serde_json::Value = serde_json::from_str(&fixture("player_response.json")).unwrap();
+    serde_json::from_value(serde_json::json!({
+        "mime": "video/mp4",
+        "codecs": ["avc1.42001E", "mp4a.40.2"],
+        "is_progressive": true,
+        "includes_video_track": true,
+        "includes_audio_track": true,
+        "content_length": 0,
+        "expires_at": expires_at,
+        "fps": 30,
+        "is_otf": false,
+        "itag": 18,
+        "projection_type": "RECTANGULAR",
+        "quality": "medium",
+        "signature_cipher": { "url": url, "s": null },
+        "video_details": player_response["videoDetails"],
+    })).unwrap()
+}
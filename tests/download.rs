@@ -1,9 +1,17 @@
 #![cfg(feature = "download")]
 
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use futures::FutureExt;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
 
 use common::*;
-use rustube::VideoFetcher;
+use rustube::{Error, Stream, VideoFetcher};
 
 #[macro_use]
 mod common;
@@ -91,3 +99,234 @@ async fn download_to() {
 
     correct_path!(&path, path);
 }
+
+const CONTENT: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// How long the `/stalled` path of the test server stalls, before it closes the connection.
+const STALL: Duration = Duration::from_secs(3);
+
+/// Serves [`CONTENT`] on a local port, see [`serve`].
+async fn serve_content(ranges: bool) -> (String, Arc<Mutex<Vec<Option<String>>>>) {
+    serve(CONTENT.to_vec(), ranges).await
+}
+
+/// Serves `content` on a local port, and records the `Range` header of every request.
+/// If `ranges` is false, the server ignores the `Range` header, like some CDNs do.
+///
+/// Requests to `/forbidden` are rejected with `403 Forbidden`, and requests to `/stalled` only
+/// receive the first half of `content`, before the connection is closed after [`STALL`].
+async fn serve(content: Vec<u8>, ranges: bool) -> (String, Arc<Mutex<Vec<Option<String>>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/videoplayback?itag=18", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let recorded = Arc::clone(&requests);
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 { break; }
+                request.extend_from_slice(&buf[..n]);
+            }
+            let request = String::from_utf8(request).unwrap();
+            let path = request.split_whitespace().nth(1).unwrap_or_default().to_owned();
+            let range = request
+                .lines()
+                .find_map(|line| line.to_ascii_lowercase().strip_prefix("range: bytes=").map(str::to_owned));
+            recorded.lock().unwrap().push(range.clone());
+
+            if path.starts_with("/forbidden") {
+                socket.write_all(b"HTTP/1.1 403 Forbidden\r\ncontent-length: 0\r\nconnection: close\r\n\r\n").await.unwrap();
+                socket.shutdown().await.unwrap();
+                continue;
+            }
+            if path.starts_with("/stalled") {
+                let head = format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n", content.len());
+                socket.write_all(&[head.as_bytes(), &content[..content.len() / 2]].concat()).await.unwrap();
+                socket.flush().await.unwrap();
+                tokio::time::sleep(STALL).await;
+                socket.shutdown().await.unwrap();
+                continue;
+            }
+
+            let response = match range.filter(|_| ranges) {
+                None => [
+                    format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n", content.len()).into_bytes(),
+                    content.clone(),
+                ].concat(),
+                Some(range) => {
+                    let (start, end) = range.split_once('-').unwrap();
+                    let start: usize = start.parse().unwrap();
+                    let end = end.parse::<usize>().map_or(content.len(), |end| end + 1);
+                    match content.get(start..end).filter(|body| !body.is_empty()) {
+                        Some(body) => [
+                            format!(
+                                "HTTP/1.1 206 Partial Content\r\ncontent-range: bytes {}-{}/{}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                                start, end - 1, content.len(), body.len(),
+                            ).into_bytes(),
+                            body.to_vec(),
+                        ].concat(),
+                        None => b"HTTP/1.1 416 Range Not Satisfiable\r\ncontent-length: 0\r\nconnection: close\r\n\r\n".to_vec(),
+                    }
+                }
+            };
+            socket.write_all(&response).await.unwrap();
+            socket.shutdown().await.unwrap();
+        }
+    });
+    (url, requests)
+}
+
+/// Replaces the url refresh of `stream` with one, that returns `url`, and counts the refreshes.
+fn refreshing_to(stream: Stream, url: &str) -> (Stream, Arc<AtomicUsize>) {
+    let refreshes = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&refreshes);
+    let url = url::Url::parse(url).unwrap();
+    let stream = stream.with_url_refresher(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        let url = url.clone();
+        async move { Ok(url) }.boxed()
+    });
+    (stream, refreshes)
+}
+
+fn download_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rustube_{}", name));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test_log::test(tokio::test)]
+async fn download_to_local_server() {
+    let (url, requests) = serve_content(true).await;
+    let path = download_file("download_to_local_server.mp4");
+
+    stream_fixture(&url, None).download_to(&path).await.unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), CONTENT);
+    assert_eq!(*requests.lock().unwrap(), [None]);
+}
+
+#[test_log::test(tokio::test)]
+async fn resume_download() {
+    let (url, requests) = serve_content(true).await;
+    let path = download_file("resume_download.mp4");
+    std::fs::write(&path, &CONTENT[..10]).unwrap();
+
+    stream_fixture(&url, None).resume_download_to(&path).await.unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), CONTENT);
+    assert_eq!(*requests.lock().unwrap(), [Some("10-".to_owned())]);
+}
+
+#[test_log::test(tokio::test)]
+async fn resume_complete_download() {
+    let (url, requests) = serve_content(true).await;
+    let path = download_file("resume_complete_download.mp4");
+    std::fs::write(&path, CONTENT).unwrap();
+
+    stream_fixture(&url, None).resume_download_to(&path).await.unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), CONTENT);
+    assert_eq!(*requests.lock().unwrap(), [Some(format!("{}-", CONTENT.len()))]);
+}
+
+#[test_log::test(tokio::test)]
+async fn resume_download_without_ranges() {
+    let (url, _) = serve_content(false).await;
+    let path = download_file("resume_download_without_ranges.mp4");
+    std::fs::write(&path, &CONTENT[..10]).unwrap();
+
+    let res = stream_fixture(&url, None).resume_download_to(&path).await;
+
+    assert!(matches!(res, Err(Error::UnexpectedResponse(_))), "{:?}", res);
+    // the partial file is kept, instead of appending the whole stream to it
+    assert_eq!(std::fs::read(&path).unwrap(), &CONTENT[..10]);
+}
+
+#[test_log::test(tokio::test)]
+async fn resume_forbidden_download_with_new_url() {
+    let (url, requests) = serve_content(true).await;
+    let path = download_file("resume_forbidden_download_with_new_url.mp4");
+    std::fs::write(&path, &CONTENT[..10]).unwrap();
+
+    let forbidden = url.replace("/videoplayback", "/forbidden");
+    let (stream, refreshes) = refreshing_to(stream_fixture(&forbidden, None), &url);
+    stream.resume_download_to(&path).await.unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), CONTENT);
+    assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+    // the new url is requested from the same offset
+    assert_eq!(*requests.lock().unwrap(), [Some("10-".to_owned()), Some("10-".to_owned())]);
+}
+
+#[test_log::test(tokio::test)]
+async fn download_resumes_after_the_url_expired_mid_transfer() {
+    let (url, requests) = serve_content(true).await;
+    let path = download_file("download_resumes_after_the_url_expired_mid_transfer.mp4");
+
+    // the url expires while the server stalls in the middle of the response
+    let expire = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() + 2;
+    let stalled = format!("{}&expire={}", url.replace("/videoplayback", "/stalled"), expire);
+    let (stream, refreshes) = refreshing_to(stream_fixture(&stalled, None), &url);
+    stream.download_to(&path).await.unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), CONTENT);
+    assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+    // the download continues after the half, which was received from the expired url
+    assert_eq!(*requests.lock().unwrap(), [None, Some(format!("{}-", CONTENT.len() / 2))]);
+}
+
+#[test_log::test(tokio::test)]
+async fn download_range_with_new_url() {
+    // an initialization segment, a segment index with fragments of 100, 200 and 300 bytes of 2
+    // seconds each, and the fragments
+    let init = b"\0\0\0\x08ftyp".to_vec();
+    let mut sidx = Vec::new();
+    sidx.extend_from_slice(&(32u32 + 3 * 12).to_be_bytes());
+    sidx.extend_from_slice(b"sidx");
+    sidx.extend_from_slice(&[0, 0, 0, 0]);
+    sidx.extend_from_slice(&1u32.to_be_bytes());
+    sidx.extend_from_slice(&1000u32.to_be_bytes());
+    sidx.extend_from_slice(&[0; 8]);
+    sidx.extend_from_slice(&[0, 0]);
+    sidx.extend_from_slice(&3u16.to_be_bytes());
+    for size in [100u32, 200, 300] {
+        sidx.extend_from_slice(&size.to_be_bytes());
+        sidx.extend_from_slice(&2000u32.to_be_bytes());
+        sidx.extend_from_slice(&0x9000_0000u32.to_be_bytes());
+    }
+    let fragments = (0..600u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    let content = [init.clone(), sidx.clone(), fragments.clone()].concat();
+
+    let (url, requests) = serve(content, true).await;
+    let path = download_file("download_range_with_new_url.mp4");
+
+    let forbidden = url.replace("/videoplayback", "/forbidden");
+    let mut stream = stream_fixture(&forbidden, None);
+    stream.init_range = Some(0..7);
+    stream.index_range = Some(8..75);
+    let (stream, refreshes) = refreshing_to(stream, &url);
+    let covered = stream
+        .download_range_to(&path, Duration::from_millis(2500)..Duration::from_millis(3000))
+        .await
+        .unwrap();
+
+    assert_eq!(covered, Duration::from_secs(2)..Duration::from_secs(4));
+    assert_eq!(std::fs::read(&path).unwrap(), [&init[..], &fragments[100..300]].concat());
+    assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        *requests.lock().unwrap(),
+        [Some("0-75".to_owned()), Some("0-75".to_owned()), Some("176-375".to_owned())],
+    );
+
+    let res = stream.download_range_to(&path, Duration::from_secs(7)..Duration::from_secs(8)).await;
+    assert!(matches!(res, Err(Error::TimeRangeNotInStream(_))), "{:?}", res);
+    let mut progressive = stream_fixture(&url, None);
+    progressive.init_range = None;
+    let res = progressive.download_range_to(&path, Duration::ZERO..Duration::from_secs(1)).await;
+    assert!(matches!(res, Err(Error::PartialDownloadUnsupported)), "{:?}", res);
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "fetch")]
+
+use serde_json::Value;
+
+use rustube::{DiagnosticsBundle, Error, Id, VideoFetcher};
+use rustube::diagnostics::MAX_SNIPPET_LEN;
+use rustube::video_info::player_response::playability_status::PlayabilityStatus;
+
+#[macro_use]
+mod common;
+
+fn playability_error(name: &str) -> Error {
+    let statuses: Value = serde_json::from_str(&common::fixture("playability_statuses.json")).unwrap();
+    let status: PlayabilityStatus = serde_json::from_value(statuses[name].clone()).unwrap();
+    Error::from(status)
+}
+
+#[test]
+fn playability_status_errors() {
+    match playability_error("geo_blocked") {
+        Error::GeoBlocked { reason, allowed, blocked } => {
+            assert_eq!(reason, "Video unavailable");
+            // the playability status alone does not contain any countries
+            assert!(allowed.is_empty() && blocked.is_empty());
+        }
+        err => panic!("expected Error::GeoBlocked, got: {:?}", err),
+    }
+    match playability_error("age_restricted") {
+        Error::AgeRestricted { reason } => assert_eq!(reason, "Sign in to confirm your age"),
+        err => panic!("expected Error::AgeRestricted, got: {:?}", err),
+    }
+    assert!(matches!(playability_error("members_only"), Error::MembersOnly { .. }));
+    match playability_error("private") {
+        Error::Private { reason } => assert_eq!(reason, "Private video"),
+        err => panic!("expected Error::Private, got: {:?}", err),
+    }
+    match playability_error("removed") {
+        Error::Removed { reason } => assert_eq!(
+            reason,
+            "This video is no longer available because the YouTube account associated with this video has been terminated."
+        ),
+        err => panic!("expected Error::Removed, got: {:?}", err),
+    }
+    match playability_error("unknown_id") {
+        Error::VideoUnavailable(status) => assert_eq!(status.reason().as_deref(), Some("Video unavailable")),
+        err => panic!("expected Error::VideoUnavailable, got: {:?}", err),
+    }
+}
+
+fn geo_blocked(fetcher: VideoFetcher) -> (Vec<String>, Vec<String>) {
+    match fetcher.check_watch_page(&common::fixture("watch_geo_blocked.html")) {
+        Err(Error::GeoBlocked { reason, allowed, blocked }) => {
+            assert_eq!(reason, "Video unavailable");
+            (allowed, blocked)
+        }
+        res => panic!("expected Error::GeoBlocked, got: {:?}", res),
+    }
+}
+
+#[test]
+fn geo_blocked_watch_page() {
+    let fetcher = || VideoFetcher::from_id(Id::from_str("jNQXAC9IVRw").unwrap().into_owned()).unwrap();
+
+    let (allowed, blocked) = geo_blocked(fetcher());
+    assert_eq!(allowed, ["AT", "CH", "US"]);
+    assert_eq!(blocked, ["DE"]);
+
+    // the watch page is localized for the overridden country, not the one of the request
+    let (allowed, blocked) = geo_blocked(fetcher().with_gl("US"));
+    assert_eq!(allowed, ["AT", "CH", "US"]);
+    assert!(blocked.is_empty());
+}
+
+#[test]
+fn diagnostics_bundle() {
+    let error = Error::LayoutChange {
+        context: "the watch page".into(),
+        path: "ytInitialPlayerResponse".into(),
+        source: serde_json::from_str::<Value>("{").err(),
+    };
+    let watch_html = format!(
+        r#"<script src="/s/player/4fbb4d5b/player_ias.vflset/en_US/base.js"></script>
+        <script>var ytInitialPlayerResponse = {{"playabilityStatus":{{"status":"OK"}},"padding":"{}"}};</script>"#,
+        "a".repeat(2 * MAX_SNIPPET_LEN),
+    );
+    let bundle = DiagnosticsBundle::new(Id::from_str("jNQXAC9IVRw").unwrap().into_owned(), &error)
+        .with_watch_html(&watch_html);
+
+    assert!(bundle.error.starts_with("failed to parse the watch page: unexpected value at `ytInitialPlayerResponse`: EOF"));
+    assert_eq!(bundle.player_id.as_deref(), Some("4fbb4d5b"));
+    let sources: Vec<_> = bundle.snippets.iter().map(|snippet| snippet.source.as_str()).collect();
+    assert_eq!(sources, ["watch page: playabilityStatus", "watch page: ytInitialPlayerResponse", "watch page: player url"]);
+    assert!(bundle.snippets[1].content.starts_with(r#"ytInitialPlayerResponse = {"playabilityStatus""#));
+    assert!(bundle.snippets[1].truncated);
+    assert!(bundle.snippets.iter().all(|snippet| snippet.content.len() <= MAX_SNIPPET_LEN));
+}
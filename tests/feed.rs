@@ -0,0 +1,65 @@
+#![cfg(feature = "fetch")]
+
+use rustube::{ChannelFeed, ChannelId, Id, PlaylistId};
+use rustube::feed::Feed;
+
+#[macro_use]
+mod common;
+
+#[test]
+fn channel_feed() {
+    let feed = Feed::from_xml(&common::fixture("channel_feed.xml")).unwrap();
+    assert_eq!(feed.title, "YouTube");
+    assert_eq!(feed.channel_id.as_ref().unwrap().as_str(), "UCBR8-60-B28hp2BmDPdntcQ");
+    assert_eq!(feed.playlist_id, None);
+    assert_eq!(feed.author.as_deref(), Some("YouTube"));
+    assert_eq!(feed.published.unwrap().to_rfc3339(), "2005-09-20T01:28:22+00:00");
+    assert_eq!(feed.entries.len(), 2);
+
+    let entry = &feed.entries[0];
+    assert_eq!(entry.video_id, Id::from_str("jNQXAC9IVRw").unwrap());
+    assert_eq!(entry.title, "Me at the zoo & more");
+    assert_eq!(entry.description, "The first video on YouTube.\nSecond line.");
+    assert_eq!(entry.published.to_rfc3339(), "2024-05-02T16:00:06+00:00");
+    assert_eq!(entry.updated.to_rfc3339(), "2024-05-03T08:12:44+00:00");
+    assert_eq!(entry.views, Some(312_345_678));
+    let thumbnail = entry.thumbnail.as_ref().unwrap();
+    assert_eq!(thumbnail.url, "https://i2.ytimg.com/vi/jNQXAC9IVRw/hqdefault.jpg");
+    assert_eq!((thumbnail.width, thumbnail.height), (480, 360));
+
+    assert_eq!(feed.entries[1].description, "");
+    assert_eq!(feed.entries[1].views, Some(0));
+}
+
+#[test]
+fn playlist_feed() {
+    let feed = Feed::from_xml(&common::fixture("playlist_feed.xml")).unwrap();
+    assert_eq!(feed.playlist_id.as_deref(), Some("PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I"));
+    assert_eq!(feed.entries.len(), 1);
+
+    let entry = &feed.entries[0];
+    assert_eq!(entry.channel_id.as_ref().unwrap().as_str(), "UCX6OQ3DkcsbYNE6H8uQQuVA");
+    assert_eq!(entry.author.as_deref(), Some("Other"));
+    assert_eq!(entry.views, None);
+}
+
+#[test]
+fn invalid_feed() {
+    assert!(Feed::from_xml("").is_err());
+    assert!(Feed::from_xml("<html><body></body></html>").is_err());
+    assert!(Feed::from_xml(&common::fixture("channel_feed.xml").replace("<yt:videoId>jNQXAC9IVRw</yt:videoId>", "")).is_err());
+}
+
+#[test]
+fn feed_urls_and_validators() {
+    let feed = ChannelFeed::for_channel(ChannelId::from_str("UCBR8-60-B28hp2BmDPdntcQ").unwrap()).unwrap();
+    assert_eq!(feed.url().as_str(), "https://www.youtube.com/feeds/videos.xml?channel_id=UCBR8-60-B28hp2BmDPdntcQ");
+    assert_eq!(feed.etag(), None);
+
+    let feed = ChannelFeed::for_playlist(PlaylistId::from_str("PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I").unwrap())
+        .unwrap()
+        .with_validators(Some("\"abc\"".to_owned()), Some("Fri, 03 May 2024 08:12:44 GMT".to_owned()));
+    assert_eq!(feed.url().as_str(), "https://www.youtube.com/feeds/videos.xml?playlist_id=PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I");
+    assert_eq!(feed.etag(), Some("\"abc\""));
+    assert_eq!(feed.last_modified(), Some("Fri, 03 May 2024 08:12:44 GMT"));
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "fetch")]
+
+use rustube::{ChannelId, Error, Id};
+use rustube::music::{parse_discography, Album, Artist, Lyrics, Track};
+
+#[macro_use]
+mod common;
+
+const DAFT_PUNK: &str = "UCmMUZbaYdNH0bEd1PAlAqsA";
+
+#[test]
+fn track_from_next() {
+    let track = Track::from_next_json(&common::fixture("music_next.json")).unwrap();
+    assert_eq!(track.video_id, Id::from_str("gAjR4_CbPpQ").unwrap());
+    assert_eq!(track.title, "Harder, Better, Faster, Stronger");
+    assert_eq!(track.artists.len(), 1);
+    assert_eq!(track.artists[0].name, "Daft Punk");
+    assert_eq!(track.artists[0].channel_id.as_ref().unwrap().as_str(), DAFT_PUNK);
+    let album = track.album.unwrap();
+    assert_eq!(album.title, "Discovery");
+    assert_eq!(album.browse_id, "MPREb_BQZvl3BFGay");
+    assert_eq!(track.year, Some(2001));
+    assert_eq!(track.track_number, None);
+    assert_eq!(track.length_seconds, Some(225));
+    assert!(track.is_explicit);
+    assert_eq!(track.thumbnails.len(), 2);
+    assert_eq!(track.lyrics, None);
+}
+
+#[test]
+fn lyrics() {
+    let lyrics = Lyrics::from_json(&common::fixture("music_lyrics.json")).unwrap().unwrap();
+    assert!(lyrics.text.starts_with("Work it, make it, do it, makes us\n"));
+    assert_eq!(lyrics.source.as_deref(), Some("Source: Musixmatch"));
+
+    assert_eq!(Lyrics::from_json(r#"{"contents":{}}"#).unwrap(), None);
+}
+
+#[test]
+fn album() {
+    let album = Album::from_json("MPREb_BQZvl3BFGay", &common::fixture("music_album.json")).unwrap();
+    assert_eq!(album.title, "Discovery");
+    assert_eq!(album.kind.as_deref(), Some("Album"));
+    assert_eq!(album.year, Some(2001));
+    assert_eq!(album.playlist_id.unwrap().as_str(), "OLAK5uy_k1ZWqjVgCNOxbMg7ItJf8aPDYGvk5fkH4");
+    assert_eq!(album.artists.len(), 1);
+    assert_eq!(album.artists[0].name, "Daft Punk");
+    assert_eq!(album.description.as_deref(), Some("Discovery is the second studio album by Daft Punk."));
+    assert!(!album.is_explicit);
+    assert_eq!(album.thumbnails[0].width, 544);
+
+    // the unavailable second track has no video id
+    assert_eq!(album.tracks.len(), 2);
+    let first = &album.tracks[0];
+    assert_eq!(first.video_id, Id::from_str("FGBhQbmPwH8").unwrap());
+    assert_eq!(first.title, "One More Time");
+    assert_eq!(first.track_number, Some(1));
+    assert_eq!(first.length_seconds, Some(321));
+    assert_eq!(first.artists, album.artists);
+    assert_eq!(first.album.as_ref().unwrap().title, "Discovery");
+    assert_eq!(first.year, Some(2001));
+    assert!(!first.is_explicit);
+
+    let featuring = &album.tracks[1];
+    assert_eq!(featuring.track_number, Some(4));
+    assert_eq!(featuring.artists.iter().map(|artist| artist.name.as_str()).collect::<Vec<_>>(), ["Daft Punk", "Guest"]);
+    assert_eq!(featuring.artists[1].channel_id, None);
+    assert!(featuring.is_explicit);
+}
+
+#[test]
+fn album_browse_id_from_playlist_page() {
+    let html = common::fixture("music_album_playlist.html");
+    // a recommended album is linked before the album of the playlist
+    assert!(html.find("MPREb_recommended00") < html.find("MPREb_BQZvl3BFGay"));
+    assert_eq!(Album::browse_id_from_playlist_page(&html).unwrap(), "MPREb_BQZvl3BFGay");
+
+    // without a link in the header, the canonical url is used
+    let canonical = html
+        .replace("MPREb_BQZvl3BFGay", "UC_kRDKYrUlrbtrSiyu5Tflg")
+        .replace(
+            r"playlist?list\x3dOLAK5uy_k1ZWqjVgCNOxbMg7ItJf8aPDYGvk5fkH4",
+            r"browse\/MPREb_canonical0",
+        );
+    assert_eq!(Album::browse_id_from_playlist_page(&canonical).unwrap(), "MPREb_canonical0");
+
+    let not_an_album = html.replace("MPREb_BQZvl3BFGay", "UC_kRDKYrUlrbtrSiyu5Tflg");
+    let result = Album::browse_id_from_playlist_page(&not_an_album);
+    assert!(matches!(result, Err(Error::LayoutChange { .. })), "{:?}", result);
+    let result = Album::browse_id_from_playlist_page("<html>MPREb_BQZvl3BFGay</html>");
+    assert!(matches!(result, Err(Error::LayoutChange { .. })), "{:?}", result);
+}
+
+#[test]
+fn artist() {
+    let channel_id = ChannelId::from_str(DAFT_PUNK).unwrap();
+    let artist = Artist::from_json(channel_id, &common::fixture("music_artist.json")).unwrap();
+    assert_eq!(artist.name, "Daft Punk");
+    assert_eq!(artist.channel_id.as_str(), DAFT_PUNK);
+    assert_eq!(artist.description.as_deref(), Some("Daft Punk were a French electronic music duo."));
+    assert_eq!(artist.subscriber_count, Some(7_590_000));
+    assert_eq!(artist.thumbnails.len(), 2);
+
+    assert_eq!(artist.top_tracks.len(), 2);
+    let top = &artist.top_tracks[0];
+    assert_eq!(top.title, "Get Lucky");
+    assert_eq!(top.artists.len(), 2);
+    assert_eq!(top.artists[1].name, "Pharrell Williams");
+    assert_eq!(top.album, None);
+
+    assert_eq!(artist.albums.len(), 2);
+    assert_eq!(artist.albums[0].title, "Random Access Memories");
+    assert_eq!(artist.albums[0].browse_id, "MPREb_8QkDeEIawvX");
+    assert_eq!(artist.albums[0].kind.as_deref(), Some("Album"));
+    assert_eq!(artist.albums[0].year, Some(2013));
+
+    assert_eq!(artist.singles.len(), 1);
+    assert_eq!(artist.singles[0].kind.as_deref(), Some("Single"));
+    assert!(artist.singles[0].is_explicit);
+}
+
+#[test]
+fn discography() {
+    let albums = parse_discography(&common::fixture("music_discography.json")).unwrap();
+    assert_eq!(
+        albums.iter().map(|album| (album.title.as_str(), album.year)).collect::<Vec<_>>(),
+        [
+            ("Random Access Memories", Some(2013)),
+            ("Human After All", Some(2005)),
+            ("Discovery", Some(2001)),
+            ("Homework", Some(1997)),
+        ]
+    );
+    assert!(albums.iter().all(|album| album.kind.is_none()));
+}
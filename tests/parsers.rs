@@ -0,0 +1,370 @@
+//! Property tests, which feed arbitrary and mutated input to the offline parsers.
+//!
+//! The parsers must never panic, no matter how YouTube changes its responses. Mutated fixtures
+//! simulate layout changes, by removing or replacing single values of real responses.
+
+#![cfg(feature = "download")]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use proptest::prelude::*;
+use proptest::sample::Index;
+use serde_json::Value;
+
+use rustube::{Channel, ChannelId, Clip, Id, Playlist, PlaylistId, Resource};
+use rustube::channel_info::{ChannelTabPage, channel_about::ChannelAbout};
+use rustube::channel_info::channel_playlist::ChannelPlaylist;
+use rustube::channel_info::channel_short::ChannelShort;
+use rustube::channel_info::channel_stream::ChannelStream;
+use rustube::channel_info::channel_video::ChannelVideo;
+use rustube::channel_info::community_post::CommunityPost;
+use rustube::comment_info::CommentPage;
+use rustube::feed::Feed;
+use rustube::music::{parse_discography, Album, Artist, Lyrics, Track};
+use rustube::search::parse_suggestions;
+use rustube::search_info::SearchPage;
+use rustube::stream::SegmentIndex;
+use rustube::video_info::player_response::storyboard::Storyboard;
+
+#[macro_use]
+mod common;
+
+/// Keys the parsers look for, so arbitrary json reaches more than the first lookup.
+const KEYS: &[&str] = &[
+    "contents", "content", "runs", "text", "simpleText", "title", "videoId", "browseId", "tabs",
+    "tabRenderer", "selected", "header", "items", "thumbnail", "thumbnails", "url", "width", "height",
+    "sectionListRenderer", "itemSectionRenderer", "richGridRenderer", "continuationItemRenderer",
+    "continuationEndpoint", "continuationCommand", "token", "onResponseReceivedActions",
+    "onResponseReceivedCommands", "onResponseReceivedEndpoints", "appendContinuationItemsAction",
+    "continuationItems", "twoColumnBrowseResultsRenderer", "twoColumnSearchResultsRenderer",
+    "primaryContents", "playlistVideoListRenderer", "playlistVideoRenderer", "videoRenderer",
+    "channelRenderer", "playlistRenderer", "lengthText", "commentRenderer", "commentId",
+    "microformat", "microformatDataRenderer", "currentVideoEndpoint", "watchEndpoint", "clipConfig",
+    "startTimeMs", "endTimeMs", "watchNextTabbedResultsRenderer", "playlistPanelRenderer",
+    "playlistPanelVideoRenderer", "longBylineText", "musicResponsiveHeaderRenderer",
+    "musicShelfRenderer", "musicResponsiveListItemRenderer", "flexColumns",
+    "musicImmersiveHeaderRenderer", "musicCarouselShelfRenderer", "musicTwoRowItemRenderer",
+    "gridRenderer", "navigationEndpoint", "browseEndpoint", "musicDescriptionShelfRenderer",
+    "twoColumnWatchNextResults", "playlist", "isInfinite", "totalVideos",
+    "richItemRenderer", "reelItemRenderer", "shortsLockupViewModel", "gridPlaylistRenderer",
+    "lockupViewModel", "backstagePostThreadRenderer", "backstagePostRenderer", "aboutChannelViewModel",
+    "channelAboutFullMetadataRenderer", "showEngagementPanelEndpoint", "upcomingEventData",
+    "simpleText", "publishedTimeText", "commentViewModel", "commentKey", "toolbarStateKey",
+    "frameworkUpdates", "entityBatchUpdate", "mutations", "payload", "commentEntityPayload",
+    "properties", "commandRuns", "startIndex", "length", "contentId", "contentType",
+    "thumbnailBadgeViewModel", "metadataRows", "metadataParts",
+];
+
+const JSON_FIXTURES: &[&str] = &[
+    "channel_about.json",
+    "channel_about_response.json",
+    "channel_community.json",
+    "channel_playlists.json",
+    "channel_shorts.json",
+    "channel_streams.json",
+    "channel_videos.json",
+    "clip_initial_data.json",
+    "music_album.json",
+    "music_artist.json",
+    "music_discography.json",
+    "music_lyrics.json",
+    "music_next.json",
+    "next_playlist_panel_mix.json",
+    "next_comments.json",
+    "next_comments_view_model.json",
+    "next_replies.json",
+    "next_replies_view_model.json",
+    "playlist_initial_data.json",
+    "playlist_initial_data_header.json",
+    "search_continuation.json",
+    "search_response.json",
+    "watch_initial_data.json",
+    "watch_initial_data_heatseeker.json",
+    "watch_initial_data_lockup.json",
+    "watch_playlist_panel_album.json",
+];
+
+fn json() -> impl Strategy<Value=Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        any::<f64>().prop_map(Value::from),
+        "[a-zA-Z0-9 :.,•@_-]{0,16}".prop_map(Value::from),
+    ];
+    leaf.prop_recursive(5, 96, 6, |inner| prop_oneof![
+        prop::collection::vec(inner.clone(), 0..6).prop_map(Value::from),
+        prop::collection::vec((prop::sample::select(KEYS), inner), 0..6)
+            .prop_map(|entries| Value::Object(entries.into_iter().map(|(k, v)| (k.to_owned(), v)).collect())),
+    ])
+}
+
+/// A replacement for a single value, or `None` to remove it.
+fn replacement() -> impl Strategy<Value=Option<Value>> {
+    prop_oneof![
+        Just(None),
+        Just(Some(Value::Null)),
+        any::<i64>().prop_map(|n| Some(Value::from(n))),
+        ".{0,8}".prop_map(|s| Some(Value::from(s))),
+        Just(Some(Value::Array(Vec::new()))),
+        Just(Some(Value::Object(Default::default()))),
+    ]
+}
+
+fn mutations() -> impl Strategy<Value=Vec<(Index, Option<Value>)>> {
+    prop::collection::vec((any::<Index>(), replacement()), 1..4)
+}
+
+fn count_nodes(value: &Value) -> usize {
+    1 + match value {
+        Value::Array(array) => array.iter().map(count_nodes).sum(),
+        Value::Object(object) => object.values().map(count_nodes).sum(),
+        _ => 0,
+    }
+}
+
+/// Replaces the `target`-th value (in depth-first order, excluding the root) with `replacement`,
+/// or removes it from its parent, if `replacement` is `None`.
+fn mutate(value: &mut Value, target: &mut usize, replacement: &mut Option<Option<Value>>) -> bool {
+    let len = match value {
+        Value::Array(array) => array.len(),
+        Value::Object(object) => object.len(),
+        _ => return false,
+    };
+    for i in 0..len {
+        if *target == 0 {
+            match (&mut *value, replacement.take().flatten()) {
+                (Value::Array(array), Some(replacement)) => array[i] = replacement,
+                (Value::Array(array), None) => {
+                    array.remove(i);
+                }
+                (Value::Object(object), replacement) => {
+                    let key = object.keys().nth(i).unwrap().clone();
+                    match replacement {
+                        Some(replacement) => object.insert(key, replacement),
+                        None => object.remove(&key),
+                    };
+                }
+                _ => {}
+            }
+            return true;
+        }
+        *target -= 1;
+        let child = match value {
+            Value::Array(array) => &mut array[i],
+            Value::Object(object) => object.values_mut().nth(i).unwrap(),
+            _ => return false,
+        };
+        if mutate(child, target, replacement) {
+            return true;
+        }
+    }
+    false
+}
+
+fn mutated_fixture(name: &str, mutations: Vec<(Index, Option<Value>)>) -> String {
+    let mut value: Value = serde_json::from_str(&common::fixture(name)).unwrap();
+    for (index, replacement) in mutations {
+        let mut target = index.index(count_nodes(&value).max(2) - 1);
+        mutate(&mut value, &mut target, &mut Some(replacement));
+    }
+    value.to_string()
+}
+
+/// Runs every json parser on `json`. Only panics matter, errors are expected.
+fn parse_json(json: &str) {
+    let _ = SearchPage::from_json(json);
+    let _ = CommentPage::from_json(json);
+    let _ = Playlist::from_initial_data(json);
+    let _ = Clip::from_initial_data("UgkxTSyxdrzGxEb1T_8_Ia5hXNbtnMLuvSPQ", json);
+    let _ = Track::from_next_json(json);
+    let _ = Lyrics::from_json(json);
+    let _ = Album::from_json("MPREb_BQZvl3BFGay", json);
+    let _ = Artist::from_json(ChannelId::from_str("UCmMUZbaYdNH0bEd1PAlAqsA").unwrap(), json);
+    let _ = parse_discography(json);
+    let _ = Playlist::from_watch_panel_data(PlaylistId::from_str("RD5jlI4uzZGjU").unwrap(), json, [json], 10);
+    let _ = Channel::from_initial_data(json);
+    let _ = ChannelTabPage::<ChannelVideo>::from_json(json);
+    let _ = ChannelTabPage::<ChannelShort>::from_json(json);
+    let _ = ChannelTabPage::<ChannelStream>::from_json(json);
+    let _ = ChannelTabPage::<ChannelPlaylist>::from_json(json);
+    let _ = ChannelTabPage::<CommunityPost>::from_json(json);
+    let _ = ChannelAbout::from_json(json);
+    let _ = ChannelAbout::continuation(json);
+
+    if let Ok(initial_data) = serde_json::from_str(json) {
+        let mut video_info = common::video_info_fixture(None);
+        video_info.initial_data = Some(initial_data);
+        let _ = video_info.chapters();
+        let _ = video_info.metadata();
+        let _ = video_info.heatmap();
+    }
+}
+
+/// A version 0 `sidx` box with the given `(size, duration)` references.
+fn sidx(timescale: u32, references: &[(u32, u32)]) -> Vec<u8> {
+    let mut sidx = Vec::new();
+    sidx.extend_from_slice(&(32 + 12 * references.len() as u32).to_be_bytes());
+    sidx.extend_from_slice(b"sidx");
+    sidx.extend_from_slice(&[0; 8]);
+    sidx.extend_from_slice(&timescale.to_be_bytes());
+    sidx.extend_from_slice(&[0; 10]);
+    sidx.extend_from_slice(&(references.len() as u16).to_be_bytes());
+    for (size, duration) in references {
+        sidx.extend_from_slice(&size.to_be_bytes());
+        sidx.extend_from_slice(&duration.to_be_bytes());
+        sidx.extend_from_slice(&[0x90, 0, 0, 0]);
+    }
+    sidx
+}
+
+/// A version 1 `sidx` box, which has 64 bit offsets, with the given `(size, duration)` references.
+fn sidx_v1(earliest_presentation_time: u64, first_offset: u64, references: &[(u32, u32)]) -> Vec<u8> {
+    let mut sidx = Vec::new();
+    sidx.extend_from_slice(&(40 + 12 * references.len() as u32).to_be_bytes());
+    sidx.extend_from_slice(b"sidx");
+    sidx.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0]);
+    sidx.extend_from_slice(&1000u32.to_be_bytes());
+    sidx.extend_from_slice(&earliest_presentation_time.to_be_bytes());
+    sidx.extend_from_slice(&first_offset.to_be_bytes());
+    sidx.extend_from_slice(&[0; 2]);
+    sidx.extend_from_slice(&(references.len() as u16).to_be_bytes());
+    for (size, duration) in references {
+        sidx.extend_from_slice(&size.to_be_bytes());
+        sidx.extend_from_slice(&duration.to_be_bytes());
+        sidx.extend_from_slice(&[0x90, 0, 0, 0]);
+    }
+    sidx
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_text(text in ".{0,256}") {
+        let _ = Feed::from_xml(&text);
+        let _ = parse_suggestions(&text);
+        let _ = Resource::parse(&text);
+        let _ = Storyboard::from_spec(&text, 60_000);
+    }
+
+    #[test]
+    fn arbitrary_bytes(bytes in prop::collection::vec(any::<u8>(), 0..128), start in any::<u64>(), end in any::<u64>()) {
+        if let Ok(index) = SegmentIndex::parse(&bytes) {
+            let _ = index.locate(start, Duration::from_nanos(start)..Duration::from_nanos(end));
+        }
+    }
+
+    #[test]
+    fn truncated_feed(len in 0usize..4096, name in prop::sample::select(&["channel_feed.xml", "playlist_feed.xml"][..])) {
+        let xml = common::fixture(name);
+        let len = (0..=len.min(xml.len())).rev().find(|&len| xml.is_char_boundary(len)).unwrap_or(0);
+        let _ = Feed::from_xml(&xml[..len]);
+    }
+
+    #[test]
+    fn segment_index_with_large_offsets(
+        earliest_presentation_time in any::<u64>(),
+        first_offset in any::<u64>(),
+        references in prop::collection::vec((0u32..1 << 31, any::<u32>()), 0..8),
+        index_start in any::<u64>(),
+    ) {
+        let index = SegmentIndex::parse(&sidx_v1(earliest_presentation_time, first_offset, &references)).unwrap();
+        let _ = index.locate(index_start, Duration::ZERO..Duration::MAX);
+    }
+
+    #[test]
+    fn segment_index_covers_all_fragments(
+        timescale in 1u32..=90_000,
+        references in prop::collection::vec((1u32..1 << 30, 1u32..1 << 20), 1..32),
+        index_start in 0u64..1 << 40,
+    ) {
+        let index = SegmentIndex::parse(&sidx(timescale, &references)).unwrap();
+        let (bytes, time) = index.locate(index_start, Duration::ZERO..Duration::MAX).unwrap();
+
+        let first = index_start + index.box_size;
+        let size: u64 = references.iter().map(|(size, _)| *size as u64).sum();
+        let ticks: u64 = references.iter().map(|(_, duration)| *duration as u64).sum();
+        prop_assert_eq!(bytes, first..first + size);
+        prop_assert_eq!(time.start, Duration::ZERO);
+        prop_assert_eq!(time.end.as_nanos(), ticks as u128 * 1_000_000_000 / timescale as u128);
+    }
+
+    #[test]
+    fn resource_video_urls(
+        id in "[a-zA-Z0-9_-]{10}[AEIMQUYcgkosw048]",
+        seconds in 0u64..100_000,
+        template in prop::sample::select(&[
+            "https://youtu.be/{id}?t={t}",
+            "https://www.youtube.com/watch?v={id}&t={t}s",
+            "youtube.com/shorts/{id}?feature=share&start={t}",
+            "https://m.youtube.com/watch?app=desktop&v={id}#t={t}",
+        ][..]),
+    ) {
+        let url = template.replace("{id}", &id).replace("{t}", &seconds.to_string());
+        let resource = Resource::parse(&url).unwrap();
+        prop_assert_eq!(resource, Resource::Video {
+            id: Id::from_string(id).unwrap(),
+            start_seconds: Some(seconds),
+            playlist_id: None,
+            playlist_index: None,
+        });
+    }
+
+    #[test]
+    fn resource_start_times(
+        time in "[0-9]{1,24}(h|m|s)?([0-9]{1,24}m)?([0-9]{1,24}s?)?",
+        template in prop::sample::select(&[
+            "https://youtu.be/jNQXAC9IVRw?t={t}",
+            "https://www.youtube.com/watch?v=jNQXAC9IVRw&t={t}",
+            "https://www.youtube.com/embed/jNQXAC9IVRw?start={t}",
+            "https://www.youtube.com/watch?v=jNQXAC9IVRw#t={t}",
+        ][..]),
+    ) {
+        // huge times must be ignored instead of overflowing
+        let resource = Resource::parse(&template.replace("{t}", &time));
+        let is_video = matches!(resource, Ok(Resource::Video { .. }));
+        prop_assert!(is_video, "{:?}", resource);
+    }
+
+    #[test]
+    fn channel_video_lengths(length in "[0-9:]{0,32}|LIVE|SHORTS|.{0,8}") {
+        let _ = serde_json::from_value::<ChannelVideo>(serde_json::json!({
+            "videoId": "5jlI4uzZGjU",
+            "title": { "runs": [{ "text": "Fixture video" }] },
+            "lengthText": { "simpleText": length },
+            "thumbnail": { "thumbnails": [] },
+        }));
+    }
+
+    #[test]
+    fn description_chapters(description in "(([0-9]{1,2}:){1,2}[0-9]{2} [a-z ]{0,8}\n|[^\n]{0,24}\n){0,12}") {
+        let mut video_info = common::video_info_fixture(None);
+        Arc::make_mut(&mut video_info.player_response.video_details).short_description = description;
+        let _ = video_info.chapters();
+    }
+}
+
+proptest! {
+    // `Playlist::from_initial_data` builds a new client for every case, which is slow
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn arbitrary_json(json in prop::collection::vec((prop::sample::select(KEYS), json()), 0..8)) {
+        let json = Value::Object(json.into_iter().map(|(k, v)| (k.to_owned(), v)).collect());
+        parse_json(&json.to_string());
+    }
+
+    #[test]
+    fn mutated_fixtures(name in prop::sample::select(JSON_FIXTURES), mutations in mutations()) {
+        parse_json(&mutated_fixture(name, mutations));
+    }
+}
+
+#[test]
+fn segment_index_overflow() {
+    let index = SegmentIndex::parse(&sidx_v1(0, u64::MAX, &[(100, 1000)])).unwrap();
+    assert_eq!(index.locate(0, Duration::ZERO..Duration::MAX), None);
+
+    let index = SegmentIndex::parse(&sidx_v1(u64::MAX, 0, &[(100, 1000)])).unwrap();
+    assert_eq!(index.locate(0, Duration::ZERO..Duration::MAX), None);
+}
@@ -0,0 +1,253 @@
+#![cfg(feature = "fetch")]
+
+use futures::StreamExt;
+
+use rustube::{Id, Playlist, PlaylistId, PlaylistIdBuf, PlaylistKind};
+use rustube::playlist_info::{playlist_video::Availability, PlaylistPrivacy};
+
+use common::*;
+
+#[macro_use]
+mod common;
+
+#[tokio::test]
+async fn first_page_from_initial_data() {
+    let playlist = Playlist::from_initial_data(&fixture("playlist_initial_data.json")).unwrap();
+
+    let videos = playlist
+        .videos()
+        .iter()
+        .take(2)
+        .map(|video| (video.index, video.video_id.as_str(), video.title.as_str(), video.length_seconds))
+        .collect::<Vec<_>>();
+    assert_eq!(videos, [(1, "5jlI4uzZGjU", "First video", Some(600)), (2, "JgGuRKgvWQ4", "Second video", Some(61))]);
+    assert_eq!(playlist.videos().len(), 6);
+    assert_eq!(playlist.continuation(), Some("PLAYLIST_TOKEN"));
+    assert_eq!(playlist.playlist_info().title, "Fixture playlist");
+    assert_eq!(playlist.id().unwrap().as_str(), "PLfixture");
+    assert!(!playlist.is_mix());
+}
+
+#[tokio::test]
+async fn videos_stream_without_continuation() {
+    let mut initial_data: serde_json::Value = serde_json::from_str(&fixture("playlist_initial_data.json")).unwrap();
+    initial_data["contents"]["twoColumnBrowseResultsRenderer"]["tabs"][0]["tabRenderer"]["content"][
+        "sectionListRenderer"]["contents"][0]["itemSectionRenderer"]["contents"][0][
+        "playlistVideoListRenderer"]["contents"].as_array_mut().unwrap().pop();
+    let playlist = Playlist::from_initial_data(&initial_data.to_string()).unwrap();
+    assert_eq!(playlist.continuation(), None);
+
+    let ids = playlist
+        .videos_stream()
+        .map(|video| video.unwrap().video_id)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(ids.len(), 6);
+
+    let first = playlist.videos_stream().take(1).collect::<Vec<_>>().await;
+    assert_eq!(first.len(), 1);
+
+    assert_eq!(playlist.pages().collect::<Vec<_>>().await.len(), 0);
+    let owned = playlist
+        .into_videos_stream()
+        .map(|video| video.unwrap().video_id)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(owned, ids);
+}
+
+#[test]
+fn bad_initial_data() {
+    assert!(Playlist::from_initial_data("{}").is_err());
+}
+
+#[test]
+fn info_from_sidebar() {
+    let info = Playlist::from_initial_data(&fixture("playlist_initial_data.json"))
+        .unwrap()
+        .playlist_info();
+
+    assert_eq!(info.owner_name.as_deref(), Some("Fixture Channel"));
+    assert_eq!(info.owner_channel_id.as_deref(), Some("UCBR8-60-B28hp2BmDPdntcQ"));
+    assert_eq!(info.video_count, Some(1024));
+    assert_eq!(info.view_count, Some(56_789));
+    assert_eq!(info.last_updated_text.as_deref(), Some("Last updated on Apr 5, 2021"));
+    assert_eq!(info.last_updated, chrono::NaiveDate::from_ymd_opt(2021, 4, 5));
+    assert_eq!(info.privacy, Some(PlaylistPrivacy::Unlisted));
+    assert!(info.unavailable_videos_hidden);
+}
+
+#[test]
+fn info_from_header() {
+    let info = Playlist::from_initial_data(&fixture("playlist_initial_data_header.json"))
+        .unwrap()
+        .playlist_info();
+
+    assert_eq!(info.owner_name.as_deref(), Some("Header Channel"));
+    assert_eq!(info.owner_channel_id.as_deref(), Some("UCheaderchannel00000000a"));
+    assert_eq!(info.video_count, Some(12));
+    assert_eq!(info.view_count, Some(1500));
+    assert_eq!(info.last_updated_text.as_deref(), Some("Updated today"));
+    assert_eq!(info.last_updated, None);
+    assert_eq!(info.privacy, Some(PlaylistPrivacy::Private));
+    assert!(!info.unavailable_videos_hidden);
+}
+
+#[test]
+fn info_from_microformat_only() {
+    let mut initial_data: serde_json::Value = serde_json::from_str(&fixture("playlist_initial_data.json")).unwrap();
+    initial_data["sidebar"] = serde_json::Value::Null;
+    let info = Playlist::from_initial_data(&initial_data.to_string())
+        .unwrap()
+        .playlist_info();
+
+    assert_eq!(info.title, "Fixture playlist");
+    assert_eq!(info.owner_name, None);
+    assert_eq!(info.video_count, None);
+    assert_eq!(info.privacy, None);
+}
+
+#[test]
+fn video_details_and_availability() {
+    let playlist = Playlist::from_initial_data(&fixture("playlist_initial_data.json")).unwrap();
+    let videos = playlist.videos();
+
+    let first = &videos[0];
+    assert!(first.is_available());
+    assert!(first.is_playable);
+    assert_eq!(first.author, "Fixture Channel");
+    assert_eq!(first.owner_channel_id.as_deref(), Some("UCBR8-60-B28hp2BmDPdntcQ"));
+    assert_eq!(first.set_video_id.as_deref(), Some("SET1"));
+    assert_eq!(first.view_count_text.as_deref(), Some("1.2K views"));
+    assert_eq!(first.published_time_text.as_deref(), Some("2 years ago"));
+
+    let availability = videos
+        .iter()
+        .map(|video| video.availability)
+        .collect::<Vec<_>>();
+    assert_eq!(availability, [
+        Availability::Available,
+        Availability::Available,
+        Availability::Private,
+        Availability::Deleted,
+        Availability::RegionBlocked,
+        Availability::MembersOnly,
+    ]);
+
+    let deleted = &videos[3];
+    assert_eq!(deleted.title, "[Deleted video]");
+    assert_eq!(deleted.length_seconds, None);
+    assert_eq!(deleted.author, "");
+    assert_eq!(deleted.owner_channel_id, None);
+    assert_eq!(videos[4].owner_channel_id.as_deref(), Some("UCsomechannel0000000000a"));
+}
+
+#[test]
+fn playlist_id_format() {
+    for (id, kind) in [
+        ("PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I", PlaylistKind::Playlist),
+        ("UUBR8-60-B28hp2BmDPdntcQ", PlaylistKind::Uploads),
+        ("FLBR8-60-B28hp2BmDPdntcQ", PlaylistKind::Favorites),
+        ("RDjNQXAC9IVRw", PlaylistKind::Mix),
+        ("OLAK5uy_kq-dBbd6pJRUtXtCwyRgXBvJHkPDSwnv8", PlaylistKind::Album),
+        ("LL", PlaylistKind::Liked),
+    ] {
+        assert_eq!(PlaylistId::from_str(id).unwrap().kind(), kind, "{}", id);
+    }
+
+    for id in ["", "PL", "XXHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I", "PLHFlHpPjgk70Yv3kx!vkDEO5n5tMQia5I", "jNQXAC9IVRw"] {
+        assert!(PlaylistId::from_str(id).is_err(), "{}", id);
+    }
+
+    let id = PlaylistId::from_str("PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I").unwrap();
+    assert_eq!(id.playlist_url().as_str(), "https://www.youtube.com/playlist?list=PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I");
+    assert_eq!(id.embed_url().as_str(), "https://www.youtube.com/embed/videoseries?list=PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I");
+    assert_eq!(
+        id.watch_url(Id::from_str("jNQXAC9IVRw").unwrap()).as_str(),
+        "https://www.youtube.com/watch?v=jNQXAC9IVRw&list=PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I",
+    );
+
+    let id: PlaylistIdBuf = serde_json::from_str("\"RDjNQXAC9IVRw\"").unwrap();
+    assert_eq!(id.as_str(), "RDjNQXAC9IVRw");
+    assert!(serde_json::from_str::<PlaylistIdBuf>("\"jNQXAC9IVRw\"").is_err());
+}
+
+#[test]
+fn album_from_watch_panel() {
+    let id = PlaylistId::from_str("OLAK5uy_k5JWBq7NYpLd6T7Wx0M2CeWzamuDaHeDI").unwrap();
+    let next = fixture("next_playlist_panel_album.json");
+    let album = Playlist::from_watch_panel_data(
+        id.as_borrowed(),
+        &fixture("watch_playlist_panel_album.json"),
+        [next.as_str()],
+        usize::MAX,
+    ).unwrap();
+
+    // the index of album videos is their track number
+    let tracks = album
+        .videos()
+        .iter()
+        .map(|video| (video.index, video.video_id.as_str(), video.title.as_str(), video.length_seconds))
+        .collect::<Vec<_>>();
+    assert_eq!(tracks, [
+        (1, "5jlI4uzZGjU", "Intro", Some(65)),
+        (2, "JgGuRKgvWQ4", "Second track", Some(210)),
+        (3, "2lAe1cqCOXo", "Removed track", None),
+        (4, "QRS8MkLhQmM", "Outro", Some(284)),
+    ]);
+    assert!(!album.videos()[2].is_playable);
+    assert_eq!(album.videos()[2].availability, Availability::Unavailable);
+    assert_eq!(album.videos()[0].author, "Fixture Artist");
+    assert_eq!(album.videos()[0].owner_channel_id.as_deref(), Some("UCBR8-60-B28hp2BmDPdntcQ"));
+    assert_eq!(album.playlist_info().title, "Fixture Album");
+    assert_eq!(album.playlist_info().video_count, Some(4));
+    assert!(album.is_album());
+
+    // without the second window, the album is incomplete
+    let first_window = Playlist::from_watch_panel_data(id, &fixture("watch_playlist_panel_album.json"), [], usize::MAX).unwrap();
+    assert_eq!(first_window.videos().len(), 3);
+}
+
+#[test]
+fn mix_from_watch_panel() {
+    let id = PlaylistId::from_str("RD5jlI4uzZGjU").unwrap();
+    let watch = fixture("watch_playlist_panel_mix.json");
+    let next = fixture("next_playlist_panel_mix.json");
+
+    // the windows overlap, so the duplicates are removed
+    let mix = Playlist::from_watch_panel_data(id.as_borrowed(), &watch, [next.as_str()], 100).unwrap();
+    let videos = mix
+        .videos()
+        .iter()
+        .map(|video| (video.index, video.video_id.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(videos, [
+        (1, "5jlI4uzZGjU"),
+        (2, "JgGuRKgvWQ4"),
+        (3, "xQDsI2ptfgg"),
+        (4, "MAoOAa_izh0"),
+        (5, "qG7kqns7SVM"),
+    ]);
+    assert_eq!(mix.playlist_info().title, "Mix - Fixture");
+    assert_eq!(mix.playlist_info().video_count, None);
+    assert_eq!(mix.playlist_info().thumbnails[0].url, "https://i.ytimg.com/vi/5jlI4uzZGjU/default.jpg");
+    assert!(mix.is_mix());
+
+    // a window without new videos ends the mix
+    let mix = Playlist::from_watch_panel_data(id.as_borrowed(), &watch, [next.as_str(), next.as_str(), watch.as_str()], 100).unwrap();
+    assert_eq!(mix.videos().len(), 5);
+
+    // no further windows are used, once `max_length` is reached
+    let mix = Playlist::from_watch_panel_data(id.as_borrowed(), &watch, [next.as_str()], 4).unwrap();
+    assert_eq!(mix.videos().len(), 4);
+    assert_eq!(mix.videos().last().unwrap().index, 4);
+    let mix = Playlist::from_watch_panel_data(id, &watch, ["not json"], 2).unwrap();
+    assert_eq!(mix.videos().len(), 2);
+}
+
+#[test]
+fn bad_watch_panel_data() {
+    let id = PlaylistId::from_str("RD5jlI4uzZGjU").unwrap();
+    assert!(Playlist::from_watch_panel_data(id.as_borrowed(), "{}", [], 100).is_err());
+    assert!(Playlist::from_watch_panel_data(id, &fixture("watch_playlist_panel_mix.json"), ["{}"], 100).is_err());
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "std")]
+
+use rustube::{ChannelId, Id, PlaylistId, Resource};
+use rustube::resource::ChannelRef;
+
+const ID: &str = "jNQXAC9IVRw";
+
+fn video(start_seconds: Option<u64>, playlist_id: Option<&str>, playlist_index: Option<u64>) -> Resource {
+    Resource::Video {
+        id: Id::from_str(ID).unwrap().into_owned(),
+        start_seconds,
+        playlist_id: playlist_id.map(|id| PlaylistId::from_str(id).unwrap().into_owned()),
+        playlist_index,
+    }
+}
+
+#[test]
+fn videos() {
+    for url in [
+        ID,
+        "https://www.youtube.com/watch?v=jNQXAC9IVRw",
+        "youtube.com/watch?v=jNQXAC9IVRw&feature=share",
+        "https://m.youtube.com/watch?v=jNQXAC9IVRw",
+        "https://music.youtube.com/watch?v=jNQXAC9IVRw&si=abcdefgh",
+        "https://www.youtube.com/shorts/jNQXAC9IVRw?feature=share",
+        "https://www.youtube.com/embed/jNQXAC9IVRw",
+        "https://www.youtube-nocookie.com/embed/jNQXAC9IVRw?rel=0",
+        "https://www.youtube.com/v/jNQXAC9IVRw?version=3",
+        "https://youtu.be/jNQXAC9IVRw?si=gF4qbqCO0tSZ1s0d",
+        "https://www.youtube.com/attribution_link?a=abc&u=/watch%3Fv%3DjNQXAC9IVRw%26feature%3Dshare",
+        "https://www.google.com/url?sa=t&url=https%3A%2F%2Fwww.youtube.com%2Fwatch%3Fv%3DjNQXAC9IVRw",
+        "https%3A%2F%2Fyoutu.be%2FjNQXAC9IVRw",
+        "https://consent.youtube.com/m?continue=https%3A%2F%2Fwww.youtube.com%2Fwatch%3Fv%3DjNQXAC9IVRw",
+    ] {
+        assert_eq!(Resource::parse(url).unwrap(), video(None, None, None), "{}", url);
+    }
+}
+
+#[test]
+fn start_times_and_playlist_context() {
+    for (url, start) in [
+        ("https://youtu.be/jNQXAC9IVRw?t=90", 90),
+        ("https://youtu.be/jNQXAC9IVRw?t=90s", 90),
+        ("https://www.youtube.com/watch?v=jNQXAC9IVRw&t=1h2m3s", 3723),
+        ("https://www.youtube.com/watch?v=jNQXAC9IVRw&t=2m", 120),
+        ("https://www.youtube.com/embed/jNQXAC9IVRw?start=15", 15),
+        ("https://www.youtube.com/watch?v=jNQXAC9IVRw#t=1m5s", 65),
+    ] {
+        assert_eq!(Resource::parse(url).unwrap(), video(Some(start), None, None), "{}", url);
+    }
+    assert_eq!(Resource::parse("https://youtu.be/jNQXAC9IVRw?t=later").unwrap(), video(None, None, None));
+    assert_eq!(Resource::parse("https://youtu.be/jNQXAC9IVRw?t=9999999999999999h").unwrap(), video(None, None, None));
+    assert_eq!(Resource::parse("https://youtu.be/jNQXAC9IVRw?t=18446744073709551615m").unwrap(), video(None, None, None));
+
+    assert_eq!(
+        Resource::parse("https://www.youtube.com/watch?v=jNQXAC9IVRw&list=PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I&index=4&t=10").unwrap(),
+        video(Some(10), Some("PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I"), Some(4)),
+    );
+}
+
+#[test]
+fn playlists() {
+    let playlist = Resource::Playlist { id: PlaylistId::from_str("PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I").unwrap().into_owned() };
+    for url in [
+        "PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I",
+        "https://www.youtube.com/playlist?list=PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I",
+        "https://m.youtube.com/playlist?list=PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I&si=abc",
+        "https://www.youtube.com/watch?list=PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I",
+        "https://www.youtube-nocookie.com/embed/videoseries?list=PLHFlHpPjgk70Yv3kxQvkDEO5n5tMQia5I",
+    ] {
+        assert_eq!(Resource::parse(url).unwrap(), playlist, "{}", url);
+    }
+}
+
+#[test]
+fn channels() {
+    let id = ChannelRef::Id(ChannelId::from_str("UCBR8-60-B28hp2BmDPdntcQ").unwrap().into_owned());
+    for url in [
+        "UCBR8-60-B28hp2BmDPdntcQ",
+        "https://www.youtube.com/channel/UCBR8-60-B28hp2BmDPdntcQ",
+        "https://music.youtube.com/channel/UCBR8-60-B28hp2BmDPdntcQ/videos",
+    ] {
+        assert_eq!(Resource::parse(url).unwrap(), Resource::Channel(id.clone()), "{}", url);
+    }
+
+    let handle = Resource::Channel(ChannelRef::Handle("@YouTube".to_owned()));
+    assert_eq!(Resource::parse("@YouTube").unwrap(), handle);
+    assert_eq!(Resource::parse("https://m.youtube.com/@YouTube/videos?si=abc").unwrap(), handle);
+
+    assert_eq!(Resource::parse("https://www.youtube.com/c/YouTubeCreators").unwrap(), Resource::Channel(ChannelRef::Custom("YouTubeCreators".to_owned())));
+    assert_eq!(Resource::parse("https://www.youtube.com/user/YouTube").unwrap(), Resource::Channel(ChannelRef::User("YouTube".to_owned())));
+    assert_eq!(Resource::parse("https://www.youtube.com/YouTube").unwrap(), Resource::Channel(ChannelRef::Custom("YouTube".to_owned())));
+    assert_eq!(ChannelRef::User("YouTube".to_owned()).url(), "https://www.youtube.com/user/YouTube");
+}
+
+#[test]
+fn clips_and_live_streams() {
+    assert_eq!(
+        Resource::parse("https://youtube.com/clip/UgkxU2HSeGL_NvmDJ-nQJrlLwllwMDBdGZFs?si=abc").unwrap(),
+        Resource::Clip { id: "UgkxU2HSeGL_NvmDJ-nQJrlLwllwMDBdGZFs".to_owned() },
+    );
+    assert_eq!(
+        Resource::parse("https://www.youtube.com/live/jNQXAC9IVRw?feature=share").unwrap(),
+        Resource::Live { video_id: Some(Id::from_str(ID).unwrap().into_owned()), channel: None },
+    );
+    assert_eq!(
+        Resource::parse("https://www.youtube.com/@YouTube/live").unwrap(),
+        Resource::Live { video_id: None, channel: Some(ChannelRef::Handle("@YouTube".to_owned())) },
+    );
+}
+
+#[test]
+fn invalid_resources() {
+    for input in [
+        "",
+        "jNQXAC9IVR",
+        "https://example.com/watch?v=jNQXAC9IVRw",
+        "https://www.youtube.com/watch?v=invalid",
+        "https://www.youtube.com/results?search_query=rust",
+        "https://www.youtube.com/",
+        "https://www.google.com/url?q=https%3A%2F%2Fexample.com",
+    ] {
+        assert!(Resource::parse(input).is_err(), "{}", input);
+    }
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "fetch")]
+
+use rustube::search::{self, Feature, ResultType, Search, SortBy, UploadDate, VideoDuration};
+use rustube::search_info::{SearchPage, SearchResult};
+
+#[macro_use]
+mod common;
+
+#[test]
+fn filter_params() {
+    assert_eq!(Search::new("rust").filter.params(), None);
+    assert_eq!(Search::new("rust").result_type(ResultType::Video).filter.params().as_deref(), Some("EgIQAQ=="));
+    assert_eq!(Search::new("rust").result_type(ResultType::Movie).filter.params().as_deref(), Some("EgIQBA=="));
+    assert_eq!(Search::new("rust").sort_by(SortBy::UploadDate).filter.params().as_deref(), Some("CAI="));
+    assert_eq!(Search::new("rust").upload_date(UploadDate::ThisWeek).filter.params().as_deref(), Some("EgIIAw=="));
+    assert_eq!(Search::new("rust").duration(VideoDuration::Long).filter.params().as_deref(), Some("EgIYAg=="));
+    assert_eq!(Search::new("rust").feature(Feature::Live).filter.params().as_deref(), Some("EgJAAQ=="));
+    // features with a field number >= 16 need a two byte key
+    assert_eq!(Search::new("rust").feature(Feature::Hdr).filter.params().as_deref(), Some("EgPIAQE="));
+
+    let search = Search::new("rust")
+        .result_type(ResultType::Video)
+        .feature(Feature::Hd)
+        .feature(Feature::Hd)
+        .sort_by(SortBy::ViewCount);
+    assert_eq!(search.filter.params().as_deref(), Some("CAMSBBABIAE="));
+}
+
+#[test]
+fn first_page() {
+    let page = SearchPage::from_json(&common::fixture("search_response.json")).unwrap();
+    assert_eq!(page.estimated_results, Some(123_456));
+    assert_eq!(page.continuation.as_deref(), Some("search-page-2"));
+
+    let correction = page.correction.unwrap();
+    assert_eq!(correction.corrected_query, "rust programming");
+    assert!(correction.is_auto_corrected);
+
+    assert_eq!(page.results.len(), 5);
+    let channel = match &page.results[0] {
+        SearchResult::Channel(channel) => channel,
+        result => panic!("expected a channel, got {:?}", result),
+    };
+    assert_eq!(channel.channel_id.as_str(), "UCaYhcUwRBNscFNUKTjgPFiA");
+    assert_eq!(channel.handle.as_deref(), Some("@rustvideos"));
+    assert_eq!(channel.subscriber_count, Some(151_000));
+    assert_eq!(channel.description_snippet, "The official channel of the Rust programming language.");
+
+    let video = match &page.results[1] {
+        SearchResult::Video(video) => video,
+        result => panic!("expected a video, got {:?}", result),
+    };
+    assert_eq!(video.video_id.as_str(), "jNQXAC9IVRw");
+    assert_eq!(video.title, "Rust in 100 Seconds");
+    assert_eq!(video.author, "Fireship");
+    assert_eq!(video.channel_id.as_ref().unwrap().as_str(), "UCsBjURrPoezykLs9EqgamOA");
+    assert_eq!(video.length_seconds, Some(149));
+    assert_eq!(video.view_count, Some(2_345_678));
+    assert_eq!(video.published_time_text.as_deref(), Some("3 years ago"));
+    assert_eq!(video.description_snippet, "Rust is a memory-safe language.");
+    assert!(!video.is_live);
+
+    // the video of the shelf
+    let live = match &page.results[2] {
+        SearchResult::Video(video) => video,
+        result => panic!("expected a video, got {:?}", result),
+    };
+    assert!(live.is_live);
+    assert_eq!(live.length_seconds, None);
+
+    let playlist = match &page.results[3] {
+        SearchResult::Playlist(playlist) => playlist,
+        result => panic!("expected a playlist, got {:?}", result),
+    };
+    assert_eq!(playlist.playlist_id.as_str(), "PLai5B987bZ9CoVR-QEIN9foz4QCJ0H2Y8");
+    assert_eq!(playlist.author.as_deref(), Some("Jon Gjengset"));
+    assert_eq!(playlist.video_count, Some(22));
+    assert_eq!(playlist.thumbnails.len(), 1);
+
+    let movie = match &page.results[4] {
+        SearchResult::Movie(movie) => movie,
+        result => panic!("expected a movie, got {:?}", result),
+    };
+    assert_eq!(movie.length_seconds, Some(8341));
+    assert_eq!(movie.channel_id, None);
+}
+
+#[test]
+fn continuation_page() {
+    let page = SearchPage::from_json(&common::fixture("search_continuation.json")).unwrap();
+    assert_eq!(page.continuation, None);
+
+    let correction = page.correction.unwrap();
+    assert_eq!(correction.corrected_query, "rust lang");
+    assert!(!correction.is_auto_corrected);
+
+    assert_eq!(page.results.len(), 1);
+    match &page.results[0] {
+        SearchResult::Video(video) => {
+            assert_eq!(video.length_seconds, Some(3723));
+            assert_eq!(video.view_count, None);
+        }
+        result => panic!("expected a video, got {:?}", result),
+    }
+}
+
+#[test]
+fn unexpected_response() {
+    assert!(SearchPage::from_json("{}").is_err());
+    assert!(SearchPage::from_json("[").is_err());
+}
+
+#[test]
+fn suggestions_from_jsonp() {
+    let suggestions = search::parse_suggestions(&common::fixture("search_suggestions.jsonp")).unwrap();
+    assert_eq!(suggestions, [
+        "rust",
+        "rust programming",
+        "rust gameplay",
+        "rust (ä) \"quoted\"",
+        "rustacean",
+    ]);
+
+    // the firefox client responds with plain json
+    assert_eq!(search::parse_suggestions(r#"["rust",["rust","rust (game)"]]"#).unwrap(), ["rust", "rust (game)"]);
+    assert!(search::parse_suggestions("window.google.ac.h({})").is_err());
+    assert!(search::parse_suggestions("").is_err());
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_search_videos() {
+    // the old entry point is still exported at the crate root
+    let _search_videos = rustube::search_videos;
+}
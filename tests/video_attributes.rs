@@ -1,7 +1,10 @@
 #![cfg(feature = "stream")]
 
+use chrono::{DateTime, Duration, Utc};
+
 use common::*;
 use rustube::{Error, VideoFetcher};
+use rustube::video_info::player_response::streaming_data::SignatureCipher;
 use rustube::video_info::player_response::playability_status::PlayabilityStatus;
 
 #[macro_use]
@@ -25,6 +28,64 @@ async fn video_is_pre_signed() {
     assert!(random_entry(video.streams()).signature_cipher.s.is_none());
 }
 
+fn now() -> DateTime<Utc> {
+    DateTime::from(std::time::SystemTime::now())
+}
+
+#[test_log::test(tokio::test)]
+#[ignore]
+async fn streams_expire() {
+    let id = random_id(PRE_SIGNED);
+    let video = video!(id);
+
+    for stream in video.streams() {
+        let expires_at = stream.expires_at.expect("every stream url expires");
+        assert!(expires_at > now() && expires_at < now() + Duration::days(1));
+        assert!(!stream.is_expired());
+    }
+}
+
+#[test]
+fn stream_expiry() {
+    const URL: &str = "https://rr1---sn-4g5e6nzz.googlevideo.com/videoplayback?itag=18";
+    assert!(stream_fixture(URL, Some(now() - Duration::minutes(1))).is_expired());
+    assert!(!stream_fixture(URL, Some(now() + Duration::hours(6))).is_expired());
+    assert!(!stream_fixture(URL, None).is_expired());
+}
+
+#[test]
+fn url_expiry() {
+    let cipher = |url: &str| SignatureCipher { url: url.parse().unwrap(), s: None };
+    let expires_at = DateTime::from_timestamp(1700000000, 0);
+
+    assert_eq!(
+        cipher("https://rr1---sn-4g5e6nzz.googlevideo.com/videoplayback?expire=1700000000&itag=18").expires_at(),
+        expires_at,
+    );
+    assert_eq!(
+        cipher("https://manifest.googlevideo.com/api/manifest/hls_playlist/expire/1700000000/ei/abc/itag/95/index.m3u8").expires_at(),
+        expires_at,
+    );
+    assert_eq!(cipher("https://rr1---sn-4g5e6nzz.googlevideo.com/videoplayback?itag=18").expires_at(), None);
+    assert_eq!(cipher("https://rr1---sn-4g5e6nzz.googlevideo.com/videoplayback?expire=soon").expires_at(), None);
+    assert_eq!(cipher("https://manifest.googlevideo.com/api/manifest/hls_playlist/expire").expires_at(), None);
+}
+
+#[test]
+fn stream_expiry_uses_the_earlier_time() {
+    let url = |expires_at: DateTime<Utc>| format!(
+        "https://rr1---sn-4g5e6nzz.googlevideo.com/videoplayback?expire={}&itag=18",
+        expires_at.timestamp(),
+    );
+
+    // the url expires before the streaming data
+    assert!(stream_fixture(&url(now() - Duration::minutes(1)), Some(now() + Duration::hours(6))).is_expired());
+    assert!(stream_fixture(&url(now() - Duration::minutes(1)), None).is_expired());
+    // the streaming data expires before the url
+    assert!(stream_fixture(&url(now() + Duration::hours(6)), Some(now() - Duration::minutes(1))).is_expired());
+    assert!(!stream_fixture(&url(now() + Duration::hours(6)), Some(now() + Duration::hours(5))).is_expired());
+}
+
 #[test_log::test(tokio::test)]
 #[ignore]
 async fn video_is_age_restricted() {
@@ -47,13 +108,8 @@ async fn video_is_private_video() {
     );
 
     match res.unwrap_err() {
-        Error::VideoUnavailable(ps) => {
-            match *ps {
-                PlayabilityStatus::LoginRequired { .. } => {}
-                ps => panic!("expected LoginRequired, got: {:?}", ps)
-            }
-        }
-        e => panic!("expected Error::VideoUnavailable, got: {:?}", e)
+        Error::Private { .. } => {}
+        e => panic!("expected Error::Private, got: {:?}", e)
     }
 }
 
@@ -70,13 +126,8 @@ async fn video_is_region_blocked() {
     );
 
     match res.unwrap_err() {
-        Error::VideoUnavailable(ps) => {
-            match *ps {
-                PlayabilityStatus::LoginRequired { .. } => {}
-                ps => panic!("expected LoginRequired, got: {:?}", ps)
-            }
-        }
-        e => panic!("expected Error::VideoUnavailable, got: {:?}", e)
+        Error::GeoBlocked { .. } => {}
+        e => panic!("expected Error::GeoBlocked, got: {:?}", e)
     }
 }
 
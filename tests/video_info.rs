@@ -0,0 +1,303 @@
+#![cfg(feature = "fetch")]
+
+use common::*;
+
+#[macro_use]
+mod common;
+
+#[test]
+fn chapters_from_initial_data() {
+    let video_info = video_info_fixture(Some("watch_initial_data.json"));
+    let chapters = video_info.chapters();
+
+    let chapters = chapters
+        .iter()
+        .map(|c| (c.title.as_str(), c.start_ms, c.end_ms))
+        .collect::<Vec<_>>();
+    assert_eq!(chapters, [("Intro", 0, 42_000), ("Setup", 42_000, 420_000), ("Result", 420_000, 600_000)]);
+    assert_eq!(video_info.chapters()[0].thumbnails.len(), 1);
+}
+
+#[test]
+fn chapters_from_engagement_panel() {
+    let mut video_info = video_info_fixture(Some("watch_initial_data.json"));
+    video_info.initial_data.as_mut().unwrap()["playerOverlays"] = serde_json::Value::Null;
+
+    let starts = video_info
+        .chapters()
+        .iter()
+        .map(|c| c.start_ms)
+        .collect::<Vec<_>>();
+    assert_eq!(starts, [0, 42_000, 420_000]);
+}
+
+#[test]
+fn chapters_from_description() {
+    let video_info = video_info_fixture(None);
+    let chapters = video_info.chapters();
+
+    let chapters = chapters
+        .iter()
+        .map(|c| (c.title.as_str(), c.start_ms, c.end_ms))
+        .collect::<Vec<_>>();
+    assert_eq!(chapters, [("Intro", 0, 90_000), ("The middle part", 90_000, 300_000), ("Outro", 300_000, 600_000)]);
+}
+
+#[test]
+fn storyboard_levels() {
+    let storyboard = video_info_fixture(None)
+        .storyboard()
+        .unwrap()
+        .expect("the fixture contains a storyboard spec");
+
+    assert_eq!(storyboard.levels.len(), 3);
+    let first = &storyboard.levels[0];
+    assert_eq!((first.tile_width, first.tile_height, first.interval_ms), (48, 27, 6_000));
+    assert_eq!(first.sheet_count(), 1);
+    assert_eq!(
+        first.sheet_url(0).unwrap().as_str(),
+        "https://i.ytimg.com/sb/5jlI4uzZGjU/storyboard3_L0/default.jpg?sqp=-oaymwENSDfyq4qpAwVwAcABBqLzl_8DBgjY2ae1Bg==&sigh=rs$AOn4CLD1",
+    );
+
+    let best = storyboard.best_level().unwrap();
+    assert_eq!(best.level, 2);
+    assert_eq!(best.sheet_count(), 3);
+}
+
+#[test]
+fn storyboard_tile_geometry() {
+    let storyboard = video_info_fixture(None)
+        .storyboard()
+        .unwrap()
+        .unwrap();
+    let level = &storyboard.levels[2];
+
+    let tile = level.tile_at(125_000).unwrap();
+    assert_eq!((tile.sheet, tile.x, tile.y, tile.width, tile.height), (0, 320, 180, 160, 90));
+
+    let tile = level.tile_at(300_000).unwrap();
+    assert_eq!((tile.sheet, tile.x, tile.y), (1, 0, 90));
+    assert!(tile.url.as_str().contains("/storyboard3_L2/M1.jpg?"));
+
+    // timestamps after the end of the video map to the last tile
+    let tile = level.tile_at(u64::MAX).unwrap();
+    assert_eq!((tile.sheet, tile.x, tile.y), (2, 0, 180));
+}
+
+#[test]
+fn storyboard_bad_spec() {
+    use rustube::video_info::player_response::storyboard::Storyboard;
+
+    assert!(Storyboard::from_spec("", 1000).is_err());
+    assert!(Storyboard::from_spec("https://i.ytimg.com/sb/x/$L/$N.jpg|48#27", 1000).is_err());
+    assert!(Storyboard::from_spec("https://i.ytimg.com/sb/x/$L/$N.jpg|48#27#0#10#10#0#default#sigh", 1000).is_err());
+}
+
+#[tokio::test]
+async fn related_videos_first_page() {
+    use futures::StreamExt;
+
+    let video_info = video_info_fixture(Some("watch_initial_data.json"));
+    let related = video_info
+        .related_videos()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(related.len(), 2);
+    assert_eq!(related[0].video_id.as_str(), "JgGuRKgvWQ4");
+    assert_eq!(related[0].title, "A related video");
+    assert_eq!(related[0].author, "Some Channel");
+    assert_eq!(related[0].channel_id.as_deref(), Some("UC_x5XG1OV2P6uZZ5FSM9Ttw"));
+    assert_eq!(related[0].length_seconds, Some(3723));
+    assert_eq!(related[0].view_count, Some(1_234_567));
+    assert_eq!(related[0].published_time_text.as_deref(), Some("3 years ago"));
+    assert!(!related[0].is_live);
+
+    assert!(related[1].is_live);
+    assert_eq!(related[1].length_seconds, None);
+    assert_eq!(related[1].view_count, Some(1500));
+}
+
+#[tokio::test]
+async fn related_lockup_videos() {
+    use futures::StreamExt;
+
+    let video_info = video_info_fixture(Some("watch_initial_data_lockup.json"));
+    // the continuation would be requested after the first page
+    let related = video_info
+        .related_videos()
+        .take(2)
+        .map(Result::unwrap)
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(related[0].video_id.as_str(), "JgGuRKgvWQ4");
+    assert_eq!(related[0].title, "A related video");
+    assert_eq!(related[0].author, "Some Channel");
+    assert_eq!(related[0].channel_id.as_deref(), Some("UC_x5XG1OV2P6uZZ5FSM9Ttw"));
+    assert_eq!(related[0].length_seconds, Some(3723));
+    assert_eq!(related[0].view_count, Some(1_234_567));
+    assert_eq!(related[0].published_time_text.as_deref(), Some("3 years ago"));
+    assert_eq!(related[0].thumbnails.len(), 1);
+    assert!(!related[0].is_live);
+
+    // the mix in between is skipped
+    assert_eq!(related[1].video_id.as_str(), "ASGNUnPINdM");
+    assert!(related[1].is_live);
+    assert_eq!(related[1].author, "Live Channel");
+    assert_eq!(related[1].channel_id, None);
+    assert_eq!(related[1].length_seconds, None);
+    assert_eq!(related[1].view_count, Some(1500));
+    assert_eq!(related[1].published_time_text, None);
+}
+
+#[test]
+fn endscreen_elements() {
+    use rustube::video_info::player_response::endscreen::EndscreenElementStyle;
+
+    let video_info = video_info_fixture(None);
+    let elements = video_info
+        .endscreen()
+        .iter()
+        .map(|e| (e.style, e.target_id(), e.start_ms, e.end_ms))
+        .collect::<Vec<_>>();
+
+    assert_eq!(elements, [
+        (EndscreenElementStyle::Video, Some("JgGuRKgvWQ4"), 580_000, 600_000),
+        (EndscreenElementStyle::Playlist, Some("PLbpi6ZahtOH6Blw3RGYpWkSByi_T7Rygb"), 580_000, 600_000),
+        (EndscreenElementStyle::Channel, Some("UCBR8-60-B28hp2BmDPdntcQ"), 585_000, 600_000),
+        (EndscreenElementStyle::Other, Some("https://example.com/shop"), 590_000, 600_000),
+    ]);
+    assert_eq!(video_info.endscreen()[1].title.as_deref(), Some("A playlist"));
+}
+
+#[test]
+fn metadata_from_initial_data() {
+    let metadata = video_info_fixture(Some("watch_initial_data.json")).metadata();
+
+    assert_eq!(metadata.title, "Fixture video");
+    assert_eq!(metadata.view_count, 3_141_592_653);
+    assert_eq!(metadata.like_count, Some(12_345));
+    assert_eq!(metadata.keywords, ["fixture", "rustube"]);
+    assert_eq!(metadata.hashtags, ["#rustube", "#fixtures", "#hashtag", "#Rust"]);
+    assert_eq!(metadata.category.as_deref(), Some("Science & Technology"));
+    assert_eq!(metadata.license.as_deref(), Some("Creative Commons Attribution license (reuse allowed)"));
+    assert!(metadata.is_premiere);
+    assert!(!metadata.is_members_only);
+
+    let links = metadata.description
+        .iter()
+        .filter(|run| run.url.is_some())
+        .map(|run| run.text.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(links, ["https://example.com", "#hashtag"]);
+    assert!(metadata.plain_description().starts_with("Fixture description with a link: https://example.com and"));
+}
+
+#[test]
+fn metadata_without_initial_data() {
+    let metadata = video_info_fixture(None).metadata();
+
+    assert_eq!(metadata.like_count, None);
+    assert_eq!(metadata.license, None);
+    assert!(!metadata.is_premiere);
+    assert_eq!(metadata.description.len(), 1);
+    assert!(metadata.plain_description().contains("1:30 - The middle part"));
+}
+
+#[test]
+#[cfg(feature = "microformat")]
+fn metadata_from_microformat() {
+    let metadata = video_info_fixture(None).metadata();
+
+    assert_eq!(metadata.publish_date.unwrap().to_rfc3339(), "2021-04-05T10:00:00-07:00");
+    assert_eq!(metadata.upload_date.unwrap().to_rfc3339(), "2021-04-05T00:00:00+00:00");
+    assert_eq!(metadata.category.as_deref(), Some("Science & Technology"));
+    assert_eq!(metadata.is_family_safe, Some(true));
+    assert_eq!(metadata.is_unlisted, Some(false));
+    assert_eq!(metadata.is_short, Some(false));
+}
+
+#[test]
+#[cfg(feature = "microformat")]
+fn available_countries() {
+    let mut video_info = video_info_fixture(None);
+
+    assert!(video_info.is_available_in("DE"));
+    assert!(video_info.is_available_in("us"));
+    assert!(!video_info.is_available_in("FR"));
+
+    video_info.player_response.microformat = None;
+    assert!(video_info.is_available_in("FR"));
+}
+
+#[test]
+fn heatmap_from_entity_mutations() {
+    let heatmap = video_info_fixture(Some("watch_initial_data.json"))
+        .heatmap()
+        .unwrap();
+
+    // the marker without a start is skipped
+    assert_eq!(heatmap.markers.len(), 10);
+    assert_eq!(heatmap.markers[2].start_ms, 120_000);
+    assert_eq!(heatmap.markers[2].end_ms(), 180_000);
+    assert_eq!(heatmap.markers[2].intensity, 0.5);
+}
+
+#[test]
+fn heatmap_from_heatseeker_markers() {
+    let heatmap = video_info_fixture(Some("watch_initial_data_heatseeker.json"))
+        .heatmap()
+        .unwrap();
+
+    let starts = heatmap.markers
+        .iter()
+        .map(|marker| marker.start_ms)
+        .collect::<Vec<_>>();
+    assert_eq!(starts, [0, 300_000]);
+    assert_eq!(heatmap.top_peaks(5), vec![0..300_000]);
+}
+
+#[test]
+fn heat_marker_end_overflow() {
+    use rustube::video_info::heatmap::HeatMarker;
+
+    let marker = HeatMarker { start_ms: u64::MAX - 10, duration_ms: 60_000, intensity: 1.0 };
+    assert_eq!(marker.end_ms(), u64::MAX);
+}
+
+#[test]
+fn heatmap_missing() {
+    assert!(video_info_fixture(None).heatmap().is_none());
+
+    let mut video_info = video_info_fixture(Some("watch_initial_data.json"));
+    video_info.initial_data.as_mut().unwrap()["frameworkUpdates"] = serde_json::json!({ "entityBatchUpdate": {} });
+    assert!(video_info.heatmap().is_none());
+}
+
+#[test]
+fn heatmap_top_peaks() {
+    let heatmap = video_info_fixture(Some("watch_initial_data.json"))
+        .heatmap()
+        .unwrap();
+
+    assert_eq!(heatmap.top_peaks(0), []);
+    assert_eq!(heatmap.top_peaks(2), [0..60_000, 420_000..540_000]);
+    assert_eq!(
+        heatmap.top_peaks(10),
+        [0..60_000, 420_000..540_000, 300_000..360_000, 120_000..240_000],
+    );
+}
+
+#[test]
+fn player_response_round_trip() {
+    use rustube::video_info::player_response::PlayerResponse;
+
+    let player_response: PlayerResponse = serde_json::from_str(&fixture("player_response.json")).unwrap();
+    let json = serde_json::to_string(&player_response).unwrap();
+    let round_trip: PlayerResponse = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(round_trip, player_response);
+    assert!(!round_trip.endscreen.unwrap().endscreen_renderer.elements.is_empty());
+}